@@ -1,4 +1,12 @@
+use bitflip_program::get_coordinates;
+use bitflip_program::get_index_offset;
+use bitflip_program::quote_flip;
 use bitflip_program::FlipBit;
+use bitflip_program::RevealedCell;
+use bitflip_program::SectionMetadataState;
+use bitflip_program::SectionMinesState;
+use bitflip_program::BITFLIP_SECTION_LENGTH;
+use bitflip_program::DEFAULT_FLIP_SLIPPAGE_BASIS_POINTS;
 use js_sys::Reflect;
 use leptos::html::Canvas;
 use leptos::prelude::*;
@@ -7,12 +15,22 @@ use wasm_bindgen::JsValue;
 use web_sys::CanvasRenderingContext2d;
 use web_sys::MouseEvent;
 
+use crate::get_flip_bit_mine;
+use crate::get_game_state;
+use crate::get_section_metadata;
+use crate::get_section_mines;
 use crate::get_section_state;
 use crate::use_game_index;
 use crate::use_section_index;
 
 #[island]
-pub fn SectionCanvas(game_index: Option<u8>, section_index: Option<u8>) -> impl IntoView {
+pub fn SectionCanvas(
+	game_index: Option<u8>,
+	section_index: Option<u8>,
+	/// Render the revealed cells of a minesweeper game.
+	#[prop(optional)]
+	minesweeper: bool,
+) -> impl IntoView {
 	let canvas_ref = NodeRef::<Canvas>::new();
 	let section_index_signal = use_section_index(crate::RouterProp::Value(section_index));
 	let game_index_signal = use_game_index(crate::RouterProp::Value(game_index));
@@ -25,7 +43,24 @@ pub fn SectionCanvas(game_index: Option<u8>, section_index: Option<u8>) -> impl
 			get_section_state(game_index, section_index)
 		},
 	);
+	let game_resource = Resource::new(game_index_signal, get_game_state);
+	let mines_resource = Resource::new(
+		move || (game_index_signal(), section_index_signal()),
+		move |(game_index, section_index)| get_section_mines(game_index, section_index),
+	);
+	let metadata_resource = Resource::new(
+		move || (game_index_signal(), section_index_signal()),
+		move |(game_index, section_index)| get_section_metadata(game_index, section_index),
+	);
 	let section_state = RwSignal::new(None);
+	let section_metadata = Signal::derive(move || {
+		let (game_index, section_index) = (game_index_signal(), section_index_signal());
+
+		match metadata_resource.get() {
+			Some(Ok(section_metadata)) => section_metadata,
+			_ => SectionMetadataState::new(game_index, section_index, 0),
+		}
+	});
 
 	let effect = move || {
 		let context = get_2d_context(canvas_ref);
@@ -37,6 +72,12 @@ pub fn SectionCanvas(game_index: Option<u8>, section_index: Option<u8>) -> impl
 		section_state.set(Some(section));
 		context.set_image_smoothing_enabled(false);
 
+		let section_metadata = section_metadata.get();
+		let foreground = get_color_str(section_metadata.foreground);
+		context.set_fill_style_str(&get_color_str(section_metadata.background));
+		context.fill_rect(0f64, 0f64, 1024f64, 1024f64);
+		context.set_fill_style_str(&foreground);
+
 		for x in 0..16u32 {
 			for y in 0..16u32 {
 				let index = 16 * (x / 4 + (y / 4) * 4) + (x % 4) + (4 * (y % 4));
@@ -49,12 +90,17 @@ pub fn SectionCanvas(game_index: Option<u8>, section_index: Option<u8>) -> impl
 					let x = (4 * x) + offset % 4;
 					let y = (4 * y) + offset / 4;
 
-					context.set_fill_style_str("black");
 					context.fill_rect(f64::from(x * 16), f64::from(y * 16), 16f64, 16f64);
 				}
 			}
 		}
 
+		if minesweeper {
+			if let Some(Ok(section_mines)) = mines_resource.get() {
+				draw_section_mines(&context, &section_mines);
+			}
+		}
+
 		set_show_image(false);
 		let _data = context.get_image_data(0., 0., 1024., 1024.).unwrap();
 	};
@@ -86,37 +132,90 @@ pub fn SectionCanvas(game_index: Option<u8>, section_index: Option<u8>) -> impl
 			offset
 		);
 
-		section_state.update(move |state| {
-			let Some(state) = state else {
-				log::error!("Section state not found");
-				return;
-			};
+		let game_index = game_index_signal();
+		let flip_cell = move |mine: bool| {
+			section_state.update(move |state| {
+				let Some(state) = state else {
+					log::error!("Section state not found");
+					return;
+				};
 
-			let is_checked = state.is_checked(index, offset);
-			log::info!("is_checked: {}", is_checked);
+				let is_checked = state.is_checked(index, offset);
+				log::info!("is_checked: {}", is_checked);
+
+				let Some(Ok(game)) = game_resource.get_untracked() else {
+					log::error!("Game state not found");
+					return;
+				};
+
+				// cap the price of the flip at the quoted price with some
+				// slippage for flips of other players landing first.
+				let now = (js_sys::Date::now() / 1000.0) as i64;
+				let max_lamports_per_bit = match quote_flip(&game, state, now, 1) {
+					Ok(quote) => quote.max_price_with_slippage(DEFAULT_FLIP_SLIPPAGE_BASIS_POINTS),
+					Err(error) => {
+						log::error!("the flip could not be quoted: {error}");
+						return;
+					}
+				};
+
+				let context = get_2d_context(canvas_ref);
+				let section_metadata = section_metadata.get_untracked();
+				let result = state.set_bit(&FlipBit {
+					section_index,
+					array_index: index,
+					offset,
+					value: u8::from(!is_checked),
+					mine: u8::from(mine),
+					expected: 0,
+					flags: 0,
+					max_lamports_per_bit: max_lamports_per_bit.into(),
+					player_bump: 0,
+					section_locks_bump: 0,
+					section_metadata_bump: 0,
+				});
+				log::info!("result: {:?}", result);
+
+				if is_checked {
+					let _ = state.flip_off(1);
+					context.set_fill_style_str(&get_color_str(section_metadata.background));
+				} else {
+					let _ = state.flip_on(1);
+					context.set_fill_style_str(&get_color_str(section_metadata.foreground));
+				}
 
-			let context = get_2d_context(canvas_ref);
-			let result = state.set_bit(&FlipBit {
-				section_index,
-				array_index: index,
-				offset,
-				value: u8::from(!is_checked),
-			});
-			log::info!("result: {:?}", result);
-
-			if is_checked {
-				let _ = state.flip_off(1);
-				context.clear_rect(f64::from(x * 16), f64::from(y * 16), 16f64, 16f64);
-			} else {
-				let _ = state.flip_on(1);
-				context.set_fill_style_str("black");
 				context.fill_rect(f64::from(x * 16), f64::from(y * 16), 16f64, 16f64);
+			});
+		};
+
+		if !minesweeper {
+			flip_cell(false);
+			return;
+		}
+
+		// flips in a minesweeper game must be co-signed by the backend which
+		// reports whether the revealed cell is a mine.
+		leptos::task::spawn_local(async move {
+			match get_flip_bit_mine(game_index, section_index, index, offset).await {
+				Ok(mine) => flip_cell(mine),
+				Err(error) => log::error!("the flip was not co-signed: {error}"),
 			}
 		});
 	};
 
+	let title = move || section_metadata.get().title().to_string();
+	let link = move || section_metadata.get().link().to_string();
+
 	view! {
 		<div class="w-full h-full">
+			<Show when=move || !title().is_empty() || !link().is_empty()>
+				<div class="flex gap-2 items-center">
+					<span class="font-bold">{title}</span>
+					<a href=link target="_blank" rel="noopener noreferrer nofollow" class="underline">
+						{link}
+					</a>
+				</div>
+			</Show>
 			<Show when=show_image>
 				<SectionImage game_index=game_index_signal section_index=section_index_signal />
 			</Show>
@@ -166,11 +265,46 @@ fn get_2d_context(canvas_ref: NodeRef<Canvas>) -> CanvasRenderingContext2d {
 	context
 }
 
-/// Will panic if the index or offset is greater than `u8::MAX`.
-pub fn get_index_offset(x: u16, y: u16) -> (u8, u8) {
-	let index = ((((x / 4) / 4) + ((y / 4) / 4) * 4) * 16) + ((x / 4) % 4) + 4 * ((y / 4) % 4);
-	let offset = x % 4 + (y % 4) * 4;
-	assert!(offset <= 16);
+/// Format an rgb color for the canvas fill style.
+fn get_color_str([red, green, blue]: [u8; 3]) -> String {
+	format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+/// Draw the revealed cells of a minesweeper section. Mines, including the hits
+/// reported by the backend while the game is running, are filled in red. The
+/// number of surrounding mines is only shown once the layout is revealed after
+/// the game ends, until then safe cells are filled in light gray.
+fn draw_section_mines(context: &CanvasRenderingContext2d, section_mines: &SectionMinesState) {
+	context.set_font("12px monospace");
+	context.set_text_align("center");
+	context.set_text_baseline("middle");
+
+	for index in 0..BITFLIP_SECTION_LENGTH {
+		let index = index as u8;
 
-	(u8::try_from(index).unwrap(), u8::try_from(offset).unwrap())
+		for offset in 0..16u8 {
+			let Some(cell) = section_mines.revealed_cell(index, offset) else {
+				continue;
+			};
+
+			let (x, y) = get_coordinates(index, offset);
+			let (left, top) = (f64::from(x * 16), f64::from(y * 16));
+
+			match cell {
+				RevealedCell::Mine => {
+					context.set_fill_style_str("red");
+					context.fill_rect(left, top, 16f64, 16f64);
+				}
+				RevealedCell::Safe => {
+					context.set_fill_style_str("lightgray");
+					context.fill_rect(left, top, 16f64, 16f64);
+				}
+				RevealedCell::Adjacent(0) => {}
+				RevealedCell::Adjacent(count) => {
+					context.set_fill_style_str("gray");
+					let _ = context.fill_text(&count.to_string(), left + 8f64, top + 8f64);
+				}
+			}
+		}
+	}
 }
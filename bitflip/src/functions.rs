@@ -1,3 +1,6 @@
+use bitflip_program::GameState;
+use bitflip_program::SectionMetadataState;
+use bitflip_program::SectionMinesState;
 use bitflip_program::SectionState;
 use leptos::prelude::*;
 
@@ -26,6 +29,27 @@ pub async fn get_active_player_count() -> Result<u32, ServerFnError> {
 	Ok(rng.next_u32())
 }
 
+/// Get the game state for the given game index.
+#[allow(clippy::unused_async)]
+#[server]
+pub async fn get_game_state(game_index: u8) -> Result<GameState, ServerFnError> {
+	use std::time::SystemTime;
+
+	use bitflip_program::get_pda_game;
+	use solana_sdk::pubkey::Pubkey;
+
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.map_err(ServerFnError::new)?
+		.as_secs() as i64;
+	let bump = get_pda_game(game_index).1;
+	let mut game_state =
+		GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), game_index, bump);
+	game_state.start(now - 3600);
+
+	Ok(game_state)
+}
+
 /// Get the section state for the given game and section index.
 #[allow(clippy::unused_async)]
 #[server]
@@ -56,3 +80,80 @@ pub async fn get_section_state(
 
 	Ok(section_state)
 }
+
+/// Get the mines for the given section of a minesweeper game.
+#[allow(clippy::unused_async)]
+#[server]
+pub async fn get_section_mines(
+	game_index: u8,
+	section_index: u8,
+) -> Result<SectionMinesState, ServerFnError> {
+	Ok(create_section_mines(game_index, section_index))
+}
+
+/// Check whether the cell flipped by a player in a minesweeper game is a mine.
+///
+/// The mine layout stays hidden until the game has ended so the program can't
+/// check it during a flip. Instead the backend co-signs every flip of a
+/// minesweeper game with the temp signer and reports the result in
+/// [`bitflip_program::FlipBit::mine`]. See
+/// [`bitflip_program::FlipBitOptions::minesweeper`].
+#[allow(clippy::unused_async)]
+#[server]
+pub async fn get_flip_bit_mine(
+	game_index: u8,
+	section_index: u8,
+	array_index: u8,
+	offset: u8,
+) -> Result<bool, ServerFnError> {
+	use bitflip_program::BITFLIP_SECTION_LENGTH;
+
+	if usize::from(array_index) >= BITFLIP_SECTION_LENGTH || offset >= 16 {
+		return Err(ServerFnError::new("the bit is outside of the section"));
+	}
+
+	let section_mines = create_section_mines(game_index, section_index);
+
+	Ok(section_mines.is_mine(array_index, offset))
+}
+
+#[cfg(feature = "ssr")]
+fn create_section_mines(game_index: u8, section_index: u8) -> SectionMinesState {
+	use bitflip_program::get_mines_commitment;
+	use bitflip_program::get_pda_section_mines;
+	use bitflip_program::BITFLIP_SECTION_LENGTH;
+	use rand::Rng;
+	use rand::SeedableRng;
+
+	let mut rng = rand::rngs::StdRng::seed_from_u64(section_index.into());
+	let bump = get_pda_section_mines(game_index, section_index).1;
+	let mines: [u16; BITFLIP_SECTION_LENGTH] =
+		std::array::from_fn(|_| rng.r#gen::<u16>() & rng.r#gen::<u16>() & rng.r#gen::<u16>());
+	let revealed: [u16; BITFLIP_SECTION_LENGTH] = std::array::from_fn(|_| rng.r#gen());
+	let mut section_mines = SectionMinesState::new(
+		get_mines_commitment(&mines, &rng.r#gen()),
+		game_index,
+		section_index,
+		bump,
+	);
+	section_mines.mines = mines.map(Into::into);
+	section_mines.revealed = revealed.map(Into::into);
+	section_mines.hits = std::array::from_fn(|index| (mines[index] & revealed[index]).into());
+
+	section_mines
+}
+
+/// Get the owner customisation for the given section.
+#[allow(clippy::unused_async)]
+#[server]
+pub async fn get_section_metadata(
+	game_index: u8,
+	section_index: u8,
+) -> Result<SectionMetadataState, ServerFnError> {
+	use bitflip_program::get_pda_section_metadata;
+
+	let bump = get_pda_section_metadata(game_index, section_index).1;
+	let section_metadata = SectionMetadataState::new(game_index, section_index, bump);
+
+	Ok(section_metadata)
+}
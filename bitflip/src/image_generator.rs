@@ -1,12 +1,22 @@
 use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use bitflip_program::get_index_offset;
+use bitflip_program::section_seed_batch;
+use bitflip_program::SectionData;
+use bitflip_program::SectionMetadataState;
 use bitflip_program::SectionState;
+use bitflip_program::BITFLIP_SECTION_LENGTH;
+use bitflip_program::BITFLIP_SECTION_WIDTH;
+use bitflip_program::BITFLIP_TOTAL_SECTIONS;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
 use tiny_skia::Color;
 use tiny_skia::Paint;
 use tiny_skia::Pixmap;
 use tiny_skia::Transform;
 
+use crate::get_section_metadata;
 use crate::get_section_state;
 use crate::AppError;
 
@@ -20,12 +30,19 @@ use crate::AppError;
 /// Some more information, there are 16 sections in the `1024x1024` bit
 /// structure. Each section is a `[u16; 256]` array with each bit representing
 /// a flipped bit on the canvas. When the bit is `1` then the image should show
-/// a black square in that position and when a bit is `0` the image should show
-/// a white square.
-pub fn generate_section_image(section: &SectionState) -> Vec<u8> {
+/// a square in the `foreground` color of the [`SectionMetadataState`] and when
+/// a bit is `0` the image should show the `background` color.
+pub fn generate_section_image(
+	section: &SectionState,
+	section_metadata: &SectionMetadataState,
+) -> Vec<u8> {
+	let [red, green, blue] = section_metadata.background;
 	let mut pixmap = Pixmap::new(1024, 1024).unwrap();
+	pixmap.fill(Color::from_rgba8(red, green, blue, u8::MAX));
+
+	let [red, green, blue] = section_metadata.foreground;
 	let mut paint = Paint::default();
-	paint.set_color(Color::BLACK);
+	paint.set_color_rgba8(red, green, blue, u8::MAX);
 
 	for x in 0..16u32 {
 		for y in 0..16u32 {
@@ -53,6 +70,107 @@ pub fn generate_section_image(section: &SectionState) -> Vec<u8> {
 	pixmap.encode_png().unwrap()
 }
 
+/// Generate an image of the full canvas of a game.
+///
+/// The image is a 1024x1024 PNG with each bit represented by a single pixel.
+/// The sections are laid out in a `16x16` grid ordered by their section index,
+/// and the bits within each section follow the same layout as
+/// [`generate_section_image`].
+pub fn generate_canvas_image(sections: &[SectionState]) -> Vec<u8> {
+	let mut pixmap = Pixmap::new(1024, 1024).unwrap();
+	let mut paint = Paint::default();
+	paint.set_color(Color::BLACK);
+
+	for section in sections {
+		let section_x = 64 * (u32::from(section.section_index) % 16);
+		let section_y = 64 * (u32::from(section.section_index) / 16);
+
+		for x in 0..16u32 {
+			for y in 0..16u32 {
+				let index = 16 * (x / 4 + (y / 4) * 4) + (x % 4) + (4 * (y % 4));
+
+				for offset in 0..16u32 {
+					if !section.is_checked(index as u8, offset as u8) {
+						continue;
+					}
+
+					let x = section_x + (4 * x) + offset % 4;
+					let y = section_y + (4 * y) + offset / 4;
+
+					pixmap.fill_rect(
+						tiny_skia::Rect::from_xywh(x as f32, y as f32, 1.0, 1.0).unwrap(),
+						&paint,
+						Transform::identity(),
+						None,
+					);
+				}
+			}
+		}
+	}
+
+	pixmap.encode_png().unwrap()
+}
+
+/// Convert a PNG of the full canvas into the batched `SectionSeed`
+/// instructions which draw it onto the sections of a pending game.
+///
+/// The PNG must be `1024x1024` with each pixel representing a single bit in
+/// the same layout as [`generate_canvas_image`], so the `4x4` block mapping
+/// within each section matches [`generate_section_image`]. Dark opaque pixels
+/// are seeded as `1` bits and every other pixel is left as a `0` bit.
+pub fn get_canvas_seed_instructions(
+	authority: &Pubkey,
+	game_index: u8,
+	png: &[u8],
+) -> anyhow::Result<Vec<Instruction>> {
+	let pixmap = Pixmap::decode_png(png)?;
+
+	if pixmap.width() != 1024 || pixmap.height() != 1024 {
+		anyhow::bail!(
+			"expected a 1024x1024 canvas image but received {}x{}",
+			pixmap.width(),
+			pixmap.height()
+		);
+	}
+
+	let mut instructions = Vec::new();
+
+	for section_index in 0..=u8::MAX {
+		let section_x = 64 * (u32::from(section_index) % 16);
+		let section_y = 64 * (u32::from(section_index) / 16);
+		let mut data: SectionData = [0; BITFLIP_SECTION_LENGTH];
+
+		for x in 0..BITFLIP_SECTION_WIDTH {
+			for y in 0..BITFLIP_SECTION_WIDTH {
+				let Some(pixel) = pixmap.pixel(section_x + u32::from(x), section_y + u32::from(y))
+				else {
+					continue;
+				};
+				let color = pixel.demultiply();
+				let brightness =
+					(u16::from(color.red()) + u16::from(color.green()) + u16::from(color.blue()))
+						/ 3;
+
+				if color.alpha() < 128 || brightness >= 128 {
+					continue;
+				}
+
+				let (index, offset) = get_index_offset(x, y);
+				data[index as usize] |= 1 << offset;
+			}
+		}
+
+		instructions.extend(section_seed_batch(
+			authority,
+			game_index,
+			section_index,
+			&data,
+		));
+	}
+
+	Ok(instructions)
+}
+
 #[allow(clippy::unused_async)]
 pub async fn section_image_handler(
 	Path((game_index, section_index)): Path<(u8, u8)>,
@@ -62,7 +180,28 @@ pub async fn section_image_handler(
 		.await
 		.map_err(|e| anyhow::anyhow!("Failed to get section state: {}", e))?;
 
-	let png_data = generate_section_image(&section_state);
+	let section_metadata = get_section_metadata(game_index, section_index)
+		.await
+		.map_err(|e| anyhow::anyhow!("Failed to get section metadata: {}", e))?;
+
+	let png_data = generate_section_image(&section_state, &section_metadata);
+
+	Ok((StatusCode::OK, [("Content-Type", "image/png")], png_data))
+}
+
+pub async fn canvas_image_handler(
+	Path(game_index): Path<u8>,
+) -> Result<impl IntoResponse, AppError> {
+	let mut sections = Vec::with_capacity(BITFLIP_TOTAL_SECTIONS);
+
+	for section_index in 0..=u8::MAX {
+		let section_state = get_section_state(game_index, section_index)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to get section state: {}", e))?;
+		sections.push(section_state);
+	}
+
+	let png_data = generate_canvas_image(&sections);
 
 	Ok((StatusCode::OK, [("Content-Type", "image/png")], png_data))
 }
@@ -6,6 +6,7 @@ async fn main() {
 	use axum::routing::get;
 	use axum::Router;
 	use bitflip::app::*;
+	use bitflip::image_generator::canvas_image_handler;
 	use bitflip::image_generator::section_image_handler;
 	use leptos::prelude::*;
 	use leptos_axum::generate_route_list;
@@ -46,6 +47,7 @@ async fn main() {
 			"/game/:game_index/section-image/:section_index",
 			get(section_image_handler),
 		)
+		.route("/game/:game_index/canvas-image", get(canvas_image_handler))
 		.fallback(leptos_axum::file_and_error_handler::<LeptosOptions, _>(
 			shell,
 		))
@@ -1,8 +1,21 @@
 //! Client code for the Bitflip program.
 
+use solana_sdk::account::Account;
+use solana_sdk::address_lookup_table::instruction::create_lookup_table;
+use solana_sdk::address_lookup_table::instruction::extend_lookup_table;
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::address_lookup_table::state::LOOKUP_TABLE_MAX_ADDRESSES;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::v0;
+use solana_sdk::message::CompileError;
+use solana_sdk::message::VersionedMessage;
 use solana_sdk::nonce::state::Data as NonceData;
 use solana_sdk::nonce::state::State as NonceState;
+use solana_sdk::signature::Signature;
 use solana_sdk::system_instruction::create_nonce_account_with_seed;
+use solana_sdk::transaction::VersionedTransaction;
 use steel::*;
 use sysvar::rent::Rent;
 use wasm_client_solana::nonce_utils;
@@ -10,8 +23,47 @@ use wasm_client_solana::ClientError;
 use wasm_client_solana::ClientResult;
 use wasm_client_solana::SolanaRpcClient;
 
+use crate::get_pda_config;
+use crate::get_pda_game;
+use crate::get_pda_mint;
+use crate::get_pda_section;
+use crate::get_pda_treasury;
+use crate::get_recorded_compute_units;
+use crate::get_section_token_account;
+use crate::get_treasury_token_account;
+use crate::AccountVersion;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::FlipBit;
+use crate::GameState;
+use crate::SectionCooldownState;
+use crate::SectionState;
+use crate::TokenInitialize;
+use crate::TokenMember;
+use crate::BITFLIP_TOTAL_SECTIONS;
 use crate::SEED_PREFIX;
 
+/// The maximum number of addresses added by a single extend instruction so
+/// that it fits within a transaction.
+pub const LOOKUP_TABLE_EXTEND_CHUNK_SIZE: usize = 20;
+
+/// The compute units reserved for instructions without recorded compute units.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+
+/// The default slippage in basis points added to the quoted price of a flip
+/// when setting its `max_lamports_per_bit`.
+pub const DEFAULT_FLIP_SLIPPAGE_BASIS_POINTS: u64 = 500;
+
+/// The compute units consumed by each compute budget instruction.
+pub const COMPUTE_BUDGET_INSTRUCTION_COMPUTE_UNITS: u32 = 150;
+
+/// The maximum compute unit limit of a transaction.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// The maximum number of accounts fetched by a single `getMultipleAccounts`
+/// request.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
 /// Get the nonce data for a given nonce account.
 pub async fn get_nonce_data(rpc: &SolanaRpcClient, nonce: &Pubkey) -> ClientResult<NonceData> {
 	let account = nonce_utils::get_account(rpc, nonce)
@@ -52,6 +104,658 @@ pub fn get_nonce_seed(identifier: &str) -> String {
 	)
 }
 
+/// The errors returned when fetching the accounts of the bitflip program.
+#[derive(Debug, thiserror::Error)]
+pub enum FetchError {
+	#[error("The account {0} was not found")]
+	AccountNotFound(Pubkey),
+	#[error("The account {address} is owned by {owner} instead of the bitflip program")]
+	InvalidOwner { address: Pubkey, owner: Pubkey },
+	#[error("The account {address} has the discriminator {actual} instead of {expected}")]
+	InvalidDiscriminator {
+		address: Pubkey,
+		expected: u8,
+		actual: u8,
+	},
+	#[error("The account {address} has {actual} bytes of data instead of {expected}")]
+	InvalidLength {
+		address: Pubkey,
+		expected: usize,
+		actual: usize,
+	},
+	#[error("The account {address} has version {version} but version {expected} is required")]
+	StaleVersion {
+		address: Pubkey,
+		expected: u8,
+		version: u8,
+	},
+	#[error(transparent)]
+	Client(#[from] ClientError),
+}
+
+/// Decode the data of a bitflip account after checking its owner,
+/// discriminator, version and length.
+pub fn decode_account<T: AccountVersion + Discriminator>(
+	address: &Pubkey,
+	account: &Account,
+) -> Result<T, FetchError> {
+	if account.owner != crate::ID {
+		return Err(FetchError::InvalidOwner {
+			address: *address,
+			owner: account.owner,
+		});
+	}
+
+	let data = account.data.as_slice();
+	let expected = 8 + std::mem::size_of::<T>();
+	let actual = data.first().copied().unwrap_or_default();
+
+	if actual != T::discriminator() {
+		return Err(FetchError::InvalidDiscriminator {
+			address: *address,
+			expected: T::discriminator(),
+			actual,
+		});
+	}
+
+	// every bitflip account starts with its version. It is checked before the
+	// length since migrating an account can extend it.
+	let Some(version) = data.get(8).copied() else {
+		return Err(FetchError::InvalidLength {
+			address: *address,
+			expected,
+			actual: data.len(),
+		});
+	};
+
+	if version < T::VERSION {
+		return Err(FetchError::StaleVersion {
+			address: *address,
+			expected: T::VERSION,
+			version,
+		});
+	}
+
+	if data.len() != expected {
+		return Err(FetchError::InvalidLength {
+			address: *address,
+			expected,
+			actual: data.len(),
+		});
+	}
+
+	let state = bytemuck::pod_read_unaligned::<T>(&data[8..]);
+
+	Ok(state)
+}
+
+/// Fetch and decode several bitflip accounts of the same type. Missing
+/// accounts are returned as `None`.
+pub async fn fetch_accounts<T: AccountVersion + Discriminator>(
+	rpc: &SolanaRpcClient,
+	addresses: &[Pubkey],
+) -> Result<Vec<Option<T>>, FetchError> {
+	let mut states = Vec::with_capacity(addresses.len());
+
+	for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+		let accounts = rpc.get_multiple_accounts(chunk).await?;
+
+		for (address, account) in chunk.iter().zip(accounts) {
+			let state = account
+				.map(|account| decode_account::<T>(address, &account))
+				.transpose()?;
+			states.push(state);
+		}
+	}
+
+	Ok(states)
+}
+
+/// Fetch and decode a single bitflip account.
+pub async fn fetch_account<T: AccountVersion + Discriminator>(
+	rpc: &SolanaRpcClient,
+	address: &Pubkey,
+) -> Result<T, FetchError> {
+	fetch_accounts::<T>(rpc, &[*address])
+		.await?
+		.pop()
+		.flatten()
+		.ok_or(FetchError::AccountNotFound(*address))
+}
+
+/// Fetch the [`ConfigState`].
+pub async fn fetch_config(rpc: &SolanaRpcClient) -> Result<ConfigState, FetchError> {
+	fetch_account(rpc, &get_pda_config().0).await
+}
+
+/// Fetch the [`GameState`] of a game.
+pub async fn fetch_game(rpc: &SolanaRpcClient, game_index: u8) -> Result<GameState, FetchError> {
+	fetch_account(rpc, &get_pda_game(game_index).0).await
+}
+
+/// Fetch the [`SectionState`] of a section.
+pub async fn fetch_section(
+	rpc: &SolanaRpcClient,
+	game_index: u8,
+	section_index: u8,
+) -> Result<SectionState, FetchError> {
+	fetch_account(rpc, &get_pda_section(game_index, section_index).0).await
+}
+
+/// Fetch every section of a game which has been created, either by unlocking
+/// or by seeding it, in order of their section index.
+pub async fn fetch_all_sections(
+	rpc: &SolanaRpcClient,
+	game_index: u8,
+) -> Result<Vec<SectionState>, FetchError> {
+	let addresses = (0..BITFLIP_TOTAL_SECTIONS)
+		.map(|section_index| get_pda_section(game_index, section_index as u8).0)
+		.collect::<Vec<_>>();
+	let sections = fetch_accounts::<SectionState>(rpc, &addresses).await?;
+
+	Ok(sections.into_iter().flatten().collect())
+}
+
+/// Get the addresses shared by every transaction of a game: the program, the
+/// config, game, `$BIT` mint and treasury accounts and the programs invoked by
+/// the bitflip program.
+pub fn get_game_lookup_table_addresses(game_index: u8) -> Vec<Pubkey> {
+	vec![
+		crate::ID,
+		get_pda_config().0,
+		get_pda_game(game_index).0,
+		get_pda_mint(TokenMember::Bit).0,
+		get_pda_treasury().0,
+		get_treasury_token_account(TokenMember::Bit),
+		spl_associated_token_account::ID,
+		spl_token_2022::ID,
+		system_program::ID,
+	]
+}
+
+/// Get the addresses of an unlocked section: the section and its `$BIT` token
+/// account.
+pub fn get_section_lookup_table_addresses(game_index: u8, section_index: u8) -> [Pubkey; 2] {
+	[
+		get_pda_section(game_index, section_index).0,
+		get_section_token_account(game_index, section_index, TokenMember::Bit),
+	]
+}
+
+/// Split the [`get_game_lookup_table_addresses`] and the
+/// [`get_section_lookup_table_addresses`] of each of the unlocked
+/// `section_indices` into the addresses of as many lookup tables as needed,
+/// since a single table holds at most [`LOOKUP_TABLE_MAX_ADDRESSES`].
+///
+/// The first table holds the game addresses and the following tables only hold
+/// sections. The addresses of a section are never split across tables.
+pub fn get_game_lookup_tables_addresses(
+	game_index: u8,
+	section_indices: &[u8],
+) -> Vec<Vec<Pubkey>> {
+	let mut tables = vec![];
+	let mut table = get_game_lookup_table_addresses(game_index);
+
+	for section_index in section_indices {
+		let section_addresses = get_section_lookup_table_addresses(game_index, *section_index);
+
+		if table.len() + section_addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+			tables.push(std::mem::take(&mut table));
+		}
+
+		table.extend(section_addresses);
+	}
+
+	tables.push(table);
+
+	tables
+}
+
+/// Create the address lookup tables for a game which together contain the
+/// [`get_game_lookup_tables_addresses`]. A game with all of its sections
+/// unlocked needs more than one table.
+///
+/// Returns the address of each lookup table with the instructions to create
+/// and extend it. Each instruction should be sent in its own transaction. A
+/// table can be used from the slot after it was last extended and every table
+/// should be passed to [`create_versioned_transaction`].
+///
+/// ### Arguments
+///
+/// * `authority` - The authority of the lookup tables which can extend them.
+/// * `payer` - Pays for the lookup table accounts: must be a signer.
+/// * `recent_slots` - A distinct recent slot for each table used to derive the
+///   table address. Fails with [`ProgramError::InvalidArgument`] when there are
+///   fewer recent slots than tables.
+pub fn create_game_lookup_tables(
+	authority: &Pubkey,
+	payer: &Pubkey,
+	recent_slots: &[u64],
+	game_index: u8,
+	section_indices: &[u8],
+) -> Result<Vec<(Pubkey, Vec<Instruction>)>, ProgramError> {
+	let tables = get_game_lookup_tables_addresses(game_index, section_indices);
+
+	if recent_slots.len() < tables.len() {
+		return Err(ProgramError::InvalidArgument);
+	}
+
+	let lookup_tables = tables
+		.iter()
+		.zip(recent_slots)
+		.map(|(addresses, recent_slot)| {
+			let (create_instruction, lookup_table) =
+				create_lookup_table(*authority, *payer, *recent_slot);
+			let mut instructions = vec![create_instruction];
+			instructions.extend(extend_game_lookup_table(
+				authority,
+				payer,
+				&lookup_table,
+				addresses,
+			));
+
+			(lookup_table, instructions)
+		})
+		.collect();
+
+	Ok(lookup_tables)
+}
+
+/// Extend the lookup table of a game with new addresses, e.g. the
+/// [`get_section_lookup_table_addresses`] of a newly unlocked section.
+///
+/// The addresses are split into chunks of [`LOOKUP_TABLE_EXTEND_CHUNK_SIZE`]
+/// with one instruction per chunk.
+pub fn extend_game_lookup_table(
+	authority: &Pubkey,
+	payer: &Pubkey,
+	lookup_table: &Pubkey,
+	addresses: &[Pubkey],
+) -> Vec<Instruction> {
+	addresses
+		.chunks(LOOKUP_TABLE_EXTEND_CHUNK_SIZE)
+		.map(|chunk| extend_lookup_table(*lookup_table, *authority, Some(*payer), chunk.to_vec()))
+		.collect()
+}
+
+/// Fetch an address lookup table so that it can be used to compile a v0
+/// transaction.
+pub async fn get_lookup_table_account(
+	rpc: &SolanaRpcClient,
+	lookup_table: &Pubkey,
+) -> ClientResult<AddressLookupTableAccount> {
+	let account = rpc.get_account(lookup_table).await?;
+	let table = AddressLookupTable::deserialize(&account.data)
+		.map_err(|e| ClientError::Other(e.to_string()))?;
+
+	Ok(AddressLookupTableAccount {
+		key: *lookup_table,
+		addresses: table.addresses.to_vec(),
+	})
+}
+
+/// Create an unsigned v0 transaction which loads the accounts found in the
+/// `lookup_tables` from the tables instead of including them in the
+/// transaction.
+pub fn create_versioned_transaction(
+	payer: &Pubkey,
+	instructions: &[Instruction],
+	lookup_tables: &[AddressLookupTableAccount],
+	recent_blockhash: Hash,
+) -> Result<VersionedTransaction, CompileError> {
+	let message = v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)?;
+	let signatures = vec![Signature::default(); message.header.num_required_signatures as usize];
+
+	Ok(VersionedTransaction {
+		signatures,
+		message: VersionedMessage::V0(message),
+	})
+}
+
+/// Get the compute units to reserve for an instruction from the compute units
+/// recorded by the validator tests.
+///
+/// Instructions of other programs and bitflip instructions without recorded
+/// compute units reserve [`DEFAULT_INSTRUCTION_COMPUTE_UNITS`].
+pub fn get_instruction_compute_units(instruction: &Instruction) -> u32 {
+	get_instruction_name(instruction)
+		.and_then(|name| get_recorded_compute_units(&name))
+		.unwrap_or(DEFAULT_INSTRUCTION_COMPUTE_UNITS)
+}
+
+/// Get the name the compute units of a bitflip instruction are recorded under
+/// by the validator tests.
+fn get_instruction_name(instruction: &Instruction) -> Option<String> {
+	if instruction.program_id != crate::ID {
+		return None;
+	}
+
+	let ix = BitflipInstruction::try_from(*instruction.data.first()?).ok()?;
+	let name = match ix {
+		BitflipInstruction::TokenInitialize => {
+			let member = TokenInitialize::try_from_bytes(&instruction.data[1..])
+				.and_then(|args| args.member())
+				.ok()?;
+
+			return Some(format!("token_initialize:{}", member.name().to_lowercase()));
+		}
+		BitflipInstruction::ConfigInitialize => "config_initialize",
+		BitflipInstruction::ConfigUpdateAuthority => "config_update_authority",
+		BitflipInstruction::TokenGroupInitialize => "token_group_initialize",
+		BitflipInstruction::GameInitialize => "game_initialize",
+		BitflipInstruction::GameStart => "game_start",
+		BitflipInstruction::GameUpdateTempSigner => "game_update_temp_signer",
+		BitflipInstruction::GameResetSigners => "game_reset_signers",
+		BitflipInstruction::SectionUnlock => "section_unlock",
+		BitflipInstruction::FlipBit => "flip_bit",
+		BitflipInstruction::SectionMinesReveal => "section_mines_reveal",
+		BitflipInstruction::SectionStep => "section_step",
+		BitflipInstruction::BountyCreate => "bounty_create",
+		BitflipInstruction::BountyClaim => "bounty_claim",
+		BitflipInstruction::GameFinalize => "game_finalize",
+		BitflipInstruction::GameArtworkMint => "game_artwork_mint",
+		BitflipInstruction::SectionMetadataUpdate => "section_metadata_update",
+		BitflipInstruction::SectionMetadataModerate => "section_metadata_moderate",
+		BitflipInstruction::BadgeInitialize => "badge_initialize",
+		BitflipInstruction::BadgeMint => "badge_mint",
+		BitflipInstruction::GameUpdateCooldown => "game_update_cooldown",
+		BitflipInstruction::SectionLockCreate => "section_lock_create",
+		BitflipInstruction::SectionGateUpdate => "section_gate_update",
+		BitflipInstruction::FlipBitWithTokens => "flip_bit_with_tokens",
+		BitflipInstruction::StakePoolUpdate => "stake_pool_update",
+		BitflipInstruction::StakeTokens => "stake_tokens",
+		BitflipInstruction::UnstakeTokens => "unstake_tokens",
+		BitflipInstruction::StakeClaim => "stake_claim",
+		BitflipInstruction::GameUpdateParameter => "game_update_parameter",
+		BitflipInstruction::ProposalCreate => "proposal_create",
+		BitflipInstruction::ProposalVote => "proposal_vote",
+		BitflipInstruction::ProposalExecute => "proposal_execute",
+		BitflipInstruction::VoteWithdraw => "vote_withdraw",
+		BitflipInstruction::TeamJoin => "team_join",
+		BitflipInstruction::TeamClaim => "team_claim",
+		BitflipInstruction::GameSchedule => "game_schedule",
+		BitflipInstruction::SectionSeed => "section_seed",
+		BitflipInstruction::PlayerTokenAccountInitialize => "player_token_account_initialize",
+		BitflipInstruction::AccountMigrate => "account_migrate",
+	};
+
+	Some(name.to_string())
+}
+
+/// The priority fee paid by the transactions of a [`TransactionBuilder`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeePolicy {
+	/// Don't pay a priority fee.
+	#[default]
+	None,
+	/// Pay a fixed price in micro-lamports for each compute unit.
+	MicroLamportsPerComputeUnit(u64),
+	/// Pay a total priority fee in lamports which is spread over the compute
+	/// unit limit of the transaction.
+	Lamports(u64),
+}
+
+impl FeePolicy {
+	/// Get the compute unit price in micro-lamports for a transaction with the
+	/// provided compute unit limit. Returns `None` when no priority fee is
+	/// paid.
+	pub fn compute_unit_price(&self, compute_unit_limit: u32) -> Option<u64> {
+		match *self {
+			FeePolicy::None => None,
+			FeePolicy::MicroLamportsPerComputeUnit(price) => Some(price),
+			FeePolicy::Lamports(lamports) => {
+				let price =
+					u128::from(lamports) * 1_000_000 / u128::from(compute_unit_limit.max(1));
+				Some(u64::try_from(price).unwrap_or(u64::MAX))
+			}
+		}
+	}
+}
+
+/// Build v0 transactions which set their compute unit limit from the recorded
+/// compute units of their instructions and their compute unit price from a
+/// [`FeePolicy`].
+///
+/// ```ignore
+/// let transaction = TransactionBuilder::new(&player)
+/// 	.instruction(player_token_account_initialize(&player))
+/// 	.instruction(flip_bit(&player, 0, 0, 0, 0, 1, u64::MAX))
+/// 	.fee_policy(FeePolicy::MicroLamportsPerComputeUnit(1_000))
+/// 	.build(recent_blockhash)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct TransactionBuilder {
+	payer: Pubkey,
+	instructions: Vec<Instruction>,
+	lookup_tables: Vec<AddressLookupTableAccount>,
+	fee_policy: FeePolicy,
+}
+
+impl TransactionBuilder {
+	pub fn new(payer: &Pubkey) -> Self {
+		Self {
+			payer: *payer,
+			instructions: vec![],
+			lookup_tables: vec![],
+			fee_policy: FeePolicy::default(),
+		}
+	}
+
+	/// Add an instruction to the transaction.
+	pub fn instruction(mut self, instruction: Instruction) -> Self {
+		self.instructions.push(instruction);
+		self
+	}
+
+	/// Add several instructions to the transaction.
+	pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+		self.instructions.extend(instructions);
+		self
+	}
+
+	/// Load the accounts found in the lookup table from the table, e.g. one of
+	/// the tables created by [`create_game_lookup_tables`].
+	pub fn lookup_table(mut self, lookup_table: AddressLookupTableAccount) -> Self {
+		self.lookup_tables.push(lookup_table);
+		self
+	}
+
+	/// Set the priority fee of the transaction.
+	pub fn fee_policy(mut self, fee_policy: FeePolicy) -> Self {
+		self.fee_policy = fee_policy;
+		self
+	}
+
+	/// The compute unit limit of the transaction: the sum of the compute units
+	/// of each instruction including the compute budget instructions.
+	pub fn compute_unit_limit(&self) -> u32 {
+		let budget_instructions = if self.fee_policy == FeePolicy::None {
+			1
+		} else {
+			2
+		};
+
+		self.instructions
+			.iter()
+			.map(get_instruction_compute_units)
+			.fold(
+				budget_instructions * COMPUTE_BUDGET_INSTRUCTION_COMPUTE_UNITS,
+				u32::saturating_add,
+			)
+			.min(MAX_COMPUTE_UNIT_LIMIT)
+	}
+
+	/// Get the instructions of the transaction with the compute budget
+	/// instructions prepended.
+	pub fn to_instructions(&self) -> Vec<Instruction> {
+		let compute_unit_limit = self.compute_unit_limit();
+		let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+			compute_unit_limit,
+		)];
+
+		if let Some(price) = self.fee_policy.compute_unit_price(compute_unit_limit) {
+			instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+		}
+
+		instructions.extend(self.instructions.iter().cloned());
+		instructions
+	}
+
+	/// Build the unsigned v0 transaction.
+	pub fn build(&self, recent_blockhash: Hash) -> Result<VersionedTransaction, CompileError> {
+		create_versioned_transaction(
+			&self.payer,
+			&self.to_instructions(),
+			&self.lookup_tables,
+			recent_blockhash,
+		)
+	}
+}
+
+/// A quote of the lamports paid for a sequence of flips within a section.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlipQuote {
+	/// The price of a bit charged by each flip in order. The price rises as the
+	/// section is flipped so later flips can cost more.
+	pub prices: Vec<u64>,
+	/// The number of flips charged by each flip in order: `2` for a bit which
+	/// is unchanged and `0` when the unchanged bit is skipped.
+	pub flips: Vec<u64>,
+	/// The total lamports paid for all the flips.
+	pub lamports: u64,
+}
+
+impl FlipQuote {
+	/// The highest price of a bit within the quote which can be used as the
+	/// `max_lamports_per_bit` of each flip.
+	pub fn max_price(&self) -> u64 {
+		self.prices.iter().copied().max().unwrap_or_default()
+	}
+
+	/// The [`FlipQuote::max_price`] raised by `slippage_basis_points` so that
+	/// the flips still succeed when other players flip the section before
+	/// them.
+	pub fn max_price_with_slippage(&self, slippage_basis_points: u64) -> u64 {
+		let max_price = self.max_price();
+		let slippage = u128::from(max_price) * u128::from(slippage_basis_points) / 10_000;
+
+		max_price.saturating_add(u64::try_from(slippage).unwrap_or(u64::MAX))
+	}
+
+	/// The total number of flips charged.
+	pub fn total_flips(&self) -> u64 {
+		self.flips.iter().sum()
+	}
+
+	fn push(&mut self, price: u64, flips: u64) {
+		self.prices.push(price);
+		self.flips.push(flips);
+		self.lamports = self.lamports.saturating_add(price.saturating_mul(flips));
+	}
+}
+
+/// Quote the lamports paid for flipping `n_bits` bits of a section one after
+/// the other at the time `now`. Each bit is assumed to change.
+///
+/// The quote doesn't include the cooldown price multiplier, which depends on
+/// the words being flipped, or the transaction fees. Use [`quote_flip_bits`]
+/// to include the cooldown.
+pub fn quote_flip(
+	game: &GameState,
+	section: &SectionState,
+	now: i64,
+	n_bits: u32,
+) -> Result<FlipQuote, ProgramError> {
+	let mut section = *section;
+	let mut quote = FlipQuote::default();
+
+	for _ in 0..n_bits {
+		section.flips = section
+			.flips()
+			.checked_add(1)
+			.ok_or(ProgramError::ArithmeticOverflow)?
+			.into();
+		quote.push(
+			section.get_token_price_in_lamports(game, game.remaining_time(now)),
+			1,
+		);
+	}
+
+	Ok(quote)
+}
+
+/// Quote the lamports paid for a sequence of [`FlipBit`] instructions of the
+/// same section at the time `now`.
+///
+/// The flips are applied to a copy of the section in the same way as the
+/// program so unchanged bits are charged as two flips unless they are
+/// skipped.
+///
+/// When the game has a cooldown the flips are also recorded on a copy of the
+/// `section_cooldown` at the `slot`, which is `None` before the first flip of
+/// the section, and the price of a word which is cooling down is multiplied by
+/// the [`GameState::cooldown_multiplier`]. The quote fails with
+/// [`crate::BitflipError::BitCooldown`] when the program would reject the flip.
+pub fn quote_flip_bits(
+	game: &GameState,
+	section: &SectionState,
+	section_cooldown: Option<&SectionCooldownState>,
+	slot: u64,
+	now: i64,
+	flip_bits: &[FlipBit],
+) -> Result<FlipQuote, ProgramError> {
+	let mut section = *section;
+	let mut section_cooldown = game.cooldown().then(|| {
+		section_cooldown
+			.copied()
+			.unwrap_or_else(|| SectionCooldownState::new(game.game_index, section.section_index, 0))
+	});
+	let mut quote = FlipQuote::default();
+
+	for args in flip_bits {
+		args.validate()?;
+		let is_changed = section.set_bit(args)?;
+
+		if !is_changed && args.skip_unchanged() {
+			quote.push(0, 0);
+			continue;
+		}
+
+		let price_multiplier = match &mut section_cooldown {
+			Some(section_cooldown) => {
+				section_cooldown.touch(
+					args.array_index,
+					slot,
+					game.cooldown_slots(),
+					game.cooldown_multiplier,
+				)?
+			}
+			None => 1,
+		};
+
+		let flips = if !is_changed {
+			section.flip_on(1)?;
+			section.flip_off(1)?;
+			2
+		} else if args.on() {
+			section.flip_on(1)?;
+			1
+		} else {
+			section.flip_off(1)?;
+			1
+		};
+
+		quote.push(
+			section
+				.get_token_price_in_lamports(game, game.remaining_time(now))
+				.saturating_mul(price_multiplier),
+			flips,
+		);
+	}
+
+	Ok(quote)
+}
+
 #[derive(Clone, Debug, strum::Display, strum::EnumString, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum NonceIdentifier {
@@ -61,9 +765,20 @@ pub enum NonceIdentifier {
 
 #[cfg(test)]
 mod tests {
+	use assert2::check;
 	use rstest::rstest;
 
 	use super::*;
+	use crate::config_update_authority;
+	use crate::flip_bit;
+	use crate::section_step;
+	use crate::token_initialize;
+	use crate::BitflipError;
+	use crate::FlipBitFlags;
+	use crate::SectionEdge;
+	use crate::CONFIG_UPDATE_AUTHORITY_COMPUTE_UNITS;
+	use crate::FLIP_BIT_COMPUTE_UNITS;
+	use crate::TOKEN_INITIALIZE_KIBIBIT_COMPUTE_UNITS;
 
 	#[rstest]
 	#[case(1, 2, "section_unlock:01:002")]
@@ -77,4 +792,387 @@ mod tests {
 			expected
 		);
 	}
+
+	#[test]
+	fn game_lookup_table_should_include_sections() -> anyhow::Result<()> {
+		let authority = Pubkey::new_unique();
+		let lookup_tables = create_game_lookup_tables(&authority, &authority, &[1], 0, &[0, 1, 2])?;
+		let expected = get_game_lookup_table_addresses(0).len() + 3 * 2;
+
+		let [(lookup_table, instructions)] = lookup_tables.as_slice() else {
+			anyhow::bail!("expected a single lookup table");
+		};
+		check!(*lookup_table != Pubkey::default());
+		check!(instructions.len() == 1 + expected.div_ceil(LOOKUP_TABLE_EXTEND_CHUNK_SIZE));
+
+		Ok(())
+	}
+
+	#[test]
+	fn game_lookup_tables_should_split_sections() -> anyhow::Result<()> {
+		let authority = Pubkey::new_unique();
+		let section_indices = (0..=u8::MAX).collect::<Vec<_>>();
+		let tables = get_game_lookup_tables_addresses(0, &section_indices);
+
+		check!(tables.len() == 3);
+		check!(tables
+			.iter()
+			.all(|table| table.len() <= LOOKUP_TABLE_MAX_ADDRESSES));
+		check!(
+			tables[0][..get_game_lookup_table_addresses(0).len()]
+				== get_game_lookup_table_addresses(0)
+		);
+
+		let addresses = tables.concat();
+		let last_section = get_section_lookup_table_addresses(0, u8::MAX);
+		check!(addresses.len() == get_game_lookup_table_addresses(0).len() + 256 * 2);
+		check!(addresses.ends_with(&last_section));
+
+		let lookup_tables =
+			create_game_lookup_tables(&authority, &authority, &[1, 2, 3], 0, &section_indices)?;
+		check!(lookup_tables.len() == 3);
+		check!(lookup_tables[0].0 != lookup_tables[1].0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn game_lookup_tables_should_have_recent_slot_for_each_table() {
+		let authority = Pubkey::new_unique();
+		let section_indices = (0..=u8::MAX).collect::<Vec<_>>();
+		let result =
+			create_game_lookup_tables(&authority, &authority, &[1, 2], 0, &section_indices);
+
+		check!(result.unwrap_err() == ProgramError::InvalidArgument);
+	}
+
+	#[test]
+	fn versioned_transaction_should_load_static_accounts() -> anyhow::Result<()> {
+		let player = Pubkey::new_unique();
+		let mut addresses = get_game_lookup_table_addresses(0);
+		addresses.extend(get_section_lookup_table_addresses(0, 0));
+		let lookup_table = AddressLookupTableAccount {
+			key: Pubkey::new_unique(),
+			addresses,
+		};
+		let ix = flip_bit(&player, 0, 0, 0, 0, 1, u64::MAX);
+		let transaction =
+			create_versioned_transaction(&player, &[ix], &[lookup_table], Hash::default())?;
+		let VersionedMessage::V0(message) = &transaction.message else {
+			anyhow::bail!("expected a v0 message");
+		};
+
+		check!(transaction.signatures.len() == 1);
+		check!(message.address_table_lookups.len() == 1);
+		// the player accounts, the section locks and metadata and the invoked
+		// program remain static.
+		check!(message.account_keys.len() == 6);
+
+		Ok(())
+	}
+
+	#[test]
+	fn compute_unit_limit_should_sum_instructions() {
+		let player = Pubkey::new_unique();
+		let builder = TransactionBuilder::new(&player)
+			.instruction(flip_bit(&player, 0, 0, 0, 0, 1, u64::MAX))
+			.instruction(config_update_authority(&player, &Pubkey::new_unique()))
+			.instruction(token_initialize(&player, TokenMember::Kibibit));
+
+		check!(
+			builder.compute_unit_limit()
+				== COMPUTE_BUDGET_INSTRUCTION_COMPUTE_UNITS
+					+ FLIP_BIT_COMPUTE_UNITS
+					+ CONFIG_UPDATE_AUTHORITY_COMPUTE_UNITS
+					+ TOKEN_INITIALIZE_KIBIBIT_COMPUTE_UNITS
+		);
+		check!(builder.to_instructions().len() == 4);
+	}
+
+	#[test]
+	fn max_price_with_slippage_should_raise_max_price() {
+		let quote = FlipQuote {
+			prices: vec![100_000, 120_000],
+			flips: vec![1, 1],
+			lamports: 220_000,
+		};
+
+		check!(quote.max_price_with_slippage(0) == 120_000);
+		check!(quote.max_price_with_slippage(DEFAULT_FLIP_SLIPPAGE_BASIS_POINTS) == 126_000);
+		check!(FlipQuote::default().max_price_with_slippage(500) == 0);
+	}
+
+	#[test]
+	fn compute_units_should_be_looked_up_by_name() {
+		let player = Pubkey::new_unique();
+		let ix = section_step(&player, 0, 0, SectionEdge::Dead);
+		let expected =
+			get_recorded_compute_units("section_step").unwrap_or(DEFAULT_INSTRUCTION_COMPUTE_UNITS);
+
+		check!(get_instruction_compute_units(&ix) == expected);
+	}
+
+	#[test]
+	fn compute_unit_limit_should_be_capped() {
+		let player = Pubkey::new_unique();
+		let builder = TransactionBuilder::new(&player).instructions(
+			(0..10).map(|_| Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![])),
+		);
+
+		check!(builder.compute_unit_limit() == MAX_COMPUTE_UNIT_LIMIT);
+	}
+
+	#[rstest]
+	#[case(FeePolicy::None, 100_000, None)]
+	#[case(FeePolicy::MicroLamportsPerComputeUnit(5), 100_000, Some(5))]
+	#[case(FeePolicy::Lamports(1_000), 100_000, Some(10_000))]
+	#[case(FeePolicy::Lamports(1), 0, Some(1_000_000))]
+	fn fee_policy_compute_unit_price(
+		#[case] policy: FeePolicy,
+		#[case] compute_unit_limit: u32,
+		#[case] expected: Option<u64>,
+	) {
+		check!(policy.compute_unit_price(compute_unit_limit) == expected);
+	}
+
+	#[test]
+	fn transaction_builder_should_set_compute_unit_price() -> anyhow::Result<()> {
+		let player = Pubkey::new_unique();
+		let transaction = TransactionBuilder::new(&player)
+			.instruction(flip_bit(&player, 0, 0, 0, 0, 1, u64::MAX))
+			.fee_policy(FeePolicy::MicroLamportsPerComputeUnit(1_000))
+			.build(Hash::default())?;
+		let instructions = transaction.message.instructions();
+
+		check!(transaction.signatures.len() == 1);
+		check!(instructions.len() == 3);
+
+		Ok(())
+	}
+
+	fn create_config_account(owner: Pubkey) -> Account {
+		let config = ConfigState::new(Pubkey::new_unique(), 1, 2, 3, 4, 5, 6);
+		let mut data = vec![0u8; 8];
+		data[0] = ConfigState::discriminator();
+		data.extend_from_slice(config.to_bytes());
+
+		Account {
+			lamports: 1,
+			data,
+			owner,
+			executable: false,
+			rent_epoch: 0,
+		}
+	}
+
+	#[test]
+	fn decode_account_should_decode_state() -> anyhow::Result<()> {
+		let address = get_pda_config().0;
+		let account = create_config_account(crate::ID);
+		let config = decode_account::<ConfigState>(&address, &account)?;
+
+		check!(config.bump == 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn decode_account_should_check_owner() {
+		let address = get_pda_config().0;
+		let account = create_config_account(system_program::ID);
+		let result = decode_account::<ConfigState>(&address, &account);
+
+		check!(let Err(FetchError::InvalidOwner { .. }) = result);
+	}
+
+	#[test]
+	fn decode_account_should_check_discriminator() {
+		let address = get_pda_config().0;
+		let account = create_config_account(crate::ID);
+		let result = decode_account::<GameState>(&address, &account);
+
+		check!(let Err(FetchError::InvalidDiscriminator { .. }) = result);
+	}
+
+	#[test]
+	fn decode_account_should_check_length() {
+		let address = get_pda_config().0;
+		let mut account = create_config_account(crate::ID);
+		account.data.pop();
+		let result = decode_account::<ConfigState>(&address, &account);
+
+		check!(let Err(FetchError::InvalidLength { .. }) = result);
+	}
+
+	#[test]
+	fn decode_account_should_check_version_before_length() {
+		let address = get_pda_game(0).0;
+		let game = GameState::builder()
+			.version(0)
+			.game_index(0)
+			.bump(0)
+			.section_index(0)
+			.build();
+		let mut data = vec![0u8; 8];
+		data[0] = GameState::discriminator();
+		data.extend_from_slice(game.to_bytes());
+		// a version `0` game account is smaller than the latest version.
+		data.truncate(149);
+		let account = Account {
+			lamports: 1,
+			data,
+			owner: crate::ID,
+			executable: false,
+			rent_epoch: 0,
+		};
+		let result = decode_account::<GameState>(&address, &account);
+
+		check!(let Err(FetchError::StaleVersion { version: 0, .. }) = result);
+	}
+
+	#[test]
+	fn quote_flip_should_price_each_flip_after_counting_it() -> anyhow::Result<()> {
+		let now = 1_000;
+		let game = GameState::builder()
+			.temp_signer(Pubkey::new_unique())
+			.funded_signer(Pubkey::new_unique())
+			.start_time(now - 3_600)
+			.game_index(0)
+			.bump(0)
+			.section_index(1)
+			.build();
+		let section = SectionState::new(Pubkey::new_unique(), 0, 0, 0);
+		let quote = quote_flip(&game, &section, now, 3)?;
+
+		let mut expected = section;
+		for index in 0..3 {
+			expected.flip_on(1)?;
+			let price = expected.get_token_price_in_lamports(&game, game.remaining_time(now));
+			check!(quote.prices[index] == price);
+		}
+
+		check!(quote.total_flips() == 3);
+		check!(quote.lamports == quote.prices.iter().sum::<u64>());
+		check!(quote.max_price() == quote.prices[2]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn quote_flip_bits_should_charge_unchanged_bits_twice() -> anyhow::Result<()> {
+		let now = 1_000;
+		let game = GameState::builder()
+			.temp_signer(Pubkey::new_unique())
+			.funded_signer(Pubkey::new_unique())
+			.start_time(now - 3_600)
+			.game_index(0)
+			.bump(0)
+			.section_index(1)
+			.build();
+		let section = SectionState::new(Pubkey::new_unique(), 0, 0, 0);
+		let flip = |offset, flags| {
+			FlipBit::builder()
+				.section_index(0)
+				.array_index(0)
+				.offset(offset)
+				.value(1)
+				.flags(flags)
+				.max_lamports_per_bit(u64::MAX)
+				.build()
+		};
+		let flip_bits = [
+			flip(0, 0),
+			flip(0, 0),
+			flip(0, FlipBitFlags::SKIP_UNCHANGED.bits()),
+		];
+		let quote = quote_flip_bits(&game, &section, None, 0, now, &flip_bits)?;
+
+		check!(quote.flips == vec![1, 2, 0]);
+		check!(quote.total_flips() == 3);
+		check!(quote.lamports == quote.prices[0] + quote.prices[1] * 2);
+		check!(quote.prices[2] == 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn quote_flip_bits_should_apply_cooldown_multiplier() -> anyhow::Result<()> {
+		let now = 1_000;
+		let slot = 500;
+		let game = GameState::builder()
+			.start_time(now - 3_600)
+			.game_index(0)
+			.bump(0)
+			.section_index(1)
+			.cooldown_slots(100)
+			.cooldown_multiplier(3)
+			.build();
+		let section = SectionState::new(Pubkey::new_unique(), 0, 0, 0);
+		let flip = |array_index, offset| {
+			FlipBit::builder()
+				.section_index(0)
+				.array_index(array_index)
+				.offset(offset)
+				.value(1)
+				.max_lamports_per_bit(u64::MAX)
+				.build()
+		};
+		// the second flip is within the same word as the first flip.
+		let flip_bits = [flip(0, 0), flip(0, 1), flip(1, 0)];
+		let quote = quote_flip_bits(&game, &section, None, slot, now, &flip_bits)?;
+		let uncooled_quote = quote_flip(&game, &section, now, 3)?;
+
+		check!(quote.prices[0] == uncooled_quote.prices[0]);
+		check!(quote.prices[1] == uncooled_quote.prices[1] * 3);
+		check!(quote.prices[2] == uncooled_quote.prices[2]);
+
+		// a word which cooled down is charged the normal price.
+		let mut section_cooldown = SectionCooldownState::new(0, 0, 0);
+		section_cooldown.touch(0, slot - 100, 100, 3)?;
+		let quote = quote_flip_bits(
+			&game,
+			&section,
+			Some(&section_cooldown),
+			slot,
+			now,
+			&flip_bits[..1],
+		)?;
+		check!(quote.prices[0] == uncooled_quote.prices[0]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn quote_flip_bits_should_reject_cooling_word() -> anyhow::Result<()> {
+		let now = 1_000;
+		let game = GameState::builder()
+			.start_time(now - 3_600)
+			.game_index(0)
+			.bump(0)
+			.section_index(1)
+			.cooldown_slots(100)
+			.build();
+		let section = SectionState::new(Pubkey::new_unique(), 0, 0, 0);
+		let mut section_cooldown = SectionCooldownState::new(0, 0, 0);
+		section_cooldown.touch(0, 450, 100, 0)?;
+		let flip_bit = FlipBit::builder()
+			.section_index(0)
+			.array_index(0)
+			.offset(0)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build();
+		let result = quote_flip_bits(
+			&game,
+			&section,
+			Some(&section_cooldown),
+			500,
+			now,
+			&[flip_bit],
+		);
+
+		check!(result.unwrap_err() == BitflipError::BitCooldown.into());
+
+		Ok(())
+	}
 }
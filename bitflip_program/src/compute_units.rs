@@ -0,0 +1,52 @@
+//! The compute unit limits of the instructions recorded by the validator
+//! tests.
+//!
+//! Generated from `bitflip_program_tests/compute_units.json` by
+//! `generate_compute_constants`. Do not edit this file by hand.
+
+/// Initialize the config
+pub const CONFIG_INITIALIZE_COMPUTE_UNITS: u32 = 20000;
+
+/// Update the authority of the config
+pub const CONFIG_UPDATE_AUTHORITY_COMPUTE_UNITS: u32 = 10000;
+
+/// Flip a single bit from `0` to `1`
+pub const FLIP_BIT_COMPUTE_UNITS: u32 = 70000;
+
+/// Initialize the game
+pub const GAME_INITIALIZE_COMPUTE_UNITS: u32 = 20000;
+
+/// Refresh the signer
+pub const GAME_UPDATE_TEMP_SIGNER_COMPUTE_UNITS: u32 = 10000;
+
+/// Initialize the token group
+pub const TOKEN_GROUP_INITIALIZE_COMPUTE_UNITS: u32 = 50000;
+
+/// Initialize group token: Bit
+pub const TOKEN_INITIALIZE_BIT_COMPUTE_UNITS: u32 = 70000;
+
+/// Initialize member token: GibiBit
+pub const TOKEN_INITIALIZE_GIBIBIT_COMPUTE_UNITS: u32 = 70000;
+
+/// Initialize member token: KibiBit
+pub const TOKEN_INITIALIZE_KIBIBIT_COMPUTE_UNITS: u32 = 70000;
+
+/// Initialize member token: MebiBit
+pub const TOKEN_INITIALIZE_MEBIBIT_COMPUTE_UNITS: u32 = 70000;
+
+/// Get the recorded compute units of an instruction by its saved name.
+pub fn get_recorded_compute_units(name: &str) -> Option<u32> {
+	match name {
+		"config_initialize" => Some(CONFIG_INITIALIZE_COMPUTE_UNITS),
+		"config_update_authority" => Some(CONFIG_UPDATE_AUTHORITY_COMPUTE_UNITS),
+		"flip_bit" => Some(FLIP_BIT_COMPUTE_UNITS),
+		"game_initialize" => Some(GAME_INITIALIZE_COMPUTE_UNITS),
+		"game_update_temp_signer" => Some(GAME_UPDATE_TEMP_SIGNER_COMPUTE_UNITS),
+		"token_group_initialize" => Some(TOKEN_GROUP_INITIALIZE_COMPUTE_UNITS),
+		"token_initialize:bit" => Some(TOKEN_INITIALIZE_BIT_COMPUTE_UNITS),
+		"token_initialize:gibibit" => Some(TOKEN_INITIALIZE_GIBIBIT_COMPUTE_UNITS),
+		"token_initialize:kibibit" => Some(TOKEN_INITIALIZE_KIBIBIT_COMPUTE_UNITS),
+		"token_initialize:mebibit" => Some(TOKEN_INITIALIZE_MEBIBIT_COMPUTE_UNITS),
+		_ => None,
+	}
+}
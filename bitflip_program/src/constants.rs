@@ -20,6 +20,8 @@ pub const BITFLIP_TOTAL_SECTIONS: usize = 256;
 pub const BITFLIP_SECTION_LENGTH: usize = BITFLIP_TOTAL_BITS / BITFLIP_TOTAL_SECTIONS / 16;
 /// The total number of bits within a section of the game.
 pub const BITFLIP_SECTION_TOTAL_BITS: u32 = BITFLIP_SECTION_LENGTH as u32 * 16;
+/// The width and height of the square grid of bits within a section.
+pub const BITFLIP_SECTION_WIDTH: u16 = 64;
 
 /// How long a session of the bits canvas game lasts. This can be reduced after
 /// the game starts 30 days.
@@ -54,11 +56,94 @@ pub const SEED_TREASURY: &[u8] = b"treasury";
 pub const SEED_GAME: &[u8] = b"game";
 /// The PDA seed for a section within the game. Each game has 256 sections.
 pub const SEED_SECTION: &[u8] = b"section";
+/// The PDA seed for the hidden mines of a section in a minesweeper game.
+pub const SEED_SECTION_MINES: &[u8] = b"mines";
+/// The PDA seed for a bounty on a region of a section.
+pub const SEED_BOUNTY: &[u8] = b"bounty";
+/// The PDA seed for the canvas leaves which are hashed when finalizing a game.
+pub const SEED_GAME_CANVAS: &[u8] = b"canvas";
+/// The PDA seed for the final artwork NFT mint of a game.
+pub const SEED_ARTWORK_MINT: &[u8] = b"mint_artwork";
+/// The PDA seed for the owner customisation of a section.
+pub const SEED_SECTION_METADATA: &[u8] = b"metadata";
+/// The PDA seed for the last flipped slots of a section in a game with a
+/// cooldown.
+pub const SEED_SECTION_COOLDOWN: &[u8] = b"cooldown";
+/// The PDA seed for the active region locks of a section.
+pub const SEED_SECTION_LOCKS: &[u8] = b"locks";
+/// The PDA seed for the token staking pool.
+pub const SEED_STAKE_POOL: &[u8] = b"stake_pool";
+/// The PDA seed for the stake of a player in the staking pool.
+pub const SEED_STAKE: &[u8] = b"stake";
+/// The PDA seed for a governance proposal to change a game parameter.
+pub const SEED_PROPOSAL: &[u8] = b"proposal";
+/// The PDA seed for the escrowed vote of a holder on a proposal.
+pub const SEED_VOTE: &[u8] = b"vote";
+/// The PDA seed for the reward pool of a team game.
+pub const SEED_TEAM_POOL: &[u8] = b"team_pool";
+/// The PDA seed for the team a player joined in a team game.
+pub const SEED_PLAYER_TEAM: &[u8] = b"team";
+/// The PDA seed for the first flip badge mint.
+pub const SEED_FIRST_FLIP_BADGE_MINT: &[u8] = b"badge_first_flip";
+/// The PDA seed for the section threshold badge mint.
+pub const SEED_SECTION_THRESHOLD_BADGE_MINT: &[u8] = b"badge_section_threshold";
+/// The PDA seed for the player flips badge mint.
+pub const SEED_PLAYER_FLIPS_BADGE_MINT: &[u8] = b"badge_player_flips";
+
+/// The maximum number of active region locks within a section.
+pub const MAX_SECTION_LOCKS: usize = 8;
+/// The price of locking a single bit for one second.
+pub const LOCK_LAMPORTS_PER_BIT_SECOND: u64 = 100;
+/// The maximum duration of a region lock in seconds.
+///
+/// 1hr
+pub const MAX_LOCK_DURATION: i64 = 60 * 60;
+
+/// The precision of the reward index of the staking pool. The index is the
+/// lamports earned per bit staked multiplied by this value.
+pub const STAKE_REWARD_PRECISION: u128 = 1_000_000_000_000;
+/// The maximum share of the section unlock revenue which can be paid to the
+/// staking pool in basis points.
+pub const MAX_STAKE_REVENUE_BASIS_POINTS: u16 = 10_000;
+
+/// The duration of the voting period of a governance proposal.
+///
+/// 3 days
+pub const GOVERNANCE_VOTING_PERIOD: i64 = 60 * 60 * 24 * 3;
+/// The minimum number of bits which must vote on a proposal for it to pass.
+pub const GOVERNANCE_QUORUM: u64 = BITS_PER_MEBIBIT;
+
+/// The share of the lamports paid for each flip of a team game which is
+/// deposited into the team pool in basis points.
+pub const TEAM_REVENUE_BASIS_POINTS: u64 = 1_000;
+
+/// The maximum number of `u16` words of section data written by a single
+/// [`crate::SectionSeed`] instruction.
+pub const SECTION_SEED_WORDS: usize = 64;
+
+/// The duration in seconds after the start of an inheriting game during which
+/// each section can only be unlocked by its owner in the previous game.
+pub const INHERIT_CLAIM_DURATION: i64 = 60 * 60 * 24;
+
+/// The discount in basis points on the bid of a previous owner re-claiming
+/// their section in an inheriting game.
+pub const INHERIT_DISCOUNT_BASIS_POINTS: u64 = 2_500;
+
+/// The maximum length in bytes of a section title.
+pub const SECTION_TITLE_MAX_LENGTH: usize = 32;
+/// The maximum length in bytes of a section link.
+pub const SECTION_LINK_MAX_LENGTH: usize = 96;
+/// The default rgb color of the bits which are on.
+pub const DEFAULT_SECTION_FOREGROUND: [u8; 3] = [0, 0, 0];
+/// The default rgb color of the bits which are off.
+pub const DEFAULT_SECTION_BACKGROUND: [u8; 3] = [255, 255, 255];
 
 /// Assuming a price of 100USD per sol. This is approximately 1 cent.
 pub const BASE_LAMPORTS_PER_BIT: u64 = LAMPORTS_PER_SOL / 100 / 100;
 pub const MIN_LAMPORTS_PER_BIT: u64 = BASE_LAMPORTS_PER_BIT / 10;
 pub const MAX_LAMPORTS_PER_BIT: u64 = u64::MAX;
+/// The default price of stepping a section forward by one generation.
+pub const BASE_LAMPORTS_PER_STEP: u64 = BASE_LAMPORTS_PER_BIT * 64;
 pub const SEED_BIT_CREATOR: &[u8] = b"bit_creator";
 pub const SPACE_DISCRIMINATOR: usize = 8;
 pub const SPACE_U32: usize = 4;
@@ -85,6 +170,22 @@ pub const PEBIBIT_TOKEN_URI: &str = "https://bitflip.art/pbit-meta.json";
 pub const EXBIBIT_TOKEN_NAME: &str = "ExbiBit";
 pub const EXBIBIT_TOKEN_SYMBOL: &str = "EiB";
 pub const EXBIBIT_TOKEN_URI: &str = "https://bitflip.art/ebit-meta.json";
+pub const ARTWORK_TOKEN_NAME: &str = "Bitflip Canvas";
+pub const ARTWORK_TOKEN_SYMBOL: &str = "BITFLIP";
+/// The base of the uri for the rendered image of the final canvas.
+pub const ARTWORK_TOKEN_URI: &str = "https://bitflip.art/game";
+/// The additional metadata field which stores the canvas merkle root.
+pub const ARTWORK_CANVAS_ROOT_FIELD: &str = "canvas_root";
+pub const FIRST_FLIP_BADGE_NAME: &str = "First Flip";
+pub const FIRST_FLIP_BADGE_SYMBOL: &str = "BFIRST";
+pub const FIRST_FLIP_BADGE_URI: &str = "https://bitflip.art/badge-first-flip-meta.json";
+pub const SECTION_THRESHOLD_BADGE_NAME: &str = "Section Opener";
+pub const SECTION_THRESHOLD_BADGE_SYMBOL: &str = "BOPEN";
+pub const SECTION_THRESHOLD_BADGE_URI: &str =
+	"https://bitflip.art/badge-section-threshold-meta.json";
+pub const PLAYER_FLIPS_BADGE_NAME: &str = "Kilo Flipper";
+pub const PLAYER_FLIPS_BADGE_SYMBOL: &str = "BKILO";
+pub const PLAYER_FLIPS_BADGE_URI: &str = "https://bitflip.art/badge-player-flips-meta.json";
 
 pub const BITS_PER_KIBIBIT: u64 = 1024;
 pub const BITS_PER_MEBIBIT: u64 = BITS_PER_KIBIBIT * 1024;
@@ -95,6 +196,8 @@ pub const BITS_PER_EXBIBIT: u64 = BITS_PER_PEBIBIT * 1024;
 
 /// The maximum number of games that can be created.
 pub const MAX_GAMES: usize = 8;
+/// The portion of a token payment for a flip which is burned in basis points.
+pub const TOKEN_PAYMENT_BURN_BASIS_POINTS: u64 = 1_000;
 /// The number of decimals for this token.
 pub const TOKEN_DECIMALS: u8 = 0;
 /// The number of tokens assigned to each game.
@@ -111,3 +214,6 @@ pub const MAX_SECTION_UPDATES_PER_SECOND: usize = 12_000_000 / 200_000 * 4 / 10;
 /// The minimum number of flips the previous section must have before the next
 /// section can be flipped.
 pub const MINIMUM_FLIPS_PER_SECTION: u32 = BITFLIP_SECTION_TOTAL_BITS / 4;
+/// The number of flips by a player within a game which earns the
+/// [`crate::BadgeType::PlayerFlips`] badge.
+pub const PLAYER_FLIPS_MILESTONE: u32 = 1_000;
@@ -183,6 +183,18 @@ pub fn mint_close_authority_initialize<'info>(
 	)
 }
 
+pub fn non_transferable_mint_initialize<'info>(
+	mint_info: &AccountInfo<'info>,
+	token_program_info: &AccountInfo<'info>,
+	signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+	let ix = spl_token_2022::instruction::initialize_non_transferable_mint(
+		token_program_info.key,
+		mint_info.key,
+	)?;
+	solana_program::program::invoke_signed(&ix, &[mint_info.clone()], signers_seeds)
+}
+
 pub fn create_associated_token_account<'info>(
 	payer_info: &AccountInfo<'info>,
 	associated_token_info: &AccountInfo<'info>,
@@ -264,6 +276,33 @@ pub fn mint_to<'info>(
 	)
 }
 
+pub fn burn<'info>(
+	account_info: &AccountInfo<'info>,
+	mint_info: &AccountInfo<'info>,
+	authority_info: &AccountInfo<'info>,
+	token_program_info: &AccountInfo<'info>,
+	amount: u64,
+	signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+	let ix = spl_token_2022::instruction::burn(
+		token_program_info.key,
+		account_info.key,
+		mint_info.key,
+		authority_info.key,
+		&[],
+		amount,
+	)?;
+	solana_program::program::invoke_signed(
+		&ix,
+		&[
+			account_info.clone(),
+			mint_info.clone(),
+			authority_info.clone(),
+		],
+		signers_seeds,
+	)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn transfer_checked<'info>(
 	from_info: &AccountInfo<'info>,
@@ -296,3 +335,52 @@ pub fn transfer_checked<'info>(
 		signers_seeds,
 	)
 }
+
+pub fn token_metadata_update_field<'info>(
+	mint_info: &AccountInfo<'info>,
+	authority_info: &AccountInfo<'info>,
+	token_program_info: &AccountInfo<'info>,
+	field: spl_token_metadata_interface::state::Field,
+	value: String,
+	signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+	let ix = spl_token_metadata_interface::instruction::update_field(
+		token_program_info.key,
+		mint_info.key,
+		authority_info.key,
+		field,
+		value,
+	);
+	solana_program::program::invoke_signed(
+		&ix,
+		&[
+			token_program_info.clone(),
+			mint_info.clone(),
+			authority_info.clone(),
+		],
+		signers_seeds,
+	)
+}
+
+pub fn set_authority<'info>(
+	owned_info: &AccountInfo<'info>,
+	owner_info: &AccountInfo<'info>,
+	token_program_info: &AccountInfo<'info>,
+	new_authority: Option<&Pubkey>,
+	authority_type: spl_token_2022::instruction::AuthorityType,
+	signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+	let ix = spl_token_2022::instruction::set_authority(
+		token_program_info.key,
+		owned_info.key,
+		new_authority,
+		authority_type,
+		owner_info.key,
+		&[],
+	)?;
+	solana_program::program::invoke_signed(
+		&ix,
+		&[owned_info.clone(), owner_info.clone()],
+		signers_seeds,
+	)
+}
@@ -0,0 +1,432 @@
+//! Decode bitflip instructions and transactions into structured data for
+//! indexers and explorers.
+
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+
+use crate::interface::*;
+use crate::AccountMigrate;
+use crate::BadgeInitialize;
+use crate::BadgeMint;
+use crate::BitflipInstruction;
+use crate::BountyClaim;
+use crate::BountyCreate;
+use crate::ConfigInitialize;
+use crate::ConfigUpdateAuthority;
+use crate::FlipBit;
+use crate::FlipBitWithTokens;
+use crate::GameArtworkMint;
+use crate::GameFinalize;
+use crate::GameInitialize;
+use crate::GameResetSigners;
+use crate::GameSchedule;
+use crate::GameStart;
+use crate::GameUpdateCooldown;
+use crate::GameUpdateParameter;
+use crate::GameUpdateTempSigner;
+use crate::PlayerTokenAccountInitialize;
+use crate::ProposalCreate;
+use crate::ProposalExecute;
+use crate::ProposalVote;
+use crate::SectionGateUpdate;
+use crate::SectionLockCreate;
+use crate::SectionMetadataModerate;
+use crate::SectionMetadataUpdate;
+use crate::SectionMinesReveal;
+use crate::SectionSeed;
+use crate::SectionStep;
+use crate::SectionUnlock;
+use crate::StakeClaim;
+use crate::StakePoolUpdate;
+use crate::StakeTokens;
+use crate::TeamClaim;
+use crate::TeamJoin;
+use crate::TokenGroupInitialize;
+use crate::TokenInitialize;
+use crate::UnstakeTokens;
+use crate::VoteWithdraw;
+use crate::ID;
+
+/// The errors returned when decoding bitflip instructions.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum DecodeError {
+	#[error("The instruction belongs to the program {0} instead of the bitflip program")]
+	ProgramMismatch(Pubkey),
+	#[error("The instruction has no data")]
+	EmptyData,
+	#[error("The instruction discriminator {0} is unknown")]
+	UnknownInstruction(u8),
+	#[error("The data of the {0:?} instruction is invalid")]
+	InvalidData(BitflipInstruction),
+	#[error("The {instruction:?} instruction has {actual} accounts but requires {expected}")]
+	NotEnoughAccounts {
+		instruction: BitflipInstruction,
+		expected: usize,
+		actual: usize,
+	},
+	#[error("The address lookup table {0} is missing or doesn't contain the loaded account")]
+	LookupTableMissing(Pubkey),
+	#[error("The account index {0} is outside of the transaction accounts")]
+	AccountIndexInvalid(u8),
+}
+
+/// An account of a decoded instruction.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedAccount {
+	/// The name of the account or `None` for the optional remaining accounts.
+	pub name: Option<String>,
+	#[serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")]
+	pub pubkey: Pubkey,
+	pub is_signer: bool,
+	pub is_writable: bool,
+}
+
+/// A decoded bitflip instruction.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitflipDecoded {
+	/// The instruction and its typed arguments.
+	#[serde(flatten)]
+	pub args: BitflipDecodedArgs,
+	/// The named accounts followed by any remaining accounts.
+	pub accounts: Vec<DecodedAccount>,
+}
+
+impl BitflipDecoded {
+	/// The instruction which was decoded.
+	pub fn instruction(&self) -> BitflipInstruction {
+		self.args.instruction()
+	}
+
+	/// Get a named account of the instruction.
+	pub fn account(&self, name: &str) -> Option<&DecodedAccount> {
+		self.accounts
+			.iter()
+			.find(|account| account.name.as_deref() == Some(name))
+	}
+}
+
+macro_rules! decoded {
+	($($name:ident => $accounts:ident),* $(,)?) => {
+		/// The typed arguments of each bitflip instruction.
+		#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+		#[serde(tag = "instruction", content = "args", rename_all = "camelCase")]
+		pub enum BitflipDecodedArgs {
+			$($name($name),)*
+		}
+
+		impl BitflipDecodedArgs {
+			/// The instruction of the arguments.
+			pub fn instruction(&self) -> BitflipInstruction {
+				match self {
+					$(BitflipDecodedArgs::$name(_) => BitflipInstruction::$name,)*
+				}
+			}
+		}
+
+		fn decode_args(
+			instruction: BitflipInstruction,
+			data: &[u8],
+		) -> Result<(BitflipDecodedArgs, &'static [&'static str]), DecodeError> {
+			let decoded = match instruction {
+				$(
+					BitflipInstruction::$name => (
+						BitflipDecodedArgs::$name(
+							*$name::try_from_bytes(data)
+								.or(Err(DecodeError::InvalidData(instruction)))?,
+						),
+						$accounts::ACCOUNT_NAMES,
+					),
+				)*
+			};
+
+			Ok(decoded)
+		}
+	};
+}
+
+decoded! {
+	ConfigInitialize => ConfigInitializeAccounts,
+	ConfigUpdateAuthority => ConfigUpdateAuthorityAccounts,
+	TokenInitialize => TokenInitializeAccounts,
+	TokenGroupInitialize => TokenGroupInitializeAccounts,
+	GameInitialize => GameInitializeAccounts,
+	GameStart => GameStartAccounts,
+	GameUpdateTempSigner => GameUpdateTempSignerAccounts,
+	GameResetSigners => GameResetSignersAccounts,
+	SectionUnlock => SectionUnlockAccounts,
+	FlipBit => FlipBitAccounts,
+	SectionMinesReveal => SectionMinesRevealAccounts,
+	SectionStep => SectionStepAccounts,
+	BountyCreate => BountyCreateAccounts,
+	BountyClaim => BountyClaimAccounts,
+	GameFinalize => GameFinalizeAccounts,
+	GameArtworkMint => GameArtworkMintAccounts,
+	SectionMetadataUpdate => SectionMetadataUpdateAccounts,
+	SectionMetadataModerate => SectionMetadataModerateAccounts,
+	BadgeInitialize => BadgeInitializeAccounts,
+	BadgeMint => BadgeMintAccounts,
+	GameUpdateCooldown => GameUpdateCooldownAccounts,
+	SectionLockCreate => SectionLockCreateAccounts,
+	SectionGateUpdate => SectionGateUpdateAccounts,
+	FlipBitWithTokens => FlipBitWithTokensAccounts,
+	StakePoolUpdate => StakePoolUpdateAccounts,
+	StakeTokens => StakeTokensAccounts,
+	UnstakeTokens => UnstakeTokensAccounts,
+	StakeClaim => StakeClaimAccounts,
+	GameUpdateParameter => GameUpdateParameterAccounts,
+	ProposalCreate => ProposalCreateAccounts,
+	ProposalVote => ProposalVoteAccounts,
+	ProposalExecute => ProposalExecuteAccounts,
+	VoteWithdraw => VoteWithdrawAccounts,
+	TeamJoin => TeamJoinAccounts,
+	TeamClaim => TeamClaimAccounts,
+	GameSchedule => GameScheduleAccounts,
+	SectionSeed => SectionSeedAccounts,
+	PlayerTokenAccountInitialize => PlayerTokenAccountInitializeAccounts,
+	AccountMigrate => AccountMigrateAccounts,
+}
+
+/// Decode a bitflip instruction into its typed arguments and named accounts.
+pub fn decode_instruction(instruction: &Instruction) -> Result<BitflipDecoded, DecodeError> {
+	if instruction.program_id != ID {
+		return Err(DecodeError::ProgramMismatch(instruction.program_id));
+	}
+
+	let (discriminator, data) = instruction
+		.data
+		.split_first()
+		.ok_or(DecodeError::EmptyData)?;
+	let ix = BitflipInstruction::try_from(*discriminator)
+		.or(Err(DecodeError::UnknownInstruction(*discriminator)))?;
+	let (args, names) = decode_args(ix, data)?;
+
+	if instruction.accounts.len() < names.len() {
+		return Err(DecodeError::NotEnoughAccounts {
+			instruction: ix,
+			expected: names.len(),
+			actual: instruction.accounts.len(),
+		});
+	}
+
+	let accounts = instruction
+		.accounts
+		.iter()
+		.enumerate()
+		.map(|(index, meta)| {
+			DecodedAccount {
+				name: names.get(index).map(|name| name.to_string()),
+				pubkey: meta.pubkey,
+				is_signer: meta.is_signer,
+				is_writable: meta.is_writable,
+			}
+		})
+		.collect();
+
+	Ok(BitflipDecoded { args, accounts })
+}
+
+/// Decode every bitflip instruction of a transaction. Instructions of other
+/// programs are skipped.
+///
+/// The `lookup_tables` must contain every address lookup table used by a v0
+/// transaction.
+pub fn decode_transaction(
+	transaction: &VersionedTransaction,
+	lookup_tables: &[AddressLookupTableAccount],
+) -> Result<Vec<BitflipDecoded>, DecodeError> {
+	let message = &transaction.message;
+	let mut writable = vec![];
+	let mut readonly = vec![];
+
+	for lookup in message.address_table_lookups().unwrap_or_default() {
+		let table = lookup_tables
+			.iter()
+			.find(|table| table.key == lookup.account_key)
+			.ok_or(DecodeError::LookupTableMissing(lookup.account_key))?;
+		let load = |indexes: &[u8]| {
+			indexes
+				.iter()
+				.map(|index| {
+					table
+						.addresses
+						.get(usize::from(*index))
+						.copied()
+						.ok_or(DecodeError::LookupTableMissing(table.key))
+				})
+				.collect::<Result<Vec<_>, _>>()
+		};
+
+		writable.extend(load(&lookup.writable_indexes)?);
+		readonly.extend(load(&lookup.readonly_indexes)?);
+	}
+
+	// the loaded writable accounts follow the static accounts and precede the
+	// loaded readonly accounts.
+	let static_keys = message.static_account_keys();
+	let loaded_writable_end = static_keys.len() + writable.len();
+	let keys = static_keys
+		.iter()
+		.chain(&writable)
+		.chain(&readonly)
+		.copied()
+		.collect::<Vec<_>>();
+	let is_writable = |index: usize| {
+		if index < static_keys.len() {
+			message.is_maybe_writable(index, None)
+		} else {
+			index < loaded_writable_end
+		}
+	};
+
+	message
+		.instructions()
+		.iter()
+		.filter(|compiled| keys.get(usize::from(compiled.program_id_index)) == Some(&ID))
+		.map(|compiled| {
+			let accounts = compiled
+				.accounts
+				.iter()
+				.map(|account_index| {
+					let index = usize::from(*account_index);
+					let pubkey = *keys
+						.get(index)
+						.ok_or(DecodeError::AccountIndexInvalid(*account_index))?;
+
+					Ok(AccountMeta {
+						pubkey,
+						is_signer: message.is_signer(index),
+						is_writable: is_writable(index),
+					})
+				})
+				.collect::<Result<Vec<_>, DecodeError>>()?;
+
+			decode_instruction(&Instruction {
+				program_id: ID,
+				accounts,
+				data: compiled.data.clone(),
+			})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use assert2::let_assert;
+	use solana_sdk::hash::Hash;
+
+	use super::*;
+	use crate::create_versioned_transaction;
+	use crate::flip_bit;
+	use crate::get_game_lookup_table_addresses;
+	use crate::get_pda_game;
+	use crate::get_section_lookup_table_addresses;
+	use crate::team_join;
+	use crate::token_initialize;
+	use crate::Team;
+	use crate::TokenMember;
+
+	#[test]
+	fn should_decode_instruction() -> anyhow::Result<()> {
+		let player = Pubkey::new_unique();
+		let ix = flip_bit(&player, 0, 2, 3, 4, 1, 1_000);
+		let decoded = decode_instruction(&ix)?;
+		let_assert!(BitflipDecodedArgs::FlipBit(args) = decoded.args);
+
+		check!(decoded.instruction() == BitflipInstruction::FlipBit);
+		check!(args.section_index == 2);
+		check!(args.array_index == 3);
+		check!(args.offset == 4);
+		check!(args.max_lamports_per_bit() == 1_000);
+		check!(decoded.accounts.len() == ix.accounts.len());
+		check!(decoded.account("player").unwrap().pubkey == player);
+		check!(decoded.account("player").unwrap().is_signer);
+		check!(decoded.account("game").unwrap().pubkey == get_pda_game(0).0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn should_serialize_decoded_instruction() -> anyhow::Result<()> {
+		let authority = Pubkey::new_unique();
+		let decoded = decode_instruction(&token_initialize(&authority, TokenMember::Kibibit))?;
+		let json = serde_json::to_value(&decoded)?;
+
+		check!(json["instruction"] == "tokenInitialize");
+		check!(json["args"]["member"] == 1);
+		check!(json["accounts"][0]["name"] == "authority");
+		check!(json["accounts"][0]["pubkey"] == authority.to_string());
+
+		Ok(())
+	}
+
+	#[test]
+	fn should_reject_other_programs() {
+		let ix = Instruction::new_with_bytes(system_program::ID, &[9], vec![]);
+		let result = decode_instruction(&ix);
+
+		check!(result.unwrap_err() == DecodeError::ProgramMismatch(system_program::ID));
+	}
+
+	#[test]
+	fn should_reject_invalid_data() {
+		let player = Pubkey::new_unique();
+		let mut ix = flip_bit(&player, 0, 0, 0, 0, 1, u64::MAX);
+		ix.data.pop();
+		let result = decode_instruction(&ix);
+
+		check!(result.unwrap_err() == DecodeError::InvalidData(BitflipInstruction::FlipBit));
+	}
+
+	#[test]
+	fn should_require_named_accounts() {
+		let player = Pubkey::new_unique();
+		let mut ix = team_join(&player, 0, Team::On);
+		ix.accounts.pop();
+		let result = decode_instruction(&ix);
+
+		check!(
+			result.unwrap_err()
+				== DecodeError::NotEnoughAccounts {
+					instruction: BitflipInstruction::TeamJoin,
+					expected: 5,
+					actual: 4,
+				}
+		);
+	}
+
+	#[test]
+	fn should_decode_transaction_with_lookup_table() -> anyhow::Result<()> {
+		let player = Pubkey::new_unique();
+		let mut addresses = get_game_lookup_table_addresses(0);
+		addresses.extend(get_section_lookup_table_addresses(0, 0));
+		let lookup_table = AddressLookupTableAccount {
+			key: Pubkey::new_unique(),
+			addresses,
+		};
+		let ix = flip_bit(&player, 0, 0, 0, 0, 1, u64::MAX);
+		let transaction = create_versioned_transaction(
+			&player,
+			&[ix.clone()],
+			&[lookup_table.clone()],
+			Hash::default(),
+		)?;
+		let decoded = decode_transaction(&transaction, &[lookup_table])?;
+
+		check!(decoded.len() == 1);
+
+		for (account, meta) in decoded[0].accounts.iter().zip(&ix.accounts) {
+			check!(account.pubkey == meta.pubkey);
+			check!(account.is_signer == meta.is_signer);
+			check!(account.is_writable == meta.is_writable);
+		}
+
+		let result = decode_transaction(&transaction, &[]);
+		check!(result.is_err());
+
+		Ok(())
+	}
+}
@@ -54,6 +54,82 @@ pub enum BitflipError {
 	DuplicateAuthority = 21,
 	#[error("The authority is not authorized to update the authority")]
 	Unauthorized = 22,
+	#[error("The current `GameState` has not ended")]
+	GameNotEnded = 23,
+	#[error("The revealed mines do not match the commitment")]
+	MinesCommitmentInvalid = 24,
+	#[error("The mines for this section have already been revealed")]
+	MinesAlreadyRevealed = 25,
+	#[error("The bounty region must fit within the section")]
+	BountyRegionInvalid = 26,
+	#[error("The bounty expiry must be in the future")]
+	BountyExpiryInvalid = 27,
+	#[error("The bounty pattern is not matched and the bounty has not expired")]
+	BountyNotClaimable = 28,
+	#[error("The game canvas has already been finalized")]
+	GameAlreadyFinalized = 29,
+	#[error("The game canvas has not been finalized")]
+	GameNotFinalized = 30,
+	#[error("The section title or link is too long or not valid utf8")]
+	SectionMetadataInvalid = 31,
+	#[error("The section metadata has been moderated")]
+	SectionMetadataModerated = 32,
+	#[error("The badge has not been earned")]
+	BadgeNotEarned = 33,
+	#[error("The badge has already been minted")]
+	BadgeAlreadyClaimed = 34,
+	#[error("The bit was not in the expected state")]
+	BitUnexpected = 35,
+	#[error("The price of the flip is higher than the maximum price")]
+	PriceExceeded = 36,
+	#[error("The bits of this word were flipped too recently")]
+	BitCooldown = 37,
+	#[error("The region or duration of the lock is invalid")]
+	SectionLockInvalid = 38,
+	#[error("The section has reached the maximum number of active locks")]
+	SectionLocksFull = 39,
+	#[error("The bit is locked by another player")]
+	BitLocked = 40,
+	#[error("The player doesn't hold enough tokens to flip this section")]
+	SectionGated = 41,
+	#[error("The stake is still locked by the unstake cooldown")]
+	StakeLocked = 42,
+	#[error("The stake doesn't hold enough tokens")]
+	StakeInsufficient = 43,
+	#[error("The staking pool configuration is invalid")]
+	StakePoolInvalid = 44,
+	#[error("The value of the game parameter is invalid")]
+	GameParameterInvalid = 45,
+	#[error("The voting period of the proposal hasn't ended")]
+	ProposalActive = 46,
+	#[error("The voting period of the proposal has ended")]
+	ProposalClosed = 47,
+	#[error("The vote doesn't match the previous vote on the proposal")]
+	VoteInvalid = 48,
+	#[error("The game doesn't have team mode enabled")]
+	TeamModeDisabled = 49,
+	#[error("The player hasn't joined a team in this game")]
+	TeamNotJoined = 50,
+	#[error("The team of the player didn't win the game")]
+	TeamNotWinner = 51,
+	#[error("The team reward has already been claimed")]
+	TeamRewardClaimed = 52,
+	#[error("The scheduled start time of the game must be in the future")]
+	GameStartTimeInvalid = 53,
+	#[error("The range of seeded words doesn't fit within the section")]
+	SectionSeedRangeInvalid = 54,
+	#[error("The first game has no previous canvas to inherit")]
+	InheritInvalid = 55,
+	#[error("The section is reserved for its owner in the previous game")]
+	SectionReserved = 56,
+	#[error("The player token account must be created before flipping a bit")]
+	PlayerTokenAccountMissing = 57,
+	#[error("The price of the flip isn't a whole number of tokens of the payment member")]
+	TokenPaymentInexact = 58,
+	#[error("The section has already been unlocked by another owner")]
+	SectionAlreadyUnlocked = 59,
+	#[error("The first flip of the game must pass the game account as writable")]
+	FirstFlipNotWritable = 60,
 }
 
 error!(BitflipError);
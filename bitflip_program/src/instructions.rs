@@ -1,20 +1,79 @@
 use steel::*;
 
+use crate::get_pda_artwork_mint;
+use crate::get_pda_badge_mint;
+use crate::get_pda_bounty;
 use crate::get_pda_config;
 use crate::get_pda_game;
+use crate::get_pda_game_canvas;
 use crate::get_pda_mint;
+use crate::get_pda_player;
+use crate::get_pda_player_team;
+use crate::get_pda_proposal;
 use crate::get_pda_section;
+use crate::get_pda_section_cooldown;
+use crate::get_pda_section_locks;
+use crate::get_pda_section_metadata;
+use crate::get_pda_section_mines;
+use crate::get_pda_stake;
+use crate::get_pda_stake_pool;
+use crate::get_pda_team_pool;
 use crate::get_pda_treasury;
+use crate::get_pda_vote;
+use crate::get_player_badge_token_account;
+use crate::get_player_token_account;
+use crate::get_stake_pool_token_account;
 use crate::get_token_account;
+use crate::get_vote_token_account;
+use crate::AccountMigrate;
+use crate::BadgeInitialize;
+use crate::BadgeMint;
+use crate::BadgeType;
+use crate::BountyClaim;
+use crate::BountyCreate;
 use crate::ConfigInitialize;
 use crate::ConfigUpdateAuthority;
 use crate::FlipBit;
+use crate::FlipBitFlags;
+use crate::FlipBitWithTokens;
+use crate::GameArtworkMint;
+use crate::GameFinalize;
+use crate::GameFlags;
 use crate::GameInitialize;
+use crate::GameParameter;
+use crate::GameSchedule;
+use crate::GameState;
+use crate::GameUpdateCooldown;
+use crate::GameUpdateParameter;
 use crate::GameUpdateTempSigner;
+use crate::PlayerTokenAccountInitialize;
+use crate::PodSectionData;
+use crate::PodSectionRows;
+use crate::ProposalCreate;
+use crate::ProposalExecute;
+use crate::ProposalVote;
+use crate::SectionData;
+use crate::SectionEdge;
+use crate::SectionGateUpdate;
+use crate::SectionLockCreate;
+use crate::SectionMetadataModerate;
+use crate::SectionMetadataUpdate;
+use crate::SectionMinesReveal;
+use crate::SectionSeed;
+use crate::SectionStep;
 use crate::SectionUnlock;
+use crate::StakeClaim;
+use crate::StakePoolUpdate;
+use crate::StakeTokens;
+use crate::Team;
+use crate::TeamClaim;
+use crate::TeamJoin;
 use crate::TokenGroupInitialize;
 use crate::TokenInitialize;
 use crate::TokenMember;
+use crate::UnstakeTokens;
+use crate::VoteWithdraw;
+use crate::SECTION_SEED_WORDS;
 
 /// Create an instruction to initialize the mint, treasury and [`ConfigState`].
 ///
@@ -136,11 +195,16 @@ pub fn token_group_initialize(authority: &Pubkey) -> Instruction {
 /// * `authority` - The authority account: must be a signer.
 /// * `temp_signer` - The access signer account: must be a signer.
 /// * `funded_signer` - The refresh signer account: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `flags` - The modes which are enabled for the game.
+/// * `step_lamports` - The price of stepping a section by one generation.
 pub fn game_initialize(
 	authority: &Pubkey,
 	temp_signer: &Pubkey,
 	funded_signer: &Pubkey,
 	game_index: u8,
+	flags: GameFlags,
+	step_lamports: u64,
 ) -> Instruction {
 	let config = get_pda_config().0;
 	let game = get_pda_game(game_index).0;
@@ -155,7 +219,29 @@ pub fn game_initialize(
 			AccountMeta::new(game, false),
 			AccountMeta::new_readonly(system_program::ID, false),
 		],
-		data: GameInitialize {}.to_bytes(),
+		data: GameInitialize::new(flags, step_lamports).to_bytes(),
+	}
+}
+
+/// Create an instruction to schedule the game to start at a future time.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `start_time` - The unix timestamp at which the game starts running.
+pub fn game_schedule(authority: &Pubkey, game_index: u8, start_time: i64) -> Instruction {
+	let config = get_pda_config().0;
+	let game = get_pda_game(game_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new_readonly(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new(game, false),
+		],
+		data: GameSchedule::new(start_time).to_bytes(),
 	}
 }
 
@@ -224,11 +310,61 @@ pub fn game_reset_signers(
 	}
 }
 
+/// Create an instruction to create the player's `$BIT` token account. This
+/// must be sent before the first flip which is paid with lamports.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+pub fn player_token_account_initialize(player: &Pubkey) -> Instruction {
+	let mint = get_pda_mint(TokenMember::Bit).0;
+	let player_token_account = get_token_account(player, &mint);
+	let config = get_pda_config().0;
+	let associated_token_program = spl_associated_token_account::ID;
+	let token_program = spl_token_2022::ID;
+	let system_program = system_program::ID;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*player, true),
+			AccountMeta::new(player_token_account, false),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(mint, false),
+			AccountMeta::new_readonly(associated_token_program, false),
+			AccountMeta::new_readonly(token_program, false),
+			AccountMeta::new_readonly(system_program, false),
+		],
+		data: PlayerTokenAccountInitialize {}.to_bytes(),
+	}
+}
+
+/// Create an instruction to migrate a program account to the latest version
+/// of its state.
+///
+/// ### Arguments
+///
+/// * `payer` - The payer of any extra rent: must be a signer.
+/// * `account` - The program account to migrate.
+pub fn account_migrate(payer: &Pubkey, account: &Pubkey) -> Instruction {
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*payer, true),
+			AccountMeta::new(*account, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: AccountMigrate {}.to_bytes(),
+	}
+}
+
 /// Create an instruction to set a bit on the player's bit token account.
 ///
 /// ### Arguments
 ///
 /// * `player` - The player account: must be a signer.
+/// * `max_lamports_per_bit` - The maximum price per flip the player is willing
+///   to pay. The instruction fails when the price has moved above this.
 pub fn flip_bit(
 	player: &Pubkey,
 	game_index: u8,
@@ -236,6 +372,7 @@ pub fn flip_bit(
 	array_index: u8,
 	offset: u8,
 	value: u8,
+	max_lamports_per_bit: u64,
 ) -> Instruction {
 	let mint = get_pda_mint(TokenMember::Bit).0;
 	let player_token_account = get_token_account(player, &mint);
@@ -246,13 +383,17 @@ pub fn flip_bit(
 	let associated_token_program = spl_associated_token_account::ID;
 	let token_program = spl_token_2022::ID;
 	let system_program = system_program::ID;
+	let player_state = get_pda_player(game_index, player).0;
+	let section_locks = get_pda_section_locks(game_index, section_index).0;
+	let section_metadata = get_pda_section_metadata(game_index, section_index).0;
 	let data = FlipBit::builder()
 		.section_index(section_index)
 		.array_index(array_index)
 		.offset(offset)
 		.value(value)
-		.build()
-		.to_bytes();
+		.max_lamports_per_bit(max_lamports_per_bit)
+		.build();
+	let data = with_flip_bumps(data, player, game_index).to_bytes();
 
 	Instruction {
 		program_id: crate::ID,
@@ -267,11 +408,329 @@ pub fn flip_bit(
 			AccountMeta::new_readonly(associated_token_program, false),
 			AccountMeta::new_readonly(token_program, false),
 			AccountMeta::new_readonly(system_program, false),
+			AccountMeta::new(player_state, false),
+			AccountMeta::new_readonly(section_locks, false),
+			AccountMeta::new_readonly(section_metadata, false),
 		],
 		data,
 	}
 }
 
+/// Set the canonical bumps of the player state, section locks and section
+/// metadata so that the program doesn't need to search for them.
+fn with_flip_bumps(mut flip: FlipBit, player: &Pubkey, game_index: u8) -> FlipBit {
+	flip.player_bump = get_pda_player(game_index, player).1;
+	flip.section_locks_bump = get_pda_section_locks(game_index, flip.section_index).1;
+	flip.section_metadata_bump = get_pda_section_metadata(game_index, flip.section_index).1;
+	flip
+}
+
+/// Create an instruction to set a bit with the compare-and-swap and skip
+/// options of [`FlipBitFlags`].
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `expected` - The value the bit must have before the flip or the
+///   instruction fails.
+/// * `skip_unchanged` - Skip the payment when the bit already has the `value`.
+#[allow(clippy::too_many_arguments)]
+pub fn flip_bit_checked(
+	player: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	array_index: u8,
+	offset: u8,
+	value: u8,
+	max_lamports_per_bit: u64,
+	expected: Option<bool>,
+	skip_unchanged: bool,
+) -> Instruction {
+	let mut flags = FlipBitFlags::empty();
+	flags.set(FlipBitFlags::EXPECTED, expected.is_some());
+	flags.set(FlipBitFlags::SKIP_UNCHANGED, skip_unchanged);
+
+	let mut instruction = flip_bit(
+		player,
+		game_index,
+		section_index,
+		array_index,
+		offset,
+		value,
+		max_lamports_per_bit,
+	);
+	let flip = FlipBit::builder()
+		.section_index(section_index)
+		.array_index(array_index)
+		.offset(offset)
+		.value(value)
+		.max_lamports_per_bit(max_lamports_per_bit)
+		.expected(expected.unwrap_or_default().into())
+		.flags(flags.bits())
+		.build();
+	instruction.data = with_flip_bumps(flip, player, game_index).to_bytes();
+
+	instruction
+}
+
+/// Pass the game account of a flip instruction as writable so that the flip can
+/// earn [`crate::BadgeType::FirstFlip`]. This write locks the game account and
+/// should only be used while [`crate::GameState::flipped`] is unset.
+///
+/// ### Arguments
+///
+/// * `instruction` - Any of the `flip_bit` instructions.
+pub fn with_first_flip(mut instruction: Instruction) -> Instruction {
+	if let Some(game) = instruction.accounts.get_mut(3) {
+		game.is_writable = true;
+	}
+
+	instruction
+}
+
+/// Create an instruction to set a bit and pay for the flip with tokens of the
+/// `member` instead of lamports.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer and hold enough tokens of
+///   the `member`.
+/// * `max_lamports_per_bit` - The maximum lamport price per flip the player is
+///   willing to pay before it is converted to tokens.
+/// * `member` - The [`TokenMember`] used to pay for the flip.
+#[allow(clippy::too_many_arguments)]
+pub fn flip_bit_with_tokens(
+	player: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	array_index: u8,
+	offset: u8,
+	value: u8,
+	max_lamports_per_bit: u64,
+	member: TokenMember,
+) -> Instruction {
+	let mint = get_pda_mint(member).0;
+	let section = get_pda_section(game_index, section_index).0;
+	let mut instruction = flip_bit(
+		player,
+		game_index,
+		section_index,
+		array_index,
+		offset,
+		value,
+		max_lamports_per_bit,
+	);
+	let flip = FlipBit::builder()
+		.section_index(section_index)
+		.array_index(array_index)
+		.offset(offset)
+		.value(value)
+		.max_lamports_per_bit(max_lamports_per_bit)
+		.build();
+	let flip = with_flip_bumps(flip, player, game_index);
+
+	// the payment is burned from the supply of the mint.
+	instruction.accounts[1] = AccountMeta::new(get_token_account(player, &mint), false);
+	instruction.accounts[4] = AccountMeta::new(mint, false);
+	instruction.accounts[6] = AccountMeta::new(get_token_account(&section, &mint), false);
+	instruction.data = FlipBitWithTokens::new(flip, member).to_bytes();
+
+	instruction
+}
+
+/// The optional accounts of a flip which depend on the settings of the game
+/// and of the section. See [`flip_bit_with_options`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlipBitOptions {
+	/// The [`GameFlags`] of the game.
+	pub flags: GameFlags,
+	/// Whether the game has a cooldown, see [`crate::GameState::cooldown`].
+	pub cooldown: bool,
+	/// The temp signer which co-signs the flips of a minesweeper game.
+	pub temp_signer: Option<Pubkey>,
+	/// Whether the revealed bit of a minesweeper game is a mine.
+	pub mine: bool,
+	/// The [`TokenMember`] which gates the section.
+	pub gate: Option<TokenMember>,
+}
+
+impl FlipBitOptions {
+	/// The options of a flip in a game with the `flags` which has a cooldown
+	/// when `cooldown` is set.
+	pub fn new(flags: GameFlags, cooldown: bool) -> Self {
+		Self {
+			flags,
+			cooldown,
+			temp_signer: None,
+			mine: false,
+			gate: None,
+		}
+	}
+
+	/// The options of a flip in the `game`.
+	pub fn from_game(game: &GameState) -> Self {
+		Self::new(game.flags(), game.cooldown())
+	}
+
+	/// Set the `temp_signer` which reports whether the bit of a minesweeper
+	/// game is a `mine`. Only the backend knows the mine layout so it builds
+	/// the instruction and partially signs the transaction as the temp signer.
+	pub fn minesweeper(mut self, temp_signer: Pubkey, mine: bool) -> Self {
+		self.temp_signer = Some(temp_signer);
+		self.mine = mine;
+		self
+	}
+
+	/// Set the [`TokenMember`] which gates the section.
+	pub fn gate(mut self, gate: TokenMember) -> Self {
+		self.gate = Some(gate);
+		self
+	}
+}
+
+/// Create an instruction to set a bit with the optional accounts required by
+/// the game and the section. The accounts are appended after the accounts of
+/// [`flip_bit`] in the order expected by the program:
+///
+/// 1. The temp signer and the section mines of a minesweeper game. The temp
+///    signer must be set with [`FlipBitOptions::minesweeper`].
+/// 2. The section cooldown of a game with a cooldown, which is created on the
+///    first flip of the section.
+/// 3. The player team and the team pool of a team game. The player must have
+///    joined a team with [`team_join`].
+/// 4. The player's token account for the gate of a gated section.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `max_lamports_per_bit` - The maximum price per flip the player is willing
+///   to pay. This should account for the cooldown multiplier when the word was
+///   flipped recently.
+/// * `options` - The [`FlipBitOptions`] of the game and the section.
+#[allow(clippy::too_many_arguments)]
+pub fn flip_bit_with_options(
+	player: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	array_index: u8,
+	offset: u8,
+	value: u8,
+	max_lamports_per_bit: u64,
+	options: &FlipBitOptions,
+) -> Instruction {
+	let mut instruction = flip_bit(
+		player,
+		game_index,
+		section_index,
+		array_index,
+		offset,
+		value,
+		max_lamports_per_bit,
+	);
+
+	if options.flags.contains(GameFlags::MINESWEEPER) {
+		let temp_signer = options.temp_signer.unwrap_or_default();
+		let section_mines = get_pda_section_mines(game_index, section_index).0;
+		let flip = FlipBit::builder()
+			.section_index(section_index)
+			.array_index(array_index)
+			.offset(offset)
+			.value(value)
+			.max_lamports_per_bit(max_lamports_per_bit)
+			.mine(options.mine.into())
+			.build();
+		instruction.data = with_flip_bumps(flip, player, game_index).to_bytes();
+		instruction.accounts.extend([
+			AccountMeta::new_readonly(temp_signer, true),
+			AccountMeta::new(section_mines, false),
+		]);
+	}
+
+	if options.cooldown {
+		let section_cooldown = get_pda_section_cooldown(game_index, section_index).0;
+		instruction
+			.accounts
+			.push(AccountMeta::new(section_cooldown, false));
+	}
+
+	if options.flags.contains(GameFlags::TEAMS) {
+		let player_team = get_pda_player_team(game_index, player).0;
+		let team_pool = get_pda_team_pool(game_index).0;
+		instruction.accounts.extend([
+			AccountMeta::new(player_team, false),
+			AccountMeta::new(team_pool, false),
+		]);
+	}
+
+	if let Some(gate) = options.gate {
+		let player_gate_token_account = get_player_token_account(player, gate);
+		instruction
+			.accounts
+			.push(AccountMeta::new_readonly(player_gate_token_account, false));
+	}
+
+	instruction
+}
+
+/// Create an instruction to seed the initial art of a section before the game
+/// starts.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the seeded section.
+/// * `start` - The index of the first word of the section data to write.
+/// * `words` - The words to write: at most [`crate::SECTION_SEED_WORDS`].
+pub fn section_seed(
+	authority: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	start: u8,
+	words: &[u16],
+) -> Instruction {
+	let config = get_pda_config().0;
+	let game = get_pda_game(game_index).0;
+	let section = get_pda_section(game_index, section_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(section, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: SectionSeed::new(section_index, start, words).to_bytes(),
+	}
+}
+
+/// Create the batched [`section_seed`] instructions which write the full
+/// `data` of a section. Every chunk is written, including the chunks which are
+/// entirely zero, so that seeding a section again replaces its earlier data.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the seeded section.
+/// * `data` - The section data in the `4x4` block layout.
+pub fn section_seed_batch(
+	authority: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	data: &SectionData,
+) -> Vec<Instruction> {
+	data.chunks(SECTION_SEED_WORDS)
+		.enumerate()
+		.map(|(chunk, words)| {
+			let start = (chunk * SECTION_SEED_WORDS) as u8;
+			section_seed(authority, game_index, section_index, start, words)
+		})
+		.collect()
+}
+
 /// Create an instruction to unlock a section.
 ///
 /// This instruction will be paired with an advance nonce instruction where the
@@ -285,19 +744,73 @@ pub fn flip_bit(
 /// * `section_index` - The index of the section.
 /// * `lamports` - The amount of lamports that is being bid on the section. The
 ///   highest bid will win.
+/// * `mines_commitment` - The commitment to the mine layout of the section:
+///   required for minesweeper games.
 pub fn section_unlock(
 	owner: &Pubkey,
 	temp_signer: &Pubkey,
 	game_index: u8,
 	section_index: u8,
 	lamports: u64,
+	mines_commitment: Option<[u8; 32]>,
+) -> Instruction {
+	create_section_unlock(
+		owner,
+		temp_signer,
+		game_index,
+		section_index,
+		lamports,
+		mines_commitment,
+		false,
+	)
+}
+
+/// Create an instruction to unlock a section in a game which inherits the
+/// canvas of the previous game. The previous game must be finalized.
+///
+/// ### Arguments
+///
+/// * `owner` - The owner account: must be a signer. During the reserved window
+///   this must be the owner of the section in the previous game.
+/// * `temp_signer` - The access signer: must be a signer.
+/// * `lamports` - The amount of lamports that is being bid on the section.
+pub fn section_unlock_inherited(
+	owner: &Pubkey,
+	temp_signer: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	lamports: u64,
+	mines_commitment: Option<[u8; 32]>,
+) -> Instruction {
+	create_section_unlock(
+		owner,
+		temp_signer,
+		game_index,
+		section_index,
+		lamports,
+		mines_commitment,
+		true,
+	)
+}
+
+/// The shared builder of [`section_unlock`] and [`section_unlock_inherited`].
+/// The optional stake pool is always the last account.
+fn create_section_unlock(
+	owner: &Pubkey,
+	temp_signer: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	lamports: u64,
+	mines_commitment: Option<[u8; 32]>,
+	inherit: bool,
 ) -> Instruction {
 	let config = get_pda_config().0;
 	let game = get_pda_game(game_index).0;
 	let section = get_pda_section(game_index, section_index).0;
 	let treasury = get_pda_treasury().0;
 	let system_program = system_program::ID;
-	let accounts = vec![
+	let stake_pool = get_pda_stake_pool().0;
+	let mut accounts = vec![
 		AccountMeta::new(*owner, true),
 		AccountMeta::new_readonly(*temp_signer, true),
 		AccountMeta::new_readonly(config, false),
@@ -306,8 +819,27 @@ pub fn section_unlock(
 		AccountMeta::new(treasury, false),
 		AccountMeta::new_readonly(system_program, false),
 	];
+
+	if mines_commitment.is_some() {
+		let section_mines = get_pda_section_mines(game_index, section_index).0;
+		accounts.push(AccountMeta::new(section_mines, false));
+	}
+
+	if inherit {
+		let previous_game_index = game_index.saturating_sub(1);
+		let previous_game = get_pda_game(previous_game_index).0;
+		let previous_section = get_pda_section(previous_game_index, section_index).0;
+		accounts.extend([
+			AccountMeta::new_readonly(previous_game, false),
+			AccountMeta::new_readonly(previous_section, false),
+		]);
+	}
+
+	accounts.push(AccountMeta::new(stake_pool, false));
+
 	let data = SectionUnlock {
 		lamports: lamports.into(),
+		mines_commitment: mines_commitment.unwrap_or_default(),
 	}
 	.to_bytes();
 
@@ -317,3 +849,778 @@ pub fn section_unlock(
 		data,
 	}
 }
+
+/// Create an instruction to reveal the mines of a section once the game has
+/// ended.
+///
+/// ### Arguments
+///
+/// * `temp_signer` - The temp signer of the game: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `salt` - The salt used when creating the mines commitment.
+/// * `mines` - The layout of the mines in the section.
+pub fn section_mines_reveal(
+	temp_signer: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	salt: [u8; 32],
+	mines: PodSectionData,
+) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let section_mines = get_pda_section_mines(game_index, section_index).0;
+	let data = SectionMinesReveal {
+		section_index,
+		salt,
+		mines,
+	}
+	.to_bytes();
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new_readonly(*temp_signer, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(section_mines, false),
+		],
+		data,
+	}
+}
+
+/// Create an instruction to step a section forward by one generation of
+/// Conway's Game of Life.
+///
+/// ### Arguments
+///
+/// * `player` - The player account which pays for the step: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `edge` - How the cells on the border of the section are treated.
+pub fn section_step(
+	player: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	edge: SectionEdge,
+) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let section = get_pda_section(game_index, section_index).0;
+	let (section_locks, section_locks_bump) = get_pda_section_locks(game_index, section_index);
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*player, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(section, false),
+			AccountMeta::new_readonly(section_locks, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: SectionStep::new(section_index, edge, section_locks_bump).to_bytes(),
+	}
+}
+
+/// Create an instruction to post a bounty on a rectangular region of a
+/// section.
+///
+/// ### Arguments
+///
+/// * `poster` - The account which escrows the reward: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `bounty_index` - The index of the bounty which must be unique for the
+///   poster and section.
+/// * `region` - The `(x, y, width, height)` of the region.
+/// * `pattern` - The target pattern. See [`crate::BountyState::pattern`].
+/// * `reward` - The lamports paid to the claimer.
+/// * `expiry` - The unix timestamp after which the bounty is refunded.
+#[allow(clippy::too_many_arguments)]
+pub fn bounty_create(
+	poster: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	bounty_index: u16,
+	region: (u8, u8, u8, u8),
+	pattern: PodSectionRows,
+	reward: u64,
+	expiry: i64,
+) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let section = get_pda_section(game_index, section_index).0;
+	let bounty = get_pda_bounty(game_index, section_index, poster, bounty_index).0;
+	let (x, y, width, height) = region;
+	let data = BountyCreate {
+		reward: reward.into(),
+		expiry: expiry.into(),
+		pattern,
+		bounty_index: bounty_index.into(),
+		section_index,
+		x,
+		y,
+		width,
+		height,
+	}
+	.to_bytes();
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*poster, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new_readonly(section, false),
+			AccountMeta::new(bounty, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data,
+	}
+}
+
+/// Create an instruction to claim a bounty, or refund it to the poster once it
+/// has expired.
+///
+/// ### Arguments
+///
+/// * `claimer` - The account which receives the reward when the pattern is
+///   matched: must be a signer.
+/// * `poster` - The account which posted the bounty.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `bounty_index` - The index of the bounty.
+pub fn bounty_claim(
+	claimer: &Pubkey,
+	poster: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	bounty_index: u16,
+) -> Instruction {
+	let section = get_pda_section(game_index, section_index).0;
+	let bounty = get_pda_bounty(game_index, section_index, poster, bounty_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*claimer, true),
+			AccountMeta::new(*poster, false),
+			AccountMeta::new_readonly(section, false),
+			AccountMeta::new(bounty, false),
+		],
+		data: BountyClaim {}.to_bytes(),
+	}
+}
+
+/// Create an instruction to hash a batch of sections into the canvas merkle
+/// tree once the game has ended.
+///
+/// ### Arguments
+///
+/// * `payer` - The account which pays for the canvas account: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_indices` - The sections to hash which must continue from the last
+///   hashed section.
+pub fn game_finalize(payer: &Pubkey, game_index: u8, section_indices: &[u8]) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let game_canvas = get_pda_game_canvas(game_index).0;
+	let team_pool = get_pda_team_pool(game_index).0;
+	let mut accounts = vec![
+		AccountMeta::new(*payer, true),
+		AccountMeta::new(game, false),
+		AccountMeta::new(game_canvas, false),
+		AccountMeta::new_readonly(system_program::ID, false),
+		AccountMeta::new(team_pool, false),
+	];
+	accounts.extend(section_indices.iter().map(|section_index| {
+		AccountMeta::new_readonly(get_pda_section(game_index, *section_index).0, false)
+	}));
+
+	Instruction {
+		program_id: crate::ID,
+		accounts,
+		data: GameFinalize {}.to_bytes(),
+	}
+}
+
+/// Create an instruction to mint the final artwork of a finalized game.
+///
+/// ### Arguments
+///
+/// * `authority` - The authority account: must be a signer.
+/// * `game_index` - The index of the game.
+pub fn game_artwork_mint(authority: &Pubkey, game_index: u8) -> Instruction {
+	let config = get_pda_config().0;
+	let treasury = get_pda_treasury().0;
+	let game = get_pda_game(game_index).0;
+	let artwork_mint = get_pda_artwork_mint(game_index).0;
+	let treasury_artwork_token_account = get_token_account(&treasury, &artwork_mint);
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(treasury, false),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(artwork_mint, false),
+			AccountMeta::new(treasury_artwork_token_account, false),
+			AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+			AccountMeta::new_readonly(spl_token_2022::ID, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: GameArtworkMint {}.to_bytes(),
+	}
+}
+
+/// Create an instruction to set the title, link and colors of a section.
+///
+/// ### Arguments
+///
+/// * `owner` - The owner of the section: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `title` - The title which is truncated to
+///   [`crate::SECTION_TITLE_MAX_LENGTH`] bytes.
+/// * `link` - The external link which is truncated to
+///   [`crate::SECTION_LINK_MAX_LENGTH`] bytes.
+/// * `foreground` - The rgb color of the bits which are on.
+/// * `background` - The rgb color of the bits which are off.
+pub fn section_metadata_update(
+	owner: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	title: &str,
+	link: &str,
+	foreground: [u8; 3],
+	background: [u8; 3],
+) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let section = get_pda_section(game_index, section_index).0;
+	let section_metadata = get_pda_section_metadata(game_index, section_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*owner, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new_readonly(section, false),
+			AccountMeta::new(section_metadata, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: SectionMetadataUpdate::new(section_index, title, link, foreground, background)
+			.to_bytes(),
+	}
+}
+
+/// Create an instruction to configure the per-word cooldown of a game.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `cooldown_slots` - The number of slots a word is cooling down for after it
+///   is flipped. Set to `0` to disable the cooldown.
+/// * `cooldown_multiplier` - The price multiplier while a word is cooling down.
+///   Set to `0` to reject the flip instead.
+pub fn game_update_cooldown(
+	authority: &Pubkey,
+	game_index: u8,
+	cooldown_slots: u32,
+	cooldown_multiplier: u8,
+) -> Instruction {
+	let config = get_pda_config().0;
+	let game = get_pda_game(game_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new_readonly(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new(game, false),
+		],
+		data: GameUpdateCooldown::new(cooldown_slots, cooldown_multiplier).to_bytes(),
+	}
+}
+
+/// Create an instruction to buy a temporary lock over a region of a section.
+///
+/// ### Arguments
+///
+/// * `holder` - The player buying the lock: must be a signer.
+/// * `owner` - The owner of the section which receives the payment.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `x` - The left coordinate of the region.
+/// * `y` - The top coordinate of the region.
+/// * `width` - The width of the region.
+/// * `height` - The height of the region.
+/// * `duration` - How long the lock lasts in seconds.
+#[allow(clippy::too_many_arguments)]
+pub fn section_lock_create(
+	holder: &Pubkey,
+	owner: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	x: u8,
+	y: u8,
+	width: u8,
+	height: u8,
+	duration: i64,
+) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let section = get_pda_section(game_index, section_index).0;
+	let section_locks = get_pda_section_locks(game_index, section_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*holder, true),
+			AccountMeta::new(*owner, false),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new_readonly(section, false),
+			AccountMeta::new(section_locks, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: SectionLockCreate {
+			duration: duration.into(),
+			section_index,
+			x,
+			y,
+			width,
+			height,
+		}
+		.to_bytes(),
+	}
+}
+
+/// Create an instruction to gate the flips of a section behind a minimum token
+/// balance.
+///
+/// ### Arguments
+///
+/// * `signer` - The section owner or config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `member` - The token which players must hold.
+/// * `amount` - The minimum balance including decimals. Set to `0` to remove
+///   the gate.
+pub fn section_gate_update(
+	signer: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	member: TokenMember,
+	amount: u64,
+) -> Instruction {
+	let config = get_pda_config().0;
+	let game = get_pda_game(game_index).0;
+	let section = get_pda_section(game_index, section_index).0;
+	let section_metadata = get_pda_section_metadata(game_index, section_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*signer, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new_readonly(section, false),
+			AccountMeta::new(section_metadata, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: SectionGateUpdate::new(section_index, member, amount).to_bytes(),
+	}
+}
+
+/// Create an instruction to moderate the metadata of a section.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `section_index` - The index of the section.
+/// * `moderated` - Whether to moderate or lift the moderation.
+pub fn section_metadata_moderate(
+	authority: &Pubkey,
+	game_index: u8,
+	section_index: u8,
+	moderated: bool,
+) -> Instruction {
+	let config = get_pda_config().0;
+	let section_metadata = get_pda_section_metadata(game_index, section_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new_readonly(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new(section_metadata, false),
+		],
+		data: SectionMetadataModerate {
+			moderated: moderated.into(),
+		}
+		.to_bytes(),
+	}
+}
+
+/// Create an instruction to initialize the non-transferable mint of a badge.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `badge` - The [`BadgeType`] of the mint.
+pub fn badge_initialize(authority: &Pubkey, badge: BadgeType) -> Instruction {
+	let config = get_pda_config().0;
+	let treasury = get_pda_treasury().0;
+	let badge_mint = get_pda_badge_mint(badge).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(treasury, false),
+			AccountMeta::new(badge_mint, false),
+			AccountMeta::new_readonly(spl_token_2022::ID, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: BadgeInitialize::new(badge).to_bytes(),
+	}
+}
+
+/// Create an instruction to mint an earned badge to the player.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `game_index` - The index of the game where the badge was earned.
+/// * `badge` - The [`BadgeType`] to mint.
+pub fn badge_mint(player: &Pubkey, game_index: u8, badge: BadgeType) -> Instruction {
+	let config = get_pda_config().0;
+	let player_state = get_pda_player(game_index, player).0;
+	let treasury = get_pda_treasury().0;
+	let badge_mint = get_pda_badge_mint(badge).0;
+	let player_badge_token_account = get_player_badge_token_account(player, badge);
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*player, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new(player_state, false),
+			AccountMeta::new_readonly(treasury, false),
+			AccountMeta::new(badge_mint, false),
+			AccountMeta::new(player_badge_token_account, false),
+			AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+			AccountMeta::new_readonly(spl_token_2022::ID, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: BadgeMint::new(badge).to_bytes(),
+	}
+}
+
+/// Create an instruction to configure the staking pool. The pool is created by
+/// the first update.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `revenue_basis_points` - The share of each section unlock bid paid to the
+///   stakers.
+/// * `unstake_cooldown` - The seconds tokens are locked for after staking.
+pub fn stake_pool_update(
+	authority: &Pubkey,
+	revenue_basis_points: u16,
+	unstake_cooldown: i64,
+) -> Instruction {
+	let config = get_pda_config().0;
+	let stake_pool = get_pda_stake_pool().0;
+	let system_program = system_program::ID;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new(stake_pool, false),
+			AccountMeta::new_readonly(system_program, false),
+		],
+		data: StakePoolUpdate::new(revenue_basis_points, unstake_cooldown).to_bytes(),
+	}
+}
+
+/// Create an instruction to stake tokens in the staking pool.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `member` - The [`TokenMember`] being staked.
+/// * `amount` - The number of tokens to stake.
+pub fn stake_tokens(player: &Pubkey, member: TokenMember, amount: u64) -> Instruction {
+	Instruction {
+		program_id: crate::ID,
+		accounts: get_stake_tokens_accounts(player, member),
+		data: StakeTokens::new(member, amount).to_bytes(),
+	}
+}
+
+/// Create an instruction to unstake tokens from the staking pool once the
+/// unstake cooldown has passed.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `member` - The [`TokenMember`] being unstaked.
+/// * `amount` - The number of tokens to unstake.
+pub fn unstake_tokens(player: &Pubkey, member: TokenMember, amount: u64) -> Instruction {
+	Instruction {
+		program_id: crate::ID,
+		accounts: get_stake_tokens_accounts(player, member),
+		data: UnstakeTokens::new(member, amount).to_bytes(),
+	}
+}
+
+fn get_stake_tokens_accounts(player: &Pubkey, member: TokenMember) -> Vec<AccountMeta> {
+	let mint = get_pda_mint(member).0;
+	let player_token_account = get_token_account(player, &mint);
+	let config = get_pda_config().0;
+	let stake_pool = get_pda_stake_pool().0;
+	let stake_pool_token_account = get_stake_pool_token_account(member);
+	let stake = get_pda_stake(player).0;
+	let associated_token_program = spl_associated_token_account::ID;
+	let token_program = spl_token_2022::ID;
+	let system_program = system_program::ID;
+
+	vec![
+		AccountMeta::new(*player, true),
+		AccountMeta::new(player_token_account, false),
+		AccountMeta::new_readonly(config, false),
+		AccountMeta::new_readonly(mint, false),
+		AccountMeta::new(stake_pool, false),
+		AccountMeta::new(stake_pool_token_account, false),
+		AccountMeta::new(stake, false),
+		AccountMeta::new_readonly(associated_token_program, false),
+		AccountMeta::new_readonly(token_program, false),
+		AccountMeta::new_readonly(system_program, false),
+	]
+}
+
+/// Create an instruction to claim the lamports earned by the stake of the
+/// player.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+pub fn stake_claim(player: &Pubkey) -> Instruction {
+	let stake_pool = get_pda_stake_pool().0;
+	let stake = get_pda_stake(player).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*player, true),
+			AccountMeta::new(stake_pool, false),
+			AccountMeta::new(stake, false),
+		],
+		data: StakeClaim {}.to_bytes(),
+	}
+}
+
+/// Create an instruction to update a parameter of a game.
+///
+/// ### Arguments
+///
+/// * `authority` - The config authority: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `parameter` - The [`GameParameter`] being updated.
+/// * `value` - The new value of the parameter.
+pub fn game_update_parameter(
+	authority: &Pubkey,
+	game_index: u8,
+	parameter: GameParameter,
+	value: u64,
+) -> Instruction {
+	let config = get_pda_config().0;
+	let game = get_pda_game(game_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new_readonly(*authority, true),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new(game, false),
+		],
+		data: GameUpdateParameter::new(parameter, value).to_bytes(),
+	}
+}
+
+/// Create an instruction to propose a change to a parameter of a game.
+///
+/// ### Arguments
+///
+/// * `proposer` - The account creating the proposal: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `proposal_index` - An index which is unique among the proposals of the
+///   `proposer` for this game.
+/// * `parameter` - The [`GameParameter`] being changed.
+/// * `value` - The proposed value of the parameter.
+pub fn proposal_create(
+	proposer: &Pubkey,
+	game_index: u8,
+	proposal_index: u16,
+	parameter: GameParameter,
+	value: u64,
+) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let proposal = get_pda_proposal(game_index, proposer, proposal_index).0;
+	let system_program = system_program::ID;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*proposer, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(proposal, false),
+			AccountMeta::new_readonly(system_program, false),
+		],
+		data: ProposalCreate::new(proposal_index, parameter, value).to_bytes(),
+	}
+}
+
+/// Create an instruction to vote on a proposal by escrowing tokens.
+///
+/// ### Arguments
+///
+/// * `voter` - The token holder: must be a signer.
+/// * `proposal` - The proposal being voted on.
+/// * `member` - The [`TokenMember`] being escrowed.
+/// * `amount` - The number of tokens to escrow.
+/// * `approve` - Whether the vote approves the proposal.
+pub fn proposal_vote(
+	voter: &Pubkey,
+	proposal: &Pubkey,
+	member: TokenMember,
+	amount: u64,
+	approve: bool,
+) -> Instruction {
+	let mint = get_pda_mint(member).0;
+	let voter_token_account = get_token_account(voter, &mint);
+	let config = get_pda_config().0;
+	let vote = get_pda_vote(proposal, voter).0;
+	let vote_token_account = get_vote_token_account(proposal, voter, member);
+	let associated_token_program = spl_associated_token_account::ID;
+	let token_program = spl_token_2022::ID;
+	let system_program = system_program::ID;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*voter, true),
+			AccountMeta::new(voter_token_account, false),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(mint, false),
+			AccountMeta::new(*proposal, false),
+			AccountMeta::new(vote, false),
+			AccountMeta::new(vote_token_account, false),
+			AccountMeta::new_readonly(associated_token_program, false),
+			AccountMeta::new_readonly(token_program, false),
+			AccountMeta::new_readonly(system_program, false),
+		],
+		data: ProposalVote::new(member, amount, approve).to_bytes(),
+	}
+}
+
+/// Create an instruction to close the voting on a proposal and apply the
+/// change when it passed.
+///
+/// ### Arguments
+///
+/// * `game_index` - The index of the game.
+/// * `proposal` - The proposal being executed.
+pub fn proposal_execute(game_index: u8, proposal: &Pubkey) -> Instruction {
+	let game = get_pda_game(game_index).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(game, false),
+			AccountMeta::new(*proposal, false),
+		],
+		data: ProposalExecute {}.to_bytes(),
+	}
+}
+
+/// Create an instruction to return the tokens escrowed by a vote.
+///
+/// ### Arguments
+///
+/// * `voter` - The token holder: must be a signer.
+/// * `proposal` - The proposal which was voted on.
+/// * `member` - The [`TokenMember`] being withdrawn.
+pub fn vote_withdraw(voter: &Pubkey, proposal: &Pubkey, member: TokenMember) -> Instruction {
+	let mint = get_pda_mint(member).0;
+	let voter_token_account = get_token_account(voter, &mint);
+	let config = get_pda_config().0;
+	let vote = get_pda_vote(proposal, voter).0;
+	let vote_token_account = get_vote_token_account(proposal, voter, member);
+	let token_program = spl_token_2022::ID;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new_readonly(*voter, true),
+			AccountMeta::new(voter_token_account, false),
+			AccountMeta::new_readonly(config, false),
+			AccountMeta::new_readonly(mint, false),
+			AccountMeta::new_readonly(*proposal, false),
+			AccountMeta::new(vote, false),
+			AccountMeta::new(vote_token_account, false),
+			AccountMeta::new_readonly(token_program, false),
+		],
+		data: VoteWithdraw::new(member).to_bytes(),
+	}
+}
+
+/// Create an instruction to join a team in a team game.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `game_index` - The index of the game.
+/// * `team` - The [`Team`] to join.
+pub fn team_join(player: &Pubkey, game_index: u8, team: Team) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let team_pool = get_pda_team_pool(game_index).0;
+	let player_team = get_pda_player_team(game_index, player).0;
+	let system_program = system_program::ID;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*player, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(team_pool, false),
+			AccountMeta::new(player_team, false),
+			AccountMeta::new_readonly(system_program, false),
+		],
+		data: TeamJoin::new(team).to_bytes(),
+	}
+}
+
+/// Create an instruction to claim the share of the team pool earned by the
+/// player once the team game has been finalized.
+///
+/// ### Arguments
+///
+/// * `player` - The player account: must be a signer.
+/// * `game_index` - The index of the game.
+pub fn team_claim(player: &Pubkey, game_index: u8) -> Instruction {
+	let game = get_pda_game(game_index).0;
+	let team_pool = get_pda_team_pool(game_index).0;
+	let player_team = get_pda_player_team(game_index, player).0;
+
+	Instruction {
+		program_id: crate::ID,
+		accounts: vec![
+			AccountMeta::new(*player, true),
+			AccountMeta::new_readonly(game, false),
+			AccountMeta::new(team_pool, false),
+			AccountMeta::new(player_team, false),
+		],
+		data: TeamClaim {}.to_bytes(),
+	}
+}
@@ -0,0 +1,626 @@
+//! Typed cross-program invocation helpers for other on-chain programs.
+//!
+//! Each instruction has an accounts struct which lists the accounts in the
+//! order expected by the bitflip program. The `[signer, writable]` markers in
+//! the docs of every field are the flags of the account meta passed to the
+//! program. Optional accounts, such as the section cooldown of a flip, are
+//! provided through `remaining_accounts` in the order documented on the
+//! processor and keep the flags of their [`AccountInfo`].
+//!
+//! Enable the `no-entrypoint` feature when depending on this crate from
+//! another program.
+//!
+//! ```ignore
+//! use bitflip_program::interface::SectionStepAccounts;
+//! use bitflip_program::SectionStep;
+//!
+//! SectionStepAccounts {
+//! 	program: bitflip_program_info,
+//! 	player: vault_info,
+//! 	game: game_info,
+//! 	section: section_info,
+//! 	section_locks: section_locks_info,
+//! 	system_program: system_program_info,
+//! 	remaining_accounts: &[],
+//! }
+//! .invoke_signed(
+//! 	&SectionStep::new(0, SectionEdge::Dead, section_locks_bump),
+//! 	&[vault_seeds],
+//! )?;
+//! ```
+
+use solana_program::program::invoke_signed;
+use steel::*;
+
+use crate::AccountMigrate;
+use crate::BadgeInitialize;
+use crate::BadgeMint;
+use crate::BountyClaim;
+use crate::BountyCreate;
+use crate::ConfigInitialize;
+use crate::ConfigUpdateAuthority;
+use crate::FlipBit;
+use crate::FlipBitWithTokens;
+use crate::GameArtworkMint;
+use crate::GameFinalize;
+use crate::GameInitialize;
+use crate::GameResetSigners;
+use crate::GameSchedule;
+use crate::GameStart;
+use crate::GameUpdateCooldown;
+use crate::GameUpdateParameter;
+use crate::GameUpdateTempSigner;
+use crate::PlayerTokenAccountInitialize;
+use crate::ProposalCreate;
+use crate::ProposalExecute;
+use crate::ProposalVote;
+use crate::SectionGateUpdate;
+use crate::SectionLockCreate;
+use crate::SectionMetadataModerate;
+use crate::SectionMetadataUpdate;
+use crate::SectionMinesReveal;
+use crate::SectionSeed;
+use crate::SectionStep;
+use crate::SectionUnlock;
+use crate::StakeClaim;
+use crate::StakePoolUpdate;
+use crate::StakeTokens;
+use crate::TeamClaim;
+use crate::TeamJoin;
+use crate::TokenGroupInitialize;
+use crate::TokenInitialize;
+use crate::UnstakeTokens;
+use crate::VoteWithdraw;
+use crate::ID;
+
+macro_rules! interface {
+	(
+		$(#[$meta:meta])*
+		$accounts:ident($args:ident) {
+			$($field:ident: [$signer:literal, $writable:literal]),* $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		pub struct $accounts<'a, 'info> {
+			/// The bitflip program.
+			pub program: &'a AccountInfo<'info>,
+			$(
+				#[doc = concat!("`[", stringify!($signer), ", ", stringify!($writable), "]`")]
+				pub $field: &'a AccountInfo<'info>,
+			)*
+			/// The optional accounts which are appended after the fixed accounts.
+			pub remaining_accounts: &'a [AccountInfo<'info>],
+		}
+
+		impl $accounts<'_, '_> {
+			/// The names of the fixed accounts in the order expected by the
+			/// program.
+			pub const ACCOUNT_NAMES: &'static [&'static str] = &[$(stringify!($field),)*];
+		}
+
+		impl<'info> $accounts<'_, 'info> {
+			/// The account metas in the order expected by the program.
+			pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+				let mut metas = vec![
+					$(
+						AccountMeta {
+							pubkey: *self.$field.key,
+							is_signer: $signer || self.$field.is_signer,
+							is_writable: $writable,
+						},
+					)*
+				];
+				metas.extend(self.remaining_accounts.iter().map(|info| {
+					AccountMeta {
+						pubkey: *info.key,
+						is_signer: info.is_signer,
+						is_writable: info.is_writable,
+					}
+				}));
+
+				metas
+			}
+
+			/// The account infos which are passed to the invoked program.
+			pub fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+				let mut infos = vec![$(self.$field.clone(),)*];
+				infos.extend(self.remaining_accounts.iter().cloned());
+				infos.push(self.program.clone());
+
+				infos
+			}
+
+			/// Create the instruction with the provided `args`.
+			pub fn instruction(&self, args: &$args) -> Instruction {
+				Instruction {
+					program_id: ID,
+					accounts: self.to_account_metas(),
+					data: args.to_bytes(),
+				}
+			}
+
+			/// Invoke the bitflip program.
+			pub fn invoke(&self, args: &$args) -> ProgramResult {
+				self.invoke_signed(args, &[])
+			}
+
+			/// Invoke the bitflip program with the seeds of the program derived
+			/// accounts which sign the instruction.
+			pub fn invoke_signed(&self, args: &$args, signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+				self.program.assert_program(&ID)?;
+				invoke_signed(&self.instruction(args), &self.to_account_infos(), signers_seeds)
+			}
+		}
+	};
+}
+
+interface! {
+	/// The accounts of [`ConfigInitialize`].
+	ConfigInitializeAccounts(ConfigInitialize) {
+		admin: [true, false],
+		authority: [true, true],
+		config: [false, true],
+		treasury: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`ConfigUpdateAuthority`].
+	ConfigUpdateAuthorityAccounts(ConfigUpdateAuthority) {
+		config: [false, true],
+		authority: [true, true],
+		new_authority: [true, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`TokenInitialize`].
+	TokenInitializeAccounts(TokenInitialize) {
+		authority: [true, true],
+		config: [false, false],
+		treasury: [false, false],
+		mint: [false, true],
+		treasury_token_account: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`TokenGroupInitialize`].
+	TokenGroupInitializeAccounts(TokenGroupInitialize) {
+		authority: [true, true],
+		config: [false, false],
+		treasury: [false, true],
+		mint_bit: [false, true],
+		mint_kibibit: [false, true],
+		mint_mebibit: [false, true],
+		mint_gibibit: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameInitialize`].
+	GameInitializeAccounts(GameInitialize) {
+		authority: [true, true],
+		temp_signer: [true, false],
+		funded_signer: [true, true],
+		config: [false, true],
+		game: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameStart`].
+	GameStartAccounts(GameStart) {
+		funded_signer: [true, false],
+		temp_signer: [true, false],
+		config: [false, false],
+		game: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameSchedule`].
+	GameScheduleAccounts(GameSchedule) {
+		authority: [true, false],
+		config: [false, false],
+		game: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameUpdateTempSigner`].
+	GameUpdateTempSignerAccounts(GameUpdateTempSigner) {
+		funded_signer: [true, true],
+		temp_signer: [true, false],
+		game: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameResetSigners`]. The `previous_funded_signer` only
+	/// signs when it is the current funded signer of the game.
+	GameResetSignersAccounts(GameResetSigners) {
+		authority: [true, true],
+		funded_signer: [true, true],
+		temp_signer: [true, false],
+		previous_funded_signer: [false, true],
+		config: [false, true],
+		game: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionUnlock`]. The section mines are passed as a
+	/// remaining account for minesweeper games and the previous game and
+	/// section for inheriting games, followed by the optional writable stake
+	/// pool.
+	SectionUnlockAccounts(SectionUnlock) {
+		owner: [true, true],
+		temp_signer: [true, false],
+		config: [false, false],
+		game: [false, true],
+		section: [false, true],
+		treasury: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionSeed`].
+	SectionSeedAccounts(SectionSeed) {
+		authority: [true, true],
+		config: [false, false],
+		game: [false, false],
+		section: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`PlayerTokenAccountInitialize`].
+	PlayerTokenAccountInitializeAccounts(PlayerTokenAccountInitialize) {
+		player: [true, true],
+		player_bit_token_account: [false, true],
+		config: [false, false],
+		mint_bit: [false, false],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`AccountMigrate`].
+	AccountMigrateAccounts(AccountMigrate) {
+		payer: [true, true],
+		account: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`FlipBit`]. The accounts of the optional game modes are
+	/// passed as remaining accounts in the order documented on
+	/// [`crate::process_flip_bit`].
+	FlipBitAccounts(FlipBit) {
+		player: [true, true],
+		player_bit_token_account: [false, true],
+		config: [false, false],
+		game: [false, false],
+		mint_bit: [false, false],
+		section: [false, true],
+		section_bit_token_account: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+		player_state: [false, true],
+		section_locks: [false, false],
+		section_metadata: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`FlipBitWithTokens`]. The mint and token accounts belong
+	/// to the payment token member.
+	FlipBitWithTokensAccounts(FlipBitWithTokens) {
+		player: [true, true],
+		player_token_account: [false, true],
+		config: [false, false],
+		game: [false, false],
+		mint: [false, true],
+		section: [false, true],
+		section_token_account: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+		player_state: [false, true],
+		section_locks: [false, false],
+		section_metadata: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionMinesReveal`].
+	SectionMinesRevealAccounts(SectionMinesReveal) {
+		temp_signer: [true, false],
+		game: [false, false],
+		section_mines: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionStep`].
+	SectionStepAccounts(SectionStep) {
+		player: [true, true],
+		game: [false, false],
+		section: [false, true],
+		section_locks: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`BountyCreate`].
+	BountyCreateAccounts(BountyCreate) {
+		poster: [true, true],
+		game: [false, false],
+		section: [false, false],
+		bounty: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`BountyClaim`].
+	BountyClaimAccounts(BountyClaim) {
+		claimer: [true, true],
+		poster: [false, true],
+		section: [false, false],
+		bounty: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameFinalize`]. The sections of the game are passed as
+	/// remaining accounts.
+	GameFinalizeAccounts(GameFinalize) {
+		payer: [true, true],
+		game: [false, true],
+		game_canvas: [false, true],
+		system_program: [false, false],
+		team_pool: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameArtworkMint`].
+	GameArtworkMintAccounts(GameArtworkMint) {
+		authority: [true, true],
+		config: [false, false],
+		treasury: [false, false],
+		game: [false, false],
+		artwork_mint: [false, true],
+		treasury_artwork_token_account: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionMetadataUpdate`].
+	SectionMetadataUpdateAccounts(SectionMetadataUpdate) {
+		owner: [true, true],
+		game: [false, false],
+		section: [false, false],
+		section_metadata: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionMetadataModerate`].
+	SectionMetadataModerateAccounts(SectionMetadataModerate) {
+		authority: [true, false],
+		config: [false, false],
+		section_metadata: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`BadgeInitialize`].
+	BadgeInitializeAccounts(BadgeInitialize) {
+		authority: [true, true],
+		config: [false, false],
+		treasury: [false, false],
+		badge_mint: [false, true],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`BadgeMint`].
+	BadgeMintAccounts(BadgeMint) {
+		player: [true, true],
+		config: [false, false],
+		player_state: [false, true],
+		treasury: [false, false],
+		badge_mint: [false, true],
+		player_badge_token_account: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameUpdateCooldown`].
+	GameUpdateCooldownAccounts(GameUpdateCooldown) {
+		authority: [true, false],
+		config: [false, false],
+		game: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionLockCreate`].
+	SectionLockCreateAccounts(SectionLockCreate) {
+		holder: [true, true],
+		owner: [false, true],
+		game: [false, false],
+		section: [false, false],
+		section_locks: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`SectionGateUpdate`].
+	SectionGateUpdateAccounts(SectionGateUpdate) {
+		signer: [true, true],
+		config: [false, false],
+		game: [false, false],
+		section: [false, false],
+		section_metadata: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`StakePoolUpdate`].
+	StakePoolUpdateAccounts(StakePoolUpdate) {
+		authority: [true, true],
+		config: [false, false],
+		stake_pool: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`StakeTokens`].
+	StakeTokensAccounts(StakeTokens) {
+		player: [true, true],
+		player_token_account: [false, true],
+		config: [false, false],
+		mint: [false, false],
+		stake_pool: [false, true],
+		stake_pool_token_account: [false, true],
+		stake: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`UnstakeTokens`].
+	UnstakeTokensAccounts(UnstakeTokens) {
+		player: [true, true],
+		player_token_account: [false, true],
+		config: [false, false],
+		mint: [false, false],
+		stake_pool: [false, true],
+		stake_pool_token_account: [false, true],
+		stake: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`StakeClaim`].
+	StakeClaimAccounts(StakeClaim) {
+		player: [true, true],
+		stake_pool: [false, true],
+		stake: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`GameUpdateParameter`].
+	GameUpdateParameterAccounts(GameUpdateParameter) {
+		authority: [true, false],
+		config: [false, false],
+		game: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`ProposalCreate`].
+	ProposalCreateAccounts(ProposalCreate) {
+		proposer: [true, true],
+		game: [false, false],
+		proposal: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`ProposalVote`].
+	ProposalVoteAccounts(ProposalVote) {
+		voter: [true, true],
+		voter_token_account: [false, true],
+		config: [false, false],
+		mint: [false, false],
+		proposal: [false, true],
+		vote: [false, true],
+		vote_token_account: [false, true],
+		associated_token_program: [false, false],
+		token_program: [false, false],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`ProposalExecute`].
+	ProposalExecuteAccounts(ProposalExecute) {
+		game: [false, true],
+		proposal: [false, true],
+	}
+}
+
+interface! {
+	/// The accounts of [`VoteWithdraw`].
+	VoteWithdrawAccounts(VoteWithdraw) {
+		voter: [true, false],
+		voter_token_account: [false, true],
+		config: [false, false],
+		mint: [false, false],
+		proposal: [false, false],
+		vote: [false, true],
+		vote_token_account: [false, true],
+		token_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`TeamJoin`].
+	TeamJoinAccounts(TeamJoin) {
+		player: [true, true],
+		game: [false, false],
+		team_pool: [false, true],
+		player_team: [false, true],
+		system_program: [false, false],
+	}
+}
+
+interface! {
+	/// The accounts of [`TeamClaim`].
+	TeamClaimAccounts(TeamClaim) {
+		player: [true, true],
+		game: [false, false],
+		team_pool: [false, true],
+		player_team: [false, true],
+	}
+}
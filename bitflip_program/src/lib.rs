@@ -1,11 +1,16 @@
 #[cfg(feature = "client")]
 mod client;
+#[cfg(feature = "client")]
+mod compute_units;
 mod constants;
 pub mod cpi;
+#[cfg(feature = "client")]
+mod decoder;
 mod errors;
 mod events;
 #[cfg(feature = "client")]
 mod instructions;
+pub mod interface;
 mod loaders;
 mod pda;
 mod processor;
@@ -16,7 +21,11 @@ use steel::*;
 
 #[cfg(feature = "client")]
 pub use crate::client::*;
+#[cfg(feature = "client")]
+pub use crate::compute_units::*;
 pub use crate::constants::*;
+#[cfg(feature = "client")]
+pub use crate::decoder::*;
 pub use crate::errors::*;
 pub use crate::events::*;
 #[cfg(feature = "client")]
@@ -1,10 +1,39 @@
 use solana_program::pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
+use steel::AccountInfo;
 use steel::ProgramError;
 
+use crate::BadgeType;
 use crate::TokenMember;
 use crate::ID;
 
+/// Assert that the account is the program address of the `seeds` with the
+/// client supplied `bump` and that the `bump` is the canonical bump. Only the
+/// bumps above the `bump` are derived, which is cheaper than searching for the
+/// canonical bump since the canonical bump is usually one of the highest.
+pub(crate) fn assert_canonical_seeds_with_bump(
+	info: &AccountInfo,
+	seeds: &[&[u8]],
+	bump: u8,
+) -> Result<(), ProgramError> {
+	let create_program_address = |bump: u8| {
+		let bump_seed = [bump];
+		let mut seeds_with_bump = seeds.to_vec();
+		seeds_with_bump.push(&bump_seed);
+		Pubkey::create_program_address(&seeds_with_bump, &ID)
+	};
+
+	if create_program_address(bump) != Ok(*info.key) {
+		return Err(ProgramError::InvalidSeeds);
+	}
+
+	if (bump..u8::MAX).any(|higher| create_program_address(higher + 1).is_ok()) {
+		return Err(ProgramError::InvalidSeeds);
+	}
+
+	Ok(())
+}
+
 pub fn get_token_account(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
 	get_associated_token_address_with_program_id(wallet, mint, &spl_token_2022::ID)
 }
@@ -163,6 +192,547 @@ pub fn create_pda_section(
 	Ok(pubkey)
 }
 
+macro_rules! seeds_section_mines {
+	($game_index:expr, $section_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_MINES,
+		]
+	};
+	($game_index:expr, $section_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_MINES,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_section_mines;
+
+pub fn get_pda_section_mines(game_index: u8, section_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_section_mines!(game_index, section_index), &ID)
+}
+
+pub fn create_pda_section_mines(
+	game_index: u8,
+	section_index: u8,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey =
+		Pubkey::create_program_address(seeds_section_mines!(game_index, section_index, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_section_cooldown {
+	($game_index:expr, $section_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_COOLDOWN,
+		]
+	};
+	($game_index:expr, $section_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_COOLDOWN,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_section_cooldown;
+
+pub fn get_pda_section_cooldown(game_index: u8, section_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_section_cooldown!(game_index, section_index), &ID)
+}
+
+pub fn create_pda_section_cooldown(
+	game_index: u8,
+	section_index: u8,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(
+		seeds_section_cooldown!(game_index, section_index, bump),
+		&ID,
+	)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_section_locks {
+	($game_index:expr, $section_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_LOCKS,
+		]
+	};
+	($game_index:expr, $section_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_LOCKS,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_section_locks;
+
+pub fn get_pda_section_locks(game_index: u8, section_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_section_locks!(game_index, section_index), &ID)
+}
+
+pub fn create_pda_section_locks(
+	game_index: u8,
+	section_index: u8,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey =
+		Pubkey::create_program_address(seeds_section_locks!(game_index, section_index, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_bounty {
+	($game_index:expr, $section_index:expr, $poster:expr, $bounty_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_BOUNTY,
+			$poster.as_ref(),
+			&$bounty_index.to_le_bytes(),
+		]
+	};
+	($game_index:expr, $section_index:expr, $poster:expr, $bounty_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_BOUNTY,
+			$poster.as_ref(),
+			&$bounty_index.to_le_bytes(),
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_bounty;
+
+pub fn get_pda_bounty(
+	game_index: u8,
+	section_index: u8,
+	poster: &Pubkey,
+	bounty_index: u16,
+) -> (Pubkey, u8) {
+	Pubkey::find_program_address(
+		seeds_bounty!(game_index, section_index, poster, bounty_index),
+		&ID,
+	)
+}
+
+pub fn create_pda_bounty(
+	game_index: u8,
+	section_index: u8,
+	poster: &Pubkey,
+	bounty_index: u16,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(
+		seeds_bounty!(game_index, section_index, poster, bounty_index, bump),
+		&ID,
+	)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_game_canvas {
+	($game_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_GAME_CANVAS,
+		]
+	};
+	($game_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_GAME_CANVAS,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_game_canvas;
+
+pub fn get_pda_game_canvas(game_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_game_canvas!(game_index), &ID)
+}
+
+pub fn create_pda_game_canvas(game_index: u8, bump: u8) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_game_canvas!(game_index, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_artwork_mint {
+	($game_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_ARTWORK_MINT,
+		]
+	};
+	($game_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_ARTWORK_MINT,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_artwork_mint;
+
+pub fn get_pda_artwork_mint(game_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_artwork_mint!(game_index), &ID)
+}
+
+pub fn create_pda_artwork_mint(game_index: u8, bump: u8) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_artwork_mint!(game_index, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_section_metadata {
+	($game_index:expr, $section_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_METADATA,
+		]
+	};
+	($game_index:expr, $section_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_SECTION,
+			&$section_index.to_le_bytes(),
+			crate::SEED_SECTION_METADATA,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_section_metadata;
+
+pub fn get_pda_section_metadata(game_index: u8, section_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_section_metadata!(game_index, section_index), &ID)
+}
+
+pub fn create_pda_section_metadata(
+	game_index: u8,
+	section_index: u8,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(
+		seeds_section_metadata!(game_index, section_index, bump),
+		&ID,
+	)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_player {
+	($game_index:expr, $player:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_PLAYER,
+			$player.as_ref(),
+		]
+	};
+	($game_index:expr, $player:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_PLAYER,
+			$player.as_ref(),
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_player;
+
+pub fn get_pda_player(game_index: u8, player: &Pubkey) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_player!(game_index, player), &ID)
+}
+
+pub fn create_pda_player(
+	game_index: u8,
+	player: &Pubkey,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_player!(game_index, player, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_stake_pool {
+	() => {
+		&[crate::SEED_PREFIX, crate::SEED_STAKE_POOL]
+	};
+	($bump:expr) => {
+		&[crate::SEED_PREFIX, crate::SEED_STAKE_POOL, &[$bump]]
+	};
+}
+
+pub(crate) use seeds_stake_pool;
+
+pub fn get_pda_stake_pool() -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_stake_pool!(), &ID)
+}
+
+pub fn create_pda_stake_pool(bump: u8) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_stake_pool!(bump), &ID)?;
+	Ok(pubkey)
+}
+
+pub fn get_stake_pool_token_account(member: TokenMember) -> Pubkey {
+	let stake_pool = get_pda_stake_pool().0;
+	let mint = get_pda_mint(member).0;
+
+	get_token_account(&stake_pool, &mint)
+}
+
+macro_rules! seeds_stake {
+	($owner:expr) => {
+		&[crate::SEED_PREFIX, crate::SEED_STAKE, $owner.as_ref()]
+	};
+	($owner:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_STAKE,
+			$owner.as_ref(),
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_stake;
+
+pub fn get_pda_stake(owner: &Pubkey) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_stake!(owner), &ID)
+}
+
+pub fn create_pda_stake(owner: &Pubkey, bump: u8) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_stake!(owner, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_proposal {
+	($game_index:expr, $proposer:expr, $proposal_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_PROPOSAL,
+			$proposer.as_ref(),
+			&$proposal_index.to_le_bytes(),
+		]
+	};
+	($game_index:expr, $proposer:expr, $proposal_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_PROPOSAL,
+			$proposer.as_ref(),
+			&$proposal_index.to_le_bytes(),
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_proposal;
+
+pub fn get_pda_proposal(game_index: u8, proposer: &Pubkey, proposal_index: u16) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_proposal!(game_index, proposer, proposal_index), &ID)
+}
+
+pub fn create_pda_proposal(
+	game_index: u8,
+	proposer: &Pubkey,
+	proposal_index: u16,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(
+		seeds_proposal!(game_index, proposer, proposal_index, bump),
+		&ID,
+	)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_vote {
+	($proposal:expr, $voter:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_VOTE,
+			$proposal.as_ref(),
+			$voter.as_ref(),
+		]
+	};
+	($proposal:expr, $voter:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_VOTE,
+			$proposal.as_ref(),
+			$voter.as_ref(),
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_vote;
+
+pub fn get_pda_vote(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_vote!(proposal, voter), &ID)
+}
+
+pub fn create_pda_vote(
+	proposal: &Pubkey,
+	voter: &Pubkey,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_vote!(proposal, voter, bump), &ID)?;
+	Ok(pubkey)
+}
+
+pub fn get_vote_token_account(proposal: &Pubkey, voter: &Pubkey, member: TokenMember) -> Pubkey {
+	let vote = get_pda_vote(proposal, voter).0;
+	let mint = get_pda_mint(member).0;
+
+	get_token_account(&vote, &mint)
+}
+
+macro_rules! seeds_team_pool {
+	($game_index:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_TEAM_POOL,
+		]
+	};
+	($game_index:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_TEAM_POOL,
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_team_pool;
+
+pub fn get_pda_team_pool(game_index: u8) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_team_pool!(game_index), &ID)
+}
+
+pub fn create_pda_team_pool(game_index: u8, bump: u8) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_team_pool!(game_index, bump), &ID)?;
+	Ok(pubkey)
+}
+
+macro_rules! seeds_player_team {
+	($game_index:expr, $player:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_PLAYER_TEAM,
+			$player.as_ref(),
+		]
+	};
+	($game_index:expr, $player:expr, $bump:expr) => {
+		&[
+			crate::SEED_PREFIX,
+			crate::SEED_GAME,
+			&$game_index.to_le_bytes(),
+			crate::SEED_PLAYER_TEAM,
+			$player.as_ref(),
+			&[$bump],
+		]
+	};
+}
+
+pub(crate) use seeds_player_team;
+
+pub fn get_pda_player_team(game_index: u8, player: &Pubkey) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_player_team!(game_index, player), &ID)
+}
+
+pub fn create_pda_player_team(
+	game_index: u8,
+	player: &Pubkey,
+	bump: u8,
+) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_player_team!(game_index, player, bump), &ID)?;
+	Ok(pubkey)
+}
+
+/// The badge mints use the same seeds as [`get_pda_mint`] with the seed of the
+/// [`BadgeType`].
+pub fn get_pda_badge_mint(badge: BadgeType) -> (Pubkey, u8) {
+	Pubkey::find_program_address(seeds_mint!(badge), &ID)
+}
+
+pub fn create_pda_badge_mint(badge: BadgeType, bump: u8) -> Result<Pubkey, ProgramError> {
+	let pubkey = Pubkey::create_program_address(seeds_mint!(badge, bump), &ID)?;
+	Ok(pubkey)
+}
+
+pub fn get_player_badge_token_account(player: &Pubkey, badge: BadgeType) -> Pubkey {
+	let mint = get_pda_badge_mint(badge).0;
+
+	get_token_account(player, &mint)
+}
+
 pub fn get_section_token_account(game_index: u8, section_index: u8, member: TokenMember) -> Pubkey {
 	let section = get_pda_section(game_index, section_index).0;
 	let mint = get_pda_mint(member).0;
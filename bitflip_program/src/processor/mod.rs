@@ -1,27 +1,85 @@
+mod process_account_migrate;
+mod process_badge_initialize;
+mod process_badge_mint;
+mod process_bounty_claim;
+mod process_bounty_create;
 mod process_config_initialize;
 mod process_config_update_authority;
 
 mod process_flip_bit;
+mod process_flip_bit_with_tokens;
+mod process_game_artwork_mint;
+mod process_game_finalize;
 mod process_game_initialize;
 mod process_game_reset_signers;
+mod process_game_schedule;
 mod process_game_start;
+mod process_game_update_cooldown;
+mod process_game_update_parameter;
 mod process_game_update_temp_signer;
+mod process_player_token_account_initialize;
+mod process_proposal_create;
+mod process_proposal_execute;
+mod process_proposal_vote;
+mod process_section_gate_update;
+mod process_section_lock_create;
+mod process_section_metadata_moderate;
+mod process_section_metadata_update;
+mod process_section_mines_reveal;
+mod process_section_seed;
+mod process_section_step;
 mod process_section_unlock;
+mod process_stake_claim;
+mod process_stake_pool_update;
+mod process_stake_tokens;
+mod process_team_claim;
+mod process_team_join;
 mod process_token_group_initialize;
 mod process_token_initialize;
+mod process_unstake_tokens;
+mod process_vote_withdraw;
 
 use steel::*;
 
+pub use self::process_account_migrate::*;
+pub use self::process_badge_initialize::*;
+pub use self::process_badge_mint::*;
+pub use self::process_bounty_claim::*;
+pub use self::process_bounty_create::*;
 pub use self::process_config_initialize::*;
 pub use self::process_config_update_authority::*;
 pub use self::process_flip_bit::*;
+pub use self::process_flip_bit_with_tokens::*;
+pub use self::process_game_artwork_mint::*;
+pub use self::process_game_finalize::*;
 pub use self::process_game_initialize::*;
 pub use self::process_game_reset_signers::*;
+pub use self::process_game_schedule::*;
 pub use self::process_game_start::*;
+pub use self::process_game_update_cooldown::*;
+pub use self::process_game_update_parameter::*;
 pub use self::process_game_update_temp_signer::*;
+pub use self::process_player_token_account_initialize::*;
+pub use self::process_proposal_create::*;
+pub use self::process_proposal_execute::*;
+pub use self::process_proposal_vote::*;
+pub use self::process_section_gate_update::*;
+pub use self::process_section_lock_create::*;
+pub use self::process_section_metadata_moderate::*;
+pub use self::process_section_metadata_update::*;
+pub use self::process_section_mines_reveal::*;
+pub use self::process_section_seed::*;
+pub use self::process_section_step::*;
 pub use self::process_section_unlock::*;
+pub use self::process_stake_claim::*;
+pub use self::process_stake_pool_update::*;
+pub use self::process_stake_tokens::*;
+pub use self::process_team_claim::*;
+pub use self::process_team_join::*;
 pub use self::process_token_group_initialize::*;
 pub use self::process_token_initialize::*;
+pub use self::process_unstake_tokens::*;
+pub use self::process_vote_withdraw::*;
 use crate::ID;
 
 #[repr(u8)]
@@ -37,6 +95,35 @@ pub enum BitflipInstruction {
 	GameResetSigners = 7,
 	SectionUnlock = 8,
 	FlipBit = 9,
+	SectionMinesReveal = 10,
+	SectionStep = 11,
+	BountyCreate = 12,
+	BountyClaim = 13,
+	GameFinalize = 14,
+	GameArtworkMint = 15,
+	SectionMetadataUpdate = 16,
+	SectionMetadataModerate = 17,
+	BadgeInitialize = 18,
+	BadgeMint = 19,
+	GameUpdateCooldown = 20,
+	SectionLockCreate = 21,
+	SectionGateUpdate = 22,
+	FlipBitWithTokens = 23,
+	StakePoolUpdate = 24,
+	StakeTokens = 25,
+	UnstakeTokens = 26,
+	StakeClaim = 27,
+	GameUpdateParameter = 28,
+	ProposalCreate = 29,
+	ProposalVote = 30,
+	ProposalExecute = 31,
+	VoteWithdraw = 32,
+	TeamJoin = 33,
+	TeamClaim = 34,
+	GameSchedule = 35,
+	SectionSeed = 36,
+	PlayerTokenAccountInitialize = 37,
+	AccountMigrate = 38,
 }
 
 pub fn process_instruction(
@@ -51,12 +138,47 @@ pub fn process_instruction(
 		BitflipInstruction::ConfigUpdateAuthority => process_config_update_authority(accounts)?,
 		BitflipInstruction::TokenGroupInitialize => process_token_group_initialize(accounts)?,
 		BitflipInstruction::TokenInitialize => process_token_initialize(accounts, data)?,
-		BitflipInstruction::GameInitialize => process_game_initialize(accounts)?,
+		BitflipInstruction::GameInitialize => process_game_initialize(accounts, data)?,
 		BitflipInstruction::GameStart => process_game_start(accounts)?,
 		BitflipInstruction::GameUpdateTempSigner => process_game_update_temp_signer(accounts)?,
 		BitflipInstruction::GameResetSigners => process_game_reset_signers(accounts)?,
 		BitflipInstruction::SectionUnlock => process_section_unlock(accounts, data)?,
 		BitflipInstruction::FlipBit => process_flip_bit(accounts, data)?,
+		BitflipInstruction::SectionMinesReveal => process_section_mines_reveal(accounts, data)?,
+		BitflipInstruction::SectionStep => process_section_step(accounts, data)?,
+		BitflipInstruction::BountyCreate => process_bounty_create(accounts, data)?,
+		BitflipInstruction::BountyClaim => process_bounty_claim(accounts)?,
+		BitflipInstruction::GameFinalize => process_game_finalize(accounts)?,
+		BitflipInstruction::GameArtworkMint => process_game_artwork_mint(accounts)?,
+		BitflipInstruction::SectionMetadataUpdate => {
+			process_section_metadata_update(accounts, data)?
+		}
+		BitflipInstruction::SectionMetadataModerate => {
+			process_section_metadata_moderate(accounts, data)?
+		}
+		BitflipInstruction::BadgeInitialize => process_badge_initialize(accounts, data)?,
+		BitflipInstruction::BadgeMint => process_badge_mint(accounts, data)?,
+		BitflipInstruction::GameUpdateCooldown => process_game_update_cooldown(accounts, data)?,
+		BitflipInstruction::SectionLockCreate => process_section_lock_create(accounts, data)?,
+		BitflipInstruction::SectionGateUpdate => process_section_gate_update(accounts, data)?,
+		BitflipInstruction::FlipBitWithTokens => process_flip_bit_with_tokens(accounts, data)?,
+		BitflipInstruction::StakePoolUpdate => process_stake_pool_update(accounts, data)?,
+		BitflipInstruction::StakeTokens => process_stake_tokens(accounts, data)?,
+		BitflipInstruction::UnstakeTokens => process_unstake_tokens(accounts, data)?,
+		BitflipInstruction::StakeClaim => process_stake_claim(accounts)?,
+		BitflipInstruction::GameUpdateParameter => process_game_update_parameter(accounts, data)?,
+		BitflipInstruction::ProposalCreate => process_proposal_create(accounts, data)?,
+		BitflipInstruction::ProposalVote => process_proposal_vote(accounts, data)?,
+		BitflipInstruction::ProposalExecute => process_proposal_execute(accounts)?,
+		BitflipInstruction::VoteWithdraw => process_vote_withdraw(accounts, data)?,
+		BitflipInstruction::TeamJoin => process_team_join(accounts, data)?,
+		BitflipInstruction::TeamClaim => process_team_claim(accounts)?,
+		BitflipInstruction::GameSchedule => process_game_schedule(accounts, data)?,
+		BitflipInstruction::SectionSeed => process_section_seed(accounts, data)?,
+		BitflipInstruction::PlayerTokenAccountInitialize => {
+			process_player_token_account_initialize(accounts)?
+		}
+		BitflipInstruction::AccountMigrate => process_account_migrate(accounts)?,
 	}
 
 	Ok(())
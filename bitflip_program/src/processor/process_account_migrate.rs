@@ -0,0 +1,261 @@
+use steel::*;
+use sysvar::rent::Rent;
+
+use crate::AccountVersion;
+use crate::BitflipAccount;
+use crate::BitflipInstruction;
+use crate::BountyState;
+use crate::ConfigState;
+use crate::GameCanvasState;
+use crate::GameState;
+use crate::PlayerState;
+use crate::PlayerTeamState;
+use crate::ProposalState;
+use crate::SectionCooldownState;
+use crate::SectionLocksState;
+use crate::SectionMetadataState;
+use crate::SectionMinesState;
+use crate::SectionState;
+use crate::StakePoolState;
+use crate::StakeState;
+use crate::TeamPoolState;
+use crate::VoteState;
+use crate::ID;
+
+/// Migrate a program account to the latest version of its state.
+///
+/// The account is resized when the latest version of the state is larger and
+/// the payer tops up the lamports needed for rent exemption. Anyone can call
+/// this instruction since the migration is deterministic and only depends on
+/// the stored state.
+pub fn process_account_migrate(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [payer_info, account_info, system_program_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	payer_info.assert_signer()?.assert_writable()?;
+	account_info.assert_owner(&ID)?.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	let discriminator = account_info
+		.try_borrow_data()?
+		.first()
+		.copied()
+		.ok_or(ProgramError::InvalidAccountData)?;
+	let account =
+		BitflipAccount::try_from(discriminator).map_err(|_| ProgramError::InvalidAccountData)?;
+
+	match account {
+		BitflipAccount::ConfigState => migrate_account::<ConfigState>(payer_info, account_info),
+		BitflipAccount::GameState => migrate_account::<GameState>(payer_info, account_info),
+		BitflipAccount::SectionState => migrate_account::<SectionState>(payer_info, account_info),
+		BitflipAccount::SectionMinesState => {
+			migrate_account::<SectionMinesState>(payer_info, account_info)
+		}
+		BitflipAccount::BountyState => migrate_account::<BountyState>(payer_info, account_info),
+		BitflipAccount::GameCanvasState => {
+			migrate_account::<GameCanvasState>(payer_info, account_info)
+		}
+		BitflipAccount::SectionMetadataState => {
+			migrate_account::<SectionMetadataState>(payer_info, account_info)
+		}
+		BitflipAccount::PlayerState => migrate_account::<PlayerState>(payer_info, account_info),
+		BitflipAccount::SectionCooldownState => {
+			migrate_account::<SectionCooldownState>(payer_info, account_info)
+		}
+		BitflipAccount::SectionLocksState => {
+			migrate_account::<SectionLocksState>(payer_info, account_info)
+		}
+		BitflipAccount::StakePoolState => {
+			migrate_account::<StakePoolState>(payer_info, account_info)
+		}
+		BitflipAccount::StakeState => migrate_account::<StakeState>(payer_info, account_info),
+		BitflipAccount::ProposalState => migrate_account::<ProposalState>(payer_info, account_info),
+		BitflipAccount::VoteState => migrate_account::<VoteState>(payer_info, account_info),
+		BitflipAccount::TeamPoolState => migrate_account::<TeamPoolState>(payer_info, account_info),
+		BitflipAccount::PlayerTeamState => {
+			migrate_account::<PlayerTeamState>(payer_info, account_info)
+		}
+	}
+}
+
+fn migrate_account<T: AccountVersion + Discriminator>(
+	payer_info: &AccountInfo,
+	account_info: &AccountInfo,
+) -> ProgramResult {
+	let space = 8 + std::mem::size_of::<T>();
+
+	if account_info.data_len() > space {
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	if account_info.data_len() < space {
+		account_info.realloc(space, true)?;
+
+		let rent_sysvar = Rent::get()?;
+		let extra_lamports = rent_sysvar
+			.minimum_balance(space)
+			.saturating_sub(account_info.lamports());
+
+		if extra_lamports > 0 {
+			account_info.collect(extra_lamports, payer_info)?;
+		}
+	}
+
+	account_info.as_account_mut::<T>(&ID)?.migrate()
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountMigrate {}
+
+instruction!(BitflipInstruction, AccountMigrate);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::leak;
+	use crate::BASE_LAMPORTS_PER_STEP;
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos(create_game_data(0));
+		let result = process_account_migrate(&accounts[..2]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn payer_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(create_game_data(0));
+		let payer_info = &mut accounts[0];
+		payer_info.is_signer = false;
+
+		let result = process_account_migrate(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn account_should_be_owned_by_program() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(create_game_data(0));
+		let account_info = &mut accounts[1];
+		account_info.owner = &system_program::ID;
+
+		let result = process_account_migrate(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidAccountOwner);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn account_should_have_known_discriminator() -> anyhow::Result<()> {
+		let mut data = create_game_data(0);
+		data[0] = u8::MAX;
+		let accounts = create_account_infos(data);
+
+		let result = process_account_migrate(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidAccountData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_migrate_game_step_lamports() -> anyhow::Result<()> {
+		let accounts = create_account_infos(create_game_data(0));
+		process_account_migrate(&accounts)?;
+
+		let game = accounts[1].as_account::<GameState>(&ID)?;
+		check!(game.version == GameState::VERSION);
+		check!(game.step_lamports() == BASE_LAMPORTS_PER_STEP);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_not_change_migrated_game() -> anyhow::Result<()> {
+		let accounts = create_account_infos(create_game_data(GameState::VERSION));
+		{
+			let game = accounts[1].as_account_mut::<GameState>(&ID)?;
+			game.step_lamports = 1.into();
+		}
+		process_account_migrate(&accounts)?;
+
+		let game = accounts[1].as_account::<GameState>(&ID)?;
+		check!(game.version == GameState::VERSION);
+		check!(game.step_lamports() == 1);
+
+		Ok(())
+	}
+
+	fn create_game_data(version: u8) -> Vec<u8> {
+		let mut game = GameState::new(
+			Pubkey::new_unique(),
+			Pubkey::new_unique(),
+			0,
+			get_pda_game(0).1,
+		);
+		game.version = version;
+
+		if version < 1 {
+			game.step_lamports = 0.into();
+		}
+
+		let mut data = vec![0u8; 8];
+		data[0] = GameState::discriminator();
+		data.append(&mut game.to_bytes().to_vec());
+		data
+	}
+
+	fn create_account_infos<'info>(account_data: Vec<u8>) -> [AccountInfo<'info>; 3] {
+		let payer_key = leak(Pubkey::new_unique());
+		let payer_lamports = leak(1_000_000_000);
+		let payer_data = leak(vec![]);
+		let account_key = leak(get_pda_game(0).0);
+		let account_lamports = leak(1_000_000_000);
+		let account_data = leak(account_data);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let payer_info = AccountInfo::new(
+			payer_key,
+			true,
+			true,
+			payer_lamports,
+			payer_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let account_info = AccountInfo::new(
+			account_key,
+			false,
+			true,
+			account_lamports,
+			account_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[payer_info, account_info, system_program_info]
+	}
+}
@@ -0,0 +1,134 @@
+use solana_program::msg;
+use spl_token_2022::extension::ExtensionType;
+use steel::*;
+use sysvar::rent::Rent;
+
+use crate::cpi::initialize_mint;
+use crate::cpi::metadata_pointer_initialize;
+use crate::cpi::non_transferable_mint_initialize;
+use crate::cpi::token_metadata_initialize;
+use crate::seeds_config;
+use crate::seeds_mint;
+use crate::seeds_treasury;
+use crate::BadgeType;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::ID;
+
+/// Create the non-transferable mint for a [`BadgeType`].
+///
+/// The treasury is the mint authority so badges can only be minted by the
+/// program once they have been earned.
+pub fn process_badge_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = BadgeInitialize::try_from_bytes(data)?;
+	let badge = args.badge()?;
+
+	// load accounts
+	let [authority_info, config_info, treasury_info, badge_mint_info, token_program_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let treasury_seeds_with_bump = seeds_treasury!(config.treasury_bump);
+	let badge_mint_seeds = seeds_mint!(badge);
+	let badge_mint_bump = badge_mint_info.assert_canonical_bump(badge_mint_seeds, &ID)?;
+
+	authority_info.assert_signer()?.assert_writable()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	treasury_info
+		.assert_owner(&system_program::ID)?
+		.assert_seeds_with_bump(treasury_seeds_with_bump, &ID)?;
+	badge_mint_info.assert_empty()?.assert_writable()?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	if authority_info.key.ne(&config.authority) {
+		return Err(BitflipError::Unauthorized.into());
+	}
+
+	let rent_sysvar = Rent::get()?;
+	let name = badge.name();
+
+	allocate_account_with_bump(
+		badge_mint_info,
+		system_program_info,
+		authority_info,
+		get_badge_mint_space()?,
+		token_program_info.key,
+		badge_mint_seeds,
+		badge_mint_bump,
+	)?;
+
+	msg!("{}: initialize non transferable mint extension", name);
+	non_transferable_mint_initialize(badge_mint_info, token_program_info, &[])?;
+
+	msg!("{}: initialize metadata pointer mint extension", name);
+	metadata_pointer_initialize(badge_mint_info, treasury_info, token_program_info, &[])?;
+
+	msg!("{}: initialize mint", name);
+	initialize_mint(badge_mint_info, token_program_info, treasury_info, 0, &[])?;
+
+	msg!("{}: initialize token metadata", name);
+	token_metadata_initialize(
+		badge_mint_info,
+		treasury_info,
+		token_program_info,
+		badge.name().into(),
+		badge.symbol().into(),
+		badge.uri().into(),
+		&[treasury_seeds_with_bump],
+	)?;
+
+	let extra_lamports = rent_sysvar
+		.minimum_balance(badge_mint_info.data_len())
+		.checked_sub(badge_mint_info.lamports())
+		.ok_or(ProgramError::ArithmeticOverflow)?;
+
+	if extra_lamports > 0 {
+		msg!("{}: collect extra lamports", name);
+		badge_mint_info.collect(extra_lamports, authority_info)?;
+	}
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BadgeInitialize {
+	pub badge: u8,
+}
+
+impl BadgeInitialize {
+	pub fn new(badge: BadgeType) -> Self {
+		Self {
+			badge: badge.into(),
+		}
+	}
+
+	pub fn badge(&self) -> Result<BadgeType, ProgramError> {
+		BadgeType::try_from(self.badge).or(Err(ProgramError::InvalidInstructionData))
+	}
+}
+
+instruction!(BitflipInstruction, BadgeInitialize);
+
+const BADGE_EXTENSION_TYPES: &[ExtensionType] = &[
+	ExtensionType::NonTransferable,
+	ExtensionType::MetadataPointer,
+];
+
+fn get_badge_mint_space() -> Result<usize, ProgramError> {
+	let mint_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+		BADGE_EXTENSION_TYPES,
+	)?;
+	Ok(mint_space)
+}
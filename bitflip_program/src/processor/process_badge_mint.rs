@@ -0,0 +1,351 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::cpi::create_associated_token_account_idempotent;
+use crate::cpi::mint_to;
+use crate::seeds_config;
+use crate::seeds_mint;
+use crate::seeds_player;
+use crate::seeds_treasury;
+use crate::BadgeType;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::PlayerState;
+use crate::ID;
+
+/// Mint an earned [`crate::BadgeType`] to the player.
+///
+/// Each badge can only be minted once per game and the token can never be
+/// transferred.
+pub fn process_badge_mint(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = BadgeMint::try_from_bytes(data)?;
+	let badge = args.badge()?;
+
+	// load accounts
+	let [player_info, config_info, player_state_info, treasury_info, badge_mint_info, player_badge_token_account_info, associated_token_program_info, token_program_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let player_state = player_state_info.as_account_mut::<PlayerState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let player_seeds_with_bump =
+		seeds_player!(player_state.game_index, player_info.key, player_state.bump);
+	let treasury_seeds_with_bump = seeds_treasury!(config.treasury_bump);
+
+	player_info.assert_signer()?.assert_writable()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	player_state_info
+		.assert_type::<PlayerState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(player_seeds_with_bump, &ID)?;
+	treasury_info
+		.assert_owner(&system_program::ID)?
+		.assert_seeds_with_bump(treasury_seeds_with_bump, &ID)?;
+	badge_mint_info
+		.assert_writable()?
+		.assert_canonical_bump(seeds_mint!(badge), &ID)?;
+	player_badge_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(player_info.key, badge_mint_info.key)?;
+	associated_token_program_info.assert_program(&spl_associated_token_account::ID)?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	player_state.claim(badge)?;
+
+	msg!("{}: create player associated token account", badge.name());
+	create_associated_token_account_idempotent(
+		player_info,
+		player_badge_token_account_info,
+		player_info,
+		badge_mint_info,
+		token_program_info,
+		system_program_info,
+		&[],
+	)?;
+
+	msg!("{}: mint badge to player", badge.name());
+	mint_to(
+		badge_mint_info,
+		player_badge_token_account_info,
+		treasury_info,
+		token_program_info,
+		1,
+		&[treasury_seeds_with_bump],
+	)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BadgeMint {
+	pub badge: u8,
+}
+
+impl BadgeMint {
+	pub fn new(badge: BadgeType) -> Self {
+		Self {
+			badge: badge.into(),
+		}
+	}
+
+	pub fn badge(&self) -> Result<BadgeType, ProgramError> {
+		BadgeType::try_from(self.badge).or(Err(ProgramError::InvalidInstructionData))
+	}
+}
+
+instruction!(BitflipInstruction, BadgeMint);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::bpf_loader_upgradeable;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_badge_mint;
+	use crate::get_pda_config;
+	use crate::get_pda_player;
+	use crate::get_pda_treasury;
+	use crate::get_player_badge_token_account;
+	use crate::leak;
+	use crate::BitflipError;
+
+	#[test_log::test]
+	fn should_have_valid_badge() -> anyhow::Result<()> {
+		let accounts = create_account_infos(true);
+		let result = process_badge_mint(&accounts, &[u8::MAX]);
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos(true);
+		let result = process_badge_mint(&accounts[..8], &create_args());
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(true);
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_badge_mint(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_state_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(true);
+		let player_state_info = &mut accounts[2];
+		player_state_info.key = leak(Pubkey::new_unique());
+
+		let result = process_badge_mint(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn badge_mint_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(true);
+		let badge_mint_info = &mut accounts[4];
+		badge_mint_info.key = leak(get_pda_badge_mint(BadgeType::PlayerFlips).0);
+
+		let result = process_badge_mint(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn badge_should_be_earned() -> anyhow::Result<()> {
+		let accounts = create_account_infos(false);
+		let result = process_badge_mint(&accounts, &create_args());
+		check!(result.unwrap_err() == BitflipError::BadgeNotEarned.into());
+
+		Ok(())
+	}
+
+	fn create_args() -> Vec<u8> {
+		bytemuck::bytes_of(&BadgeMint::new(BadgeType::FirstFlip)).to_vec()
+	}
+
+	fn create_account_infos<'info>(earned: bool) -> [AccountInfo<'info>; 9] {
+		let game_index = 0;
+		let badge = BadgeType::FirstFlip;
+		let player = Pubkey::new_unique();
+		let player_key = leak(player);
+		let player_lamports = leak(1_000_000_000);
+		let player_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					Pubkey::new_unique(),
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let player_state_key = leak(get_pda_player(game_index, &player).0);
+		let player_state_lamports = leak(0);
+		let player_state_data = {
+			let bump = get_pda_player(game_index, &player).1;
+			let mut player_state = PlayerState::new(player, game_index, bump);
+
+			if earned {
+				player_state.earn(badge);
+			}
+
+			let mut data = vec![0u8; 8];
+			data[0] = PlayerState::discriminator();
+			data.append(&mut player_state.to_bytes().to_vec());
+			leak(data)
+		};
+		let treasury_key = leak(get_pda_treasury().0);
+		let treasury_lamports = leak(0);
+		let treasury_data = leak(vec![]);
+		let badge_mint_key = leak(get_pda_badge_mint(badge).0);
+		let badge_mint_lamports = leak(0);
+		let badge_mint_data = leak(vec![]);
+		let player_badge_token_account_key = leak(get_player_badge_token_account(&player, badge));
+		let player_badge_token_account_lamports = leak(0);
+		let player_badge_token_account_data = leak(vec![]);
+		let associated_token_program_lamports = leak(0);
+		let associated_token_program_data = leak(vec![]);
+		let token_program_lamports = leak(0);
+		let token_program_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let player_state_info = AccountInfo::new(
+			player_state_key,
+			false,
+			true,
+			player_state_lamports,
+			player_state_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let treasury_info = AccountInfo::new(
+			treasury_key,
+			false,
+			false,
+			treasury_lamports,
+			treasury_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let badge_mint_info = AccountInfo::new(
+			badge_mint_key,
+			false,
+			true,
+			badge_mint_lamports,
+			badge_mint_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let player_badge_token_account_info = AccountInfo::new(
+			player_badge_token_account_key,
+			false,
+			true,
+			player_badge_token_account_lamports,
+			player_badge_token_account_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let associated_token_program_info = AccountInfo::new(
+			&spl_associated_token_account::ID,
+			false,
+			false,
+			associated_token_program_lamports,
+			associated_token_program_data,
+			&bpf_loader_upgradeable::ID,
+			true,
+			u64::MAX,
+		);
+		let token_program_info = AccountInfo::new(
+			&spl_token_2022::ID,
+			false,
+			false,
+			token_program_lamports,
+			token_program_data,
+			&bpf_loader_upgradeable::ID,
+			true,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			player_info,
+			config_info,
+			player_state_info,
+			treasury_info,
+			badge_mint_info,
+			player_badge_token_account_info,
+			associated_token_program_info,
+			token_program_info,
+			system_program_info,
+		]
+	}
+}
@@ -0,0 +1,241 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::seeds_bounty;
+use crate::seeds_section;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::BountyState;
+use crate::SectionState;
+use crate::ID;
+
+/// Claim or refund a bounty. This instruction is permissionless.
+///
+/// When the region of the section matches the target pattern before the
+/// expiry the reward is paid to the claimer. Once the bounty has expired the
+/// reward is refunded to the poster. In both cases the bounty account is
+/// closed and the rent is returned to the poster.
+pub fn process_bounty_claim(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [claimer_info, poster_info, section_info, bounty_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let bounty = bounty_info.as_account::<BountyState>(&ID)?;
+	let section = section_info.as_account::<SectionState>(&ID)?;
+	let bounty_seeds_with_bump = seeds_bounty!(
+		bounty.game_index,
+		bounty.section_index,
+		bounty.poster,
+		bounty.bounty_index(),
+		bounty.bump
+	);
+	let section_seeds_with_bump =
+		seeds_section!(bounty.game_index, bounty.section_index, section.bump);
+
+	claimer_info.assert_signer()?.assert_writable()?;
+	poster_info.assert_writable()?;
+	section_info
+		.assert_type::<SectionState>(&ID)?
+		.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+	bounty_info
+		.assert_type::<BountyState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(bounty_seeds_with_bump, &ID)?;
+	bounty.assert_err(
+		|state| state.poster.eq(poster_info.key),
+		BitflipError::InvalidAccount,
+	)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	let recipient_info = if bounty.expired(current_time) {
+		msg!("bounty expired: refunding the poster");
+		poster_info
+	} else if bounty.matches(section) {
+		msg!("bounty pattern matched: paying the claimer");
+		claimer_info
+	} else {
+		return Err(BitflipError::BountyNotClaimable.into());
+	};
+
+	bounty_info.send(bounty.reward(), recipient_info);
+	close_account(bounty_info, poster_info)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BountyClaim {}
+
+instruction!(BitflipInstruction, BountyClaim);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_bounty;
+	use crate::get_pda_section;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_bounty_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_bounty_claim(&accounts[..3]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn claimer_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let claimer_info = &mut accounts[0];
+		claimer_info.is_signer = false;
+
+		let result = process_bounty_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn poster_should_match_bounty() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let poster_info = &mut accounts[1];
+		poster_info.key = leak(Pubkey::new_unique());
+
+		let result = process_bounty_claim(&accounts);
+		check!(result.unwrap_err() == BitflipError::InvalidAccount.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_info = &mut accounts[2];
+		section_info.key = leak(Pubkey::new_unique());
+
+		let result = process_bounty_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn bounty_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let bounty_info = &mut accounts[3];
+		bounty_info.is_writable = false;
+
+		let result = process_bounty_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 4] {
+		let game_index = 0;
+		let section_index = 0;
+		let bounty_index = 0;
+		let poster = Pubkey::new_unique();
+		let claimer_key = leak(Pubkey::new_unique());
+		let claimer_lamports = leak(0);
+		let claimer_data = leak(vec![]);
+		let poster_key = leak(poster);
+		let poster_lamports = leak(0);
+		let poster_data = leak(vec![]);
+		let section_key = leak(get_pda_section(game_index, section_index).0);
+		let section_lamports = leak(0);
+		let section_data = {
+			let section_bump = get_pda_section(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionState::discriminator();
+			data.append(
+				&mut SectionState::new(
+					Pubkey::new_unique(),
+					game_index,
+					section_index,
+					section_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let (bounty_key, bounty_bump) =
+			get_pda_bounty(game_index, section_index, &poster, bounty_index);
+		let bounty_key = leak(bounty_key);
+		let bounty_lamports = leak(0);
+		let bounty_data = {
+			let mut data = vec![0u8; 8];
+			let mut bounty = BountyState::zeroed();
+			bounty.poster = poster;
+			bounty.reward = 1_000.into();
+			bounty.bounty_index = bounty_index.into();
+			bounty.game_index = game_index;
+			bounty.section_index = section_index;
+			bounty.width = 1;
+			bounty.height = 1;
+			bounty.bump = bounty_bump;
+			data[0] = BountyState::discriminator();
+			data.append(&mut bounty.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let claimer_info = AccountInfo::new(
+			claimer_key,
+			true,
+			true,
+			claimer_lamports,
+			claimer_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let poster_info = AccountInfo::new(
+			poster_key,
+			false,
+			true,
+			poster_lamports,
+			poster_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let section_info = AccountInfo::new(
+			section_key,
+			false,
+			false,
+			section_lamports,
+			section_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let bounty_info = AccountInfo::new(
+			bounty_key,
+			false,
+			true,
+			bounty_lamports,
+			bounty_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[claimer_info, poster_info, section_info, bounty_info]
+	}
+}
@@ -0,0 +1,138 @@
+use spl_pod::primitives::PodI64;
+use spl_pod::primitives::PodU16;
+use spl_pod::primitives::PodU64;
+use steel::*;
+
+use crate::seeds_bounty;
+use crate::seeds_game;
+use crate::seeds_section;
+use crate::validate_region;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::BountyState;
+use crate::GameState;
+use crate::PodSectionRows;
+use crate::SectionState;
+use crate::ID;
+
+/// Post a bounty on a rectangular region of a section.
+///
+/// The `reward` is escrowed in the bounty account and is paid to anyone who
+/// makes the region match the `pattern` before the `expiry`.
+pub fn process_bounty_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = BountyCreate::try_from_bytes(data)?;
+	validate_region(args.x, args.y, args.width, args.height)?;
+
+	// load accounts
+	let [poster_info, game_info, section_info, bounty_info, system_program_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let section = section_info.as_account::<SectionState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_seeds_with_bump = seeds_section!(game.game_index, args.section_index, section.bump);
+	let bounty_index = args.bounty_index();
+	let bounty_seeds = seeds_bounty!(
+		game.game_index,
+		args.section_index,
+		poster_info.key,
+		bounty_index
+	);
+	let bounty_bump = bounty_info.assert_canonical_bump(bounty_seeds, &ID)?;
+
+	poster_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_info
+		.assert_type::<SectionState>(&ID)?
+		.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+	bounty_info.assert_empty()?.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| state.running(current_time),
+		BitflipError::GameNotRunning,
+	)?;
+
+	if args.expiry() <= current_time {
+		return Err(BitflipError::BountyExpiryInvalid.into());
+	}
+
+	create_account_with_bump::<BountyState>(
+		bounty_info,
+		system_program_info,
+		poster_info,
+		&ID,
+		bounty_seeds,
+		bounty_bump,
+	)?;
+
+	let bounty = bounty_info.as_account_mut::<BountyState>(&ID)?;
+	*bounty = BountyState {
+		version: BountyState::VERSION,
+		poster: *poster_info.key,
+		reward: args.reward,
+		expiry: args.expiry,
+		pattern: args.pattern,
+		bounty_index: args.bounty_index,
+		game_index: game.game_index,
+		section_index: args.section_index,
+		x: args.x,
+		y: args.y,
+		width: args.width,
+		height: args.height,
+		bump: bounty_bump,
+		_padding: [0; 32],
+	};
+
+	// escrow the reward in the bounty account.
+	bounty_info.collect(args.reward(), poster_info)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BountyCreate {
+	/// The lamports paid to the claimer.
+	pub reward: PodU64,
+	/// The unix timestamp after which the bounty is refunded.
+	pub expiry: PodI64,
+	/// The target pattern for the region. See [`BountyState::pattern`].
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub pattern: PodSectionRows,
+	/// The index of the bounty which is unique for each poster and section.
+	pub bounty_index: PodU16,
+	/// The section for the bounty.
+	pub section_index: u8,
+	/// The left coordinate of the region.
+	pub x: u8,
+	/// The top coordinate of the region.
+	pub y: u8,
+	/// The width of the region.
+	pub width: u8,
+	/// The height of the region.
+	pub height: u8,
+}
+
+impl BountyCreate {
+	pub fn reward(&self) -> u64 {
+		self.reward.into()
+	}
+
+	pub fn expiry(&self) -> i64 {
+		self.expiry.into()
+	}
+
+	pub fn bounty_index(&self) -> u16 {
+		self.bounty_index.into()
+	}
+}
+
+instruction!(BitflipInstruction, BountyCreate);
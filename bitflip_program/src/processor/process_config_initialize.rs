@@ -94,6 +94,7 @@ pub fn process_config_initialize(accounts: &[AccountInfo<'_>]) -> ProgramResult
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigInitialize {}
 
 instruction!(BitflipInstruction, ConfigInitialize);
@@ -43,6 +43,7 @@ pub fn process_config_update_authority(accounts: &[AccountInfo]) -> ProgramResul
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigUpdateAuthority {}
 
 instruction!(BitflipInstruction, ConfigUpdateAuthority);
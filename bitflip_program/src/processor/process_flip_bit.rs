@@ -1,17 +1,39 @@
-use solana_program::msg;
+use bitflags::bitflags;
+use spl_pod::primitives::PodU64;
 use steel::*;
 
+use crate::as_token_account;
+use crate::assert_canonical_seeds_with_bump;
+use crate::cpi::burn;
 use crate::cpi::create_associated_token_account_idempotent;
 use crate::cpi::transfer_checked;
+use crate::create_pda_mint;
+use crate::debug_msg;
+use crate::get_token_payment;
+use crate::get_token_payment_burn;
 use crate::seeds_config;
 use crate::seeds_game;
 use crate::seeds_mint;
+use crate::seeds_player;
+use crate::seeds_player_team;
 use crate::seeds_section;
+use crate::seeds_section_cooldown;
+use crate::seeds_section_locks;
+use crate::seeds_section_metadata;
+use crate::seeds_section_mines;
+use crate::seeds_team_pool;
 use crate::BitflipError;
 use crate::BitflipInstruction;
 use crate::ConfigState;
 use crate::GameState;
+use crate::PlayerState;
+use crate::PlayerTeamState;
+use crate::SectionCooldownState;
+use crate::SectionLocksState;
+use crate::SectionMetadataState;
+use crate::SectionMinesState;
 use crate::SectionState;
+use crate::TeamPoolState;
 use crate::TokenMember;
 use crate::ID;
 use crate::SEED_GAME;
@@ -19,13 +41,72 @@ use crate::SEED_PREFIX;
 use crate::SEED_SECTION;
 use crate::TOKEN_DECIMALS;
 
+/// Flip a bit within a section.
+///
+/// When the game is a minesweeper game the `temp_signer_info` and
+/// `section_mines_info` accounts must be provided after the
+/// `section_locks_info`. The mine layout is hidden until the game ends so the
+/// program can't check it during the flip. Instead the backend co-signs every
+/// flip as the temp signer and sets [`FlipBit::mine`] from the layout. A flip
+/// which reveals a mine forfeits its payment to the section pot without
+/// earning any tokens. The reported hits are audited against the committed
+/// layout once it is revealed with `SectionMinesReveal`.
+///
+/// When the game has a cooldown the `section_cooldown_info` account must be
+/// provided after the minesweeper accounts. Flipping a word of the section
+/// which is cooling down is either rejected or charged at a multiplied price.
+///
+/// When the game is a team game the `player_team_info` and `team_pool_info`
+/// accounts must be provided after the cooldown account. The player must have
+/// joined a team and only flips which set a bit to the color of their team are
+/// counted towards their share of the team pool. A share of the lamports paid
+/// for the flip is deposited into the team pool.
+///
+/// When the [`SectionMetadataState`] gates the section the player's token
+/// account for the gate [`crate::TokenMember`] must be provided last and hold
+/// at least the gate amount.
+///
+/// A flip which doesn't change the bit is charged as two flips unless
+/// [`FlipBitFlags::SKIP_UNCHANGED`] is set, in which case nothing is paid or
+/// earned. Set [`FlipBitFlags::EXPECTED`] to fail when the bit is not in the
+/// `expected` state before the flip.
+///
+/// Bits within the active region lock of another player can't be flipped. The
+/// `section_locks_info` account is always required but is empty until the
+/// first lock of the section is bought.
+///
+/// The player's `$BIT` token account must already exist when paying with
+/// lamports. It is created once with `PlayerTokenAccountInitialize`.
+///
+/// The flips are recorded in the [`PlayerState`] of the player which is
+/// created on their first flip of the game. Any milestones reached by the flip
+/// earn a [`crate::BadgeType`] which can be minted with `BadgeMint`.
+///
+/// The `game_info` account is only written by the first flip of the game which
+/// earns [`crate::BadgeType::FirstFlip`]. It is read-only for every other flip
+/// so that flips don't contend for the game account. While
+/// [`GameState::flipped`] is unset the flip is rejected unless clients pass it
+/// as writable with [`crate::with_first_flip`], so the badge only depends on
+/// the state of the game.
 pub fn process_flip_bit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
 	// parse the instruction data.
 	let args = FlipBit::try_from_bytes(data)?;
+
+	process_flip(accounts, args, None)
+}
+
+/// Flip a bit and pay with lamports or, when the `payment` member is provided,
+/// with tokens of that member. The mint and token accounts belong to the
+/// `payment` member when paying with tokens.
+pub(crate) fn process_flip(
+	accounts: &[AccountInfo],
+	args: &FlipBit,
+	payment: Option<TokenMember>,
+) -> ProgramResult {
 	args.validate()?;
 
 	// load accounts
-	let [player_info, player_bit_token_account_info, config_info, game_info, mint_bit_info, section_info, section_bit_token_account_info, associated_token_program_info, token_program_info, system_program_info] =
+	let [player_info, player_bit_token_account_info, config_info, game_info, mint_bit_info, section_info, section_bit_token_account_info, associated_token_program_info, token_program_info, system_program_info, player_state_info, section_locks_info, section_metadata_info, remaining_accounts @ ..] =
 		accounts
 	else {
 		return Err(ProgramError::NotEnoughAccountKeys);
@@ -35,7 +116,8 @@ pub fn process_flip_bit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
 	let game = game_info.as_account::<GameState>(&ID)?;
 	let section = section_info.as_account_mut::<SectionState>(&ID)?;
 	let config_seeds_with_bump = seeds_config!(config.bump);
-	let mint_seeds_with_bump = seeds_mint!(TokenMember::Bit, config.mint_bit_bump);
+	let member = payment.unwrap_or(TokenMember::Bit);
+	let mint_seeds_with_bump = seeds_mint!(member, member.bump(config));
 	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
 	let section_seeds_with_bump = seeds_section!(game.game_index, args.section_index, section.bump);
 
@@ -68,14 +150,208 @@ pub fn process_flip_bit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
 	associated_token_program_info.assert_program(&spl_associated_token_account::ID)?;
 	token_program_info.assert_program(&spl_token_2022::ID)?;
 	system_program_info.assert_program(&system_program::ID)?;
+	player_state_info.assert_writable()?;
+
+	let player_seeds = seeds_player!(game.game_index, player_info.key);
+	let player_bump = args.player_bump;
+	if player_state_info.data_is_empty() {
+		assert_canonical_seeds_with_bump(player_state_info, player_seeds, player_bump)?;
+	} else {
+		player_state_info
+			.assert_type::<PlayerState>(&ID)?
+			.assert_seeds_with_bump(
+				seeds_player!(game.game_index, player_info.key, player_bump),
+				&ID,
+			)?;
+	}
+
+	if section_locks_info.data_is_empty() {
+		assert_canonical_seeds_with_bump(
+			section_locks_info,
+			seeds_section_locks!(game.game_index, args.section_index),
+			args.section_locks_bump,
+		)?;
+	} else {
+		section_locks_info
+			.assert_type::<SectionLocksState>(&ID)?
+			.assert_seeds_with_bump(
+				seeds_section_locks!(game.game_index, args.section_index, args.section_locks_bump),
+				&ID,
+			)?;
+	}
+
+	if section_metadata_info.data_is_empty() {
+		assert_canonical_seeds_with_bump(
+			section_metadata_info,
+			seeds_section_metadata!(game.game_index, args.section_index),
+			args.section_metadata_bump,
+		)?;
+	} else {
+		section_metadata_info
+			.assert_type::<SectionMetadataState>(&ID)?
+			.assert_seeds_with_bump(
+				seeds_section_metadata!(
+					game.game_index,
+					args.section_index,
+					args.section_metadata_bump
+				),
+				&ID,
+			)?;
+	}
 
-	let current_time = Clock::get()?.unix_timestamp;
+	let clock = Clock::get()?;
+	let current_time = clock.unix_timestamp;
 	game.assert_err(
 		|state| state.running(current_time),
 		BitflipError::GameNotRunning,
 	)?;
 
+	if !section_locks_info.data_is_empty() {
+		section_locks_info
+			.as_account::<SectionLocksState>(&ID)?
+			.assert_unlocked(player_info.key, args.array_index, args.offset, current_time)?;
+	}
+
+	let gate = if section_metadata_info.data_is_empty() {
+		None
+	} else {
+		section_metadata_info
+			.as_account::<SectionMetadataState>(&ID)?
+			.gate()
+	};
+
+	if let Some((member, amount)) = gate {
+		let gate_index = usize::from(game.minesweeper()) * 2
+			+ usize::from(game.cooldown())
+			+ usize::from(game.teams()) * 2;
+		let Some(player_gate_token_account_info) = remaining_accounts.get(gate_index) else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+		let gate_mint = create_pda_mint(member, member.bump(config))?;
+		player_gate_token_account_info
+			.assert_associated_token_address(player_info.key, &gate_mint)?;
+
+		let balance = if player_gate_token_account_info.data_is_empty() {
+			0
+		} else {
+			player_gate_token_account_info.assert_owner(&spl_token_2022::ID)?;
+			u64::from(as_token_account(player_gate_token_account_info)?.amount)
+		};
+
+		if balance < amount {
+			return Err(BitflipError::SectionGated.into());
+		}
+	}
+
+	if let Some(expected) = args.expected() {
+		section.assert_err(
+			|state| state.is_checked(args.array_index, args.offset) == expected,
+			BitflipError::BitUnexpected,
+		)?;
+	}
+
+	let mut team = if game.teams() {
+		let team_index = usize::from(game.minesweeper()) * 2 + usize::from(game.cooldown());
+		let Some([player_team_info, team_pool_info, ..]) = remaining_accounts.get(team_index..)
+		else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+
+		if player_team_info.data_is_empty() {
+			return Err(BitflipError::TeamNotJoined.into());
+		}
+
+		let player_team = player_team_info.as_account_mut::<PlayerTeamState>(&ID)?;
+		let team_pool = team_pool_info.as_account_mut::<TeamPoolState>(&ID)?;
+		let player_team_seeds_with_bump =
+			seeds_player_team!(game.game_index, player_info.key, player_team.bump);
+		let team_pool_seeds_with_bump = seeds_team_pool!(game.game_index, team_pool.bump);
+
+		player_team_info
+			.assert_type::<PlayerTeamState>(&ID)?
+			.assert_writable()?
+			.assert_seeds_with_bump(player_team_seeds_with_bump, &ID)?;
+		team_pool_info
+			.assert_type::<TeamPoolState>(&ID)?
+			.assert_writable()?
+			.assert_seeds_with_bump(team_pool_seeds_with_bump, &ID)?;
+
+		Some((player_team, team_pool, team_pool_info))
+	} else {
+		None
+	};
+
+	let previous_section_flips = section.flips();
 	let is_changed = section.set_bit(args)?;
+
+	if !is_changed && args.skip_unchanged() {
+		debug_msg!("bit unchanged: skipping payment");
+		return Ok(());
+	}
+
+	let price_multiplier = if game.cooldown() {
+		let cooldown_index = if game.minesweeper() { 2 } else { 0 };
+		let Some(section_cooldown_info) = remaining_accounts.get(cooldown_index) else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+		section_cooldown_info.assert_writable()?;
+
+		if section_cooldown_info.data_is_empty() {
+			let section_cooldown_seeds =
+				seeds_section_cooldown!(game.game_index, args.section_index);
+			let section_cooldown_bump =
+				section_cooldown_info.assert_canonical_bump(section_cooldown_seeds, &ID)?;
+
+			create_account_with_bump::<SectionCooldownState>(
+				section_cooldown_info,
+				system_program_info,
+				player_info,
+				&ID,
+				section_cooldown_seeds,
+				section_cooldown_bump,
+			)?;
+
+			let section_cooldown =
+				section_cooldown_info.as_account_mut::<SectionCooldownState>(&ID)?;
+			*section_cooldown = SectionCooldownState::new(
+				game.game_index,
+				args.section_index,
+				section_cooldown_bump,
+			);
+		}
+
+		let section_cooldown = section_cooldown_info.as_account_mut::<SectionCooldownState>(&ID)?;
+		let section_cooldown_seeds_with_bump =
+			seeds_section_cooldown!(game.game_index, args.section_index, section_cooldown.bump);
+		section_cooldown_info
+			.assert_type::<SectionCooldownState>(&ID)?
+			.assert_seeds_with_bump(section_cooldown_seeds_with_bump, &ID)?;
+
+		section_cooldown.touch(
+			args.array_index,
+			clock.slot,
+			game.cooldown_slots(),
+			game.cooldown_multiplier,
+		)?
+	} else {
+		1
+	};
+
+	if player_state_info.data_is_empty() {
+		create_account_with_bump::<PlayerState>(
+			player_state_info,
+			system_program_info,
+			player_info,
+			&ID,
+			player_seeds,
+			player_bump,
+		)?;
+
+		let player_state = player_state_info.as_account_mut::<PlayerState>(&ID)?;
+		*player_state = PlayerState::new(*player_info.key, game.game_index, player_bump);
+	}
+
+	let player_state = player_state_info.as_account_mut::<PlayerState>(&ID)?;
 	let flips = if !is_changed {
 		section.flip_on(1)?;
 		section.flip_off(1)?;
@@ -88,25 +364,133 @@ pub fn process_flip_bit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
 		1
 	};
 
-	let token_price = section.get_token_price_in_lamports(game.remaining_time(current_time));
+	let first_flip = !game.flipped();
+
+	if first_flip {
+		if !game_info.is_writable {
+			return Err(BitflipError::FirstFlipNotWritable.into());
+		}
+
+		game_info.as_account_mut::<GameState>(&ID)?.flipped = 1;
+	}
+
+	player_state.record_flips(section, previous_section_flips, first_flip, flips as u32)?;
+
+	if let Some((player_team, team_pool, _)) = &mut team {
+		if is_changed {
+			player_team.record_flip(team_pool, args)?;
+		}
+	}
+
+	let token_price = section
+		.get_token_price_in_lamports(game, game.remaining_time(current_time))
+		.saturating_mul(price_multiplier);
 	let lamports_to_transfer = token_price.saturating_mul(flips);
-	msg!("flips: {}", flips);
-	msg!("token price: {}", token_price);
+	debug_msg!("flips: {}", flips);
+	debug_msg!("token price: {}", token_price);
 
-	create_associated_token_account_idempotent(
-		player_info,
-		player_bit_token_account_info,
-		player_info,
-		mint_bit_info,
-		token_program_info,
-		system_program_info,
-		&[],
-	)?;
+	if token_price > args.max_lamports_per_bit() {
+		return Err(BitflipError::PriceExceeded.into());
+	}
+
+	let is_mine = if game.minesweeper() {
+		let [temp_signer_info, section_mines_info, ..] = remaining_accounts else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+		let section_mines = section_mines_info.as_account_mut::<SectionMinesState>(&ID)?;
+		let section_mines_seeds_with_bump =
+			seeds_section_mines!(game.game_index, args.section_index, section_mines.bump);
+
+		temp_signer_info.assert_signer()?;
+		section_mines_info
+			.assert_type::<SectionMinesState>(&ID)?
+			.assert_writable()?
+			.assert_seeds_with_bump(section_mines_seeds_with_bump, &ID)?;
+		game.assert_err(
+			|state| state.temp_signer.eq(temp_signer_info.key),
+			BitflipError::GameSignerInvalid,
+		)?;
+
+		// token payments aren't added to the lamport pot of the section.
+		let forfeited_lamports = if payment.is_some() {
+			0
+		} else {
+			lamports_to_transfer
+		};
+		section_mines.reveal(args, forfeited_lamports)?;
+		args.mine()
+	} else {
+		false
+	};
 
-	msg!("transferring lamports to section: {}", lamports_to_transfer);
-	transfer_lamports_to_section(section_info, player_info, lamports_to_transfer)?;
+	if payment.is_some() {
+		let tokens = get_token_payment(lamports_to_transfer, game.base_lamports(), member)?;
+		let burned = get_token_payment_burn(tokens);
 
-	msg!("transferring tokens from section");
+		create_associated_token_account_idempotent(
+			player_info,
+			section_bit_token_account_info,
+			section_info,
+			mint_bit_info,
+			token_program_info,
+			system_program_info,
+			&[],
+		)?;
+
+		debug_msg!("transferring tokens to section: {}", tokens - burned);
+		transfer_checked(
+			player_bit_token_account_info,
+			mint_bit_info,
+			section_bit_token_account_info,
+			player_info,
+			token_program_info,
+			tokens - burned,
+			TOKEN_DECIMALS,
+			&[],
+		)?;
+
+		if burned > 0 {
+			debug_msg!("burning tokens: {}", burned);
+			burn(
+				player_bit_token_account_info,
+				mint_bit_info,
+				player_info,
+				token_program_info,
+				burned,
+				&[],
+			)?;
+		}
+
+		return Ok(());
+	}
+
+	// the token account is created once by `PlayerTokenAccountInitialize` to
+	// keep the associated token program out of every flip.
+	if player_bit_token_account_info.data_is_empty() {
+		return Err(BitflipError::PlayerTokenAccountMissing.into());
+	}
+
+	let team_lamports = match team {
+		Some((_, team_pool, team_pool_info)) if !is_mine => {
+			let team_lamports = TeamPoolState::revenue_share(lamports_to_transfer);
+			team_pool.deposit(team_lamports)?;
+			debug_msg!("transferring lamports to team pool: {}", team_lamports);
+			team_pool_info.collect(team_lamports, player_info)?;
+			team_lamports
+		}
+		_ => 0,
+	};
+
+	let section_lamports = lamports_to_transfer - team_lamports;
+	debug_msg!("transferring lamports to section: {}", section_lamports);
+	transfer_lamports_to_section(section_info, player_info, section_lamports)?;
+
+	if is_mine {
+		debug_msg!("revealed a mine: payment forfeited to the section pot");
+		return Ok(());
+	}
+
+	debug_msg!("transferring tokens from section");
 	transfer_tokens_from_section(
 		mint_bit_info,
 		section_info,
@@ -138,7 +522,7 @@ pub fn transfer_tokens_from_section<'info>(
 	section_state: &SectionState,
 	tokens: u64,
 ) -> ProgramResult {
-	msg!("transferring tokens from section: {}", tokens);
+	debug_msg!("transferring tokens from section: {}", tokens);
 	let signer = &[
 		SEED_PREFIX,
 		SEED_GAME,
@@ -175,6 +559,44 @@ pub struct FlipBit {
 	pub offset: u8,
 	/// The value to set the bit to: `0` or `1`.
 	pub value: u8,
+	/// Set by the temp signer in minesweeper games when the revealed cell is a
+	/// mine: `0` or `1`.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub mine: u8,
+	/// The value the bit must have before the flip: `0` or `1`. Only checked
+	/// when [`FlipBitFlags::EXPECTED`] is set.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub expected: u8,
+	/// The [`FlipBitFlags`] for this flip.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub flags: u8,
+	/// The maximum price of each flip. The flip fails with
+	/// [`BitflipError::PriceExceeded`] when the price is higher. There is no
+	/// default so that clients cap the price, e.g. with
+	/// [`FlipQuote::max_price_with_slippage`](crate::FlipQuote::max_price_with_slippage).
+	#[cfg_attr(feature = "client", builder(setter(into)))]
+	pub max_lamports_per_bit: PodU64,
+	/// The canonical bump of the player state, supplied by the client so that
+	/// the program doesn't need to search for it.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub player_bump: u8,
+	/// The canonical bump of the section locks.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub section_locks_bump: u8,
+	/// The canonical bump of the section metadata.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub section_metadata_bump: u8,
+}
+
+bitflags! {
+	/// The options for a [`FlipBit`] instruction.
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	pub struct FlipBitFlags: u8 {
+		/// Fail the flip when the bit doesn't match [`FlipBit::expected`].
+		const EXPECTED = 1 << 0;
+		/// Skip the payment and token transfer when the bit is unchanged.
+		const SKIP_UNCHANGED = 1 << 1;
+	}
 }
 
 impl FlipBit {
@@ -182,6 +604,29 @@ impl FlipBit {
 		self.value == 1
 	}
 
+	pub fn mine(&self) -> bool {
+		self.mine == 1
+	}
+
+	pub fn flags(&self) -> FlipBitFlags {
+		FlipBitFlags::from_bits_truncate(self.flags)
+	}
+
+	/// The value the bit must have before the flip.
+	pub fn expected(&self) -> Option<bool> {
+		self.flags()
+			.contains(FlipBitFlags::EXPECTED)
+			.then_some(self.expected == 1)
+	}
+
+	pub fn max_lamports_per_bit(&self) -> u64 {
+		self.max_lamports_per_bit.into()
+	}
+
+	pub fn skip_unchanged(&self) -> bool {
+		self.flags().contains(FlipBitFlags::SKIP_UNCHANGED)
+	}
+
 	pub fn validate(&self) -> ProgramResult {
 		if self.offset >= 16 {
 			return Err(BitflipError::InvalidBitOffset.into());
@@ -191,6 +636,18 @@ impl FlipBit {
 			return Err(BitflipError::InvalidPlayValue.into());
 		}
 
+		if self.mine != 0 && self.mine != 1 {
+			return Err(ProgramError::InvalidInstructionData);
+		}
+
+		if self.expected != 0 && self.expected != 1 {
+			return Err(ProgramError::InvalidInstructionData);
+		}
+
+		if FlipBitFlags::from_bits(self.flags).is_none() {
+			return Err(ProgramError::InvalidInstructionData);
+		}
+
 		Ok(())
 	}
 }
@@ -210,7 +667,10 @@ mod tests {
 	use crate::get_pda_config;
 	use crate::get_pda_game;
 	use crate::get_pda_mint;
+	use crate::get_pda_player;
 	use crate::get_pda_section;
+	use crate::get_pda_section_locks;
+	use crate::get_pda_section_metadata;
 	use crate::get_player_token_account;
 	use crate::get_section_token_account;
 	use crate::leak;
@@ -225,7 +685,9 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
+		let args = with_bumps(args, &account_infos);
 		println!(
 			"account_infos: {:?}",
 			account_infos
@@ -250,6 +712,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 		let result = process_flip_bit(&account_infos[0..9], bytemuck::bytes_of(&args));
 
@@ -268,6 +731,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -286,6 +750,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -305,6 +770,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -325,6 +791,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -344,6 +811,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -363,6 +831,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -382,6 +851,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -401,6 +871,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -420,6 +891,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -439,6 +911,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -458,6 +931,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -477,6 +951,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -494,6 +969,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -513,6 +989,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -532,6 +1009,7 @@ mod tests {
 			.array_index(0)
 			.offset(0)
 			.value(1)
+			.max_lamports_per_bit(u64::MAX)
 			.build();
 
 		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
@@ -540,7 +1018,109 @@ mod tests {
 		Ok(())
 	}
 
-	fn create_account_infos<'info>(game_index: u8, section_index: u8) -> [AccountInfo<'info>; 10] {
+	#[test_log::test]
+	fn player_state_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(0, 0);
+		let player_state_info = &mut accounts[10];
+		player_state_info.key = leak(Pubkey::new_unique());
+
+		let args = FlipBit::builder()
+			.section_index(0)
+			.array_index(0)
+			.offset(0)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build();
+		let args = with_bumps(args, &accounts);
+
+		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_locks_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(0, 0);
+		let section_locks_info = &mut accounts[11];
+		section_locks_info.key = leak(Pubkey::new_unique());
+
+		let args = FlipBit::builder()
+			.section_index(0)
+			.array_index(0)
+			.offset(0)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build();
+		let args = with_bumps(args, &accounts);
+
+		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(0, 0);
+		let section_metadata_info = &mut accounts[12];
+		section_metadata_info.key = leak(Pubkey::new_unique());
+
+		let args = FlipBit::builder()
+			.section_index(0)
+			.array_index(0)
+			.offset(0)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build();
+		let args = with_bumps(args, &accounts);
+
+		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_state_bump_should_be_canonical() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(0, 0);
+		let mut args = FlipBit::builder()
+			.section_index(0)
+			.array_index(0)
+			.offset(0)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build();
+		args = with_bumps(args, &accounts);
+		// a lower bump which is also a valid program address.
+		args.player_bump = (0..args.player_bump)
+			.rev()
+			.find(|&bump| {
+				Pubkey::create_program_address(seeds_player!(0u8, accounts[0].key, bump), &ID)
+					.is_ok()
+			})
+			.unwrap();
+		let player_state = Pubkey::create_program_address(
+			seeds_player!(0u8, accounts[0].key, args.player_bump),
+			&ID,
+		)?;
+		accounts[10].key = leak(player_state);
+
+		let result = process_flip_bit(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	/// Set the canonical bumps which are supplied by the client.
+	fn with_bumps(mut args: FlipBit, accounts: &[AccountInfo]) -> FlipBit {
+		args.player_bump = get_pda_player(0, accounts[0].key).1;
+		args.section_locks_bump = get_pda_section_locks(0, args.section_index).1;
+		args.section_metadata_bump = get_pda_section_metadata(0, args.section_index).1;
+		args
+	}
+
+	fn create_account_infos<'info>(game_index: u8, section_index: u8) -> [AccountInfo<'info>; 13] {
 		let player_key = leak(Pubkey::new_unique());
 		let player_lamports = leak(0);
 		let player_data = leak(vec![]);
@@ -618,6 +1198,15 @@ mod tests {
 		let token_program_data = leak(vec![]);
 		let system_program_lamports = leak(0);
 		let system_program_data = leak(vec![]);
+		let player_state_key = leak(get_pda_player(game_index, player_key).0);
+		let player_state_lamports = leak(0);
+		let player_state_data = leak(vec![]);
+		let section_locks_key = leak(get_pda_section_locks(game_index, section_index).0);
+		let section_locks_lamports = leak(0);
+		let section_locks_data = leak(vec![]);
+		let section_metadata_key = leak(get_pda_section_metadata(game_index, section_index).0);
+		let section_metadata_lamports = leak(0);
+		let section_metadata_data = leak(vec![]);
 
 		let player_info = AccountInfo::new(
 			player_key,
@@ -719,6 +1308,36 @@ mod tests {
 			true,
 			u64::MAX,
 		);
+		let player_state_info = AccountInfo::new(
+			player_state_key,
+			false,
+			true,
+			player_state_lamports,
+			player_state_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let section_locks_info = AccountInfo::new(
+			section_locks_key,
+			false,
+			false,
+			section_locks_lamports,
+			section_locks_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let section_metadata_info = AccountInfo::new(
+			section_metadata_key,
+			false,
+			false,
+			section_metadata_lamports,
+			section_metadata_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
 		[
 			player_info,
 			player_bit_token_account_info,
@@ -730,6 +1349,9 @@ mod tests {
 			associated_token_program_info,
 			token_program_info,
 			system_program_info,
+			player_state_info,
+			section_locks_info,
+			section_metadata_info,
 		]
 	}
 }
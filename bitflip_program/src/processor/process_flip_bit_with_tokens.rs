@@ -0,0 +1,135 @@
+use steel::*;
+
+use crate::process_flip;
+use crate::BitflipInstruction;
+use crate::FlipBit;
+use crate::TokenMember;
+
+/// Flip a bit within a section and pay with tokens instead of lamports.
+///
+/// The accounts are the same as [`crate::process_flip_bit`] except that the
+/// mint and both token accounts belong to the payment [`TokenMember`]. The
+/// lamport price of the flip is converted to bits at the base price of a bit
+/// and rounded up to a whole bit. The flip fails with
+/// [`crate::BitflipError::TokenPaymentInexact`] unless the bits are a whole
+/// number of tokens of the member. A portion of the payment is burned and the
+/// rest is transferred to the section. No tokens are earned by the flip.
+pub fn process_flip_bit_with_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = FlipBitWithTokens::try_from_bytes(data)?;
+	let member = args.member()?;
+
+	process_flip(accounts, &args.flip, Some(member))
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FlipBitWithTokens {
+	/// The flip being made.
+	pub flip: FlipBit,
+	/// The [`TokenMember`] used to pay for the flip.
+	pub member: u8,
+}
+
+impl FlipBitWithTokens {
+	pub fn new(flip: FlipBit, member: TokenMember) -> Self {
+		Self {
+			flip,
+			member: member.into(),
+		}
+	}
+
+	pub fn member(&self) -> Result<TokenMember, ProgramError> {
+		TokenMember::try_from(self.member).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, FlipBitWithTokens);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_token_payment;
+	use crate::get_token_payment_burn;
+	use crate::BitflipError;
+	use crate::BASE_LAMPORTS_PER_BIT;
+	use crate::BITS_PER_KIBIBIT;
+
+	#[test_log::test]
+	fn should_have_valid_member() -> anyhow::Result<()> {
+		let mut args = FlipBitWithTokens::new(create_flip(), TokenMember::Bit);
+		args.member = u8::MAX;
+
+		let result = process_flip_bit_with_tokens(&[], bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let args = FlipBitWithTokens::new(create_flip(), TokenMember::Kibibit);
+
+		let result = process_flip_bit_with_tokens(&[], bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test]
+	fn token_payment_should_round_up_to_bit() -> anyhow::Result<()> {
+		let base = BASE_LAMPORTS_PER_BIT;
+
+		check!(get_token_payment(1, base, TokenMember::Bit)? == 1);
+		check!(get_token_payment(base, base, TokenMember::Bit)? == 1);
+		check!(get_token_payment(base + 1, base, TokenMember::Bit)? == 2);
+		check!(get_token_payment(0, base, TokenMember::Bit)? == 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn token_payment_should_be_whole_tokens() -> anyhow::Result<()> {
+		let base = BASE_LAMPORTS_PER_BIT;
+		let kibibit = base * BITS_PER_KIBIBIT;
+
+		check!(get_token_payment(kibibit, base, TokenMember::Kibibit)? == 1);
+		check!(get_token_payment(kibibit * 3, base, TokenMember::Kibibit)? == 3);
+		check!(get_token_payment(kibibit - base + 1, base, TokenMember::Kibibit)? == 1);
+		check!(get_token_payment(0, base, TokenMember::Kibibit)? == 0);
+
+		for lamports in [1, base, kibibit - base, kibibit + 1] {
+			let result = get_token_payment(lamports, base, TokenMember::Kibibit);
+			check!(result.unwrap_err() == BitflipError::TokenPaymentInexact.into());
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn token_payment_burn() {
+		check!(get_token_payment_burn(1) == 0);
+		check!(get_token_payment_burn(10) == 1);
+		check!(get_token_payment_burn(1_000) == 100);
+	}
+
+	fn create_flip() -> FlipBit {
+		FlipBit {
+			section_index: 0,
+			array_index: 0,
+			offset: 0,
+			value: 1,
+			mine: 0,
+			expected: 0,
+			flags: 0,
+			max_lamports_per_bit: u64::MAX.into(),
+			player_bump: 0,
+			section_locks_bump: 0,
+			section_metadata_bump: 0,
+		}
+	}
+}
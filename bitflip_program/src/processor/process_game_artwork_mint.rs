@@ -0,0 +1,169 @@
+use solana_program::msg;
+use spl_token_2022::extension::ExtensionType;
+use spl_token_2022::instruction::AuthorityType;
+use spl_token_metadata_interface::state::Field;
+use steel::*;
+use sysvar::rent::Rent;
+
+use crate::cpi::create_associated_token_account;
+use crate::cpi::initialize_mint;
+use crate::cpi::metadata_pointer_initialize;
+use crate::cpi::mint_to;
+use crate::cpi::set_authority;
+use crate::cpi::token_metadata_initialize;
+use crate::cpi::token_metadata_update_field;
+use crate::seeds_artwork_mint;
+use crate::seeds_config;
+use crate::seeds_game;
+use crate::seeds_treasury;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::GameState;
+use crate::ARTWORK_CANVAS_ROOT_FIELD;
+use crate::ARTWORK_TOKEN_NAME;
+use crate::ARTWORK_TOKEN_SYMBOL;
+use crate::ARTWORK_TOKEN_URI;
+use crate::ID;
+
+/// Mint the final artwork of a finalized game as a Token-2022 NFT.
+///
+/// The metadata includes the canvas merkle root and a uri for the rendered
+/// image of the canvas. A single token is minted to the treasury and the mint
+/// authority is removed so the supply is fixed.
+pub fn process_game_artwork_mint(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [authority_info, config_info, treasury_info, game_info, artwork_mint_info, treasury_artwork_token_account_info, associated_token_program_info, token_program_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let treasury_seeds_with_bump = seeds_treasury!(config.treasury_bump);
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let artwork_mint_seeds = seeds_artwork_mint!(game.game_index);
+	let artwork_mint_bump = artwork_mint_info.assert_canonical_bump(artwork_mint_seeds, &ID)?;
+
+	authority_info.assert_signer()?.assert_writable()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	treasury_info
+		.assert_owner(&system_program::ID)?
+		.assert_seeds_with_bump(treasury_seeds_with_bump, &ID)?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	artwork_mint_info.assert_empty()?.assert_writable()?;
+	treasury_artwork_token_account_info
+		.assert_empty()?
+		.assert_writable()?
+		.assert_associated_token_address(treasury_info.key, artwork_mint_info.key)?;
+	associated_token_program_info.assert_program(&spl_associated_token_account::ID)?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	if authority_info.key.ne(&config.authority) {
+		return Err(BitflipError::Unauthorized.into());
+	}
+
+	game.assert_err(|state| state.finalized(), BitflipError::GameNotFinalized)?;
+
+	let rent_sysvar = Rent::get()?;
+	let mint_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+		ExtensionType::MetadataPointer,
+	])?;
+
+	allocate_account_with_bump(
+		artwork_mint_info,
+		system_program_info,
+		authority_info,
+		mint_space,
+		token_program_info.key,
+		artwork_mint_seeds,
+		artwork_mint_bump,
+	)?;
+
+	msg!("artwork: initialize metadata pointer mint extension");
+	metadata_pointer_initialize(artwork_mint_info, treasury_info, token_program_info, &[])?;
+
+	msg!("artwork: initialize mint");
+	initialize_mint(artwork_mint_info, token_program_info, treasury_info, 0, &[])?;
+
+	msg!("artwork: initialize token metadata");
+	token_metadata_initialize(
+		artwork_mint_info,
+		treasury_info,
+		token_program_info,
+		format!("{ARTWORK_TOKEN_NAME} #{}", game.game_index),
+		ARTWORK_TOKEN_SYMBOL.into(),
+		format!("{ARTWORK_TOKEN_URI}/{}/canvas-image", game.game_index),
+		&[treasury_seeds_with_bump],
+	)?;
+
+	msg!("artwork: store canvas root");
+	token_metadata_update_field(
+		artwork_mint_info,
+		treasury_info,
+		token_program_info,
+		Field::Key(ARTWORK_CANVAS_ROOT_FIELD.into()),
+		game.canvas_root
+			.iter()
+			.map(|byte| format!("{byte:02x}"))
+			.collect(),
+		&[treasury_seeds_with_bump],
+	)?;
+
+	let extra_lamports = rent_sysvar
+		.minimum_balance(artwork_mint_info.data_len())
+		.checked_sub(artwork_mint_info.lamports())
+		.ok_or(ProgramError::ArithmeticOverflow)?;
+
+	if extra_lamports > 0 {
+		msg!("artwork: collect extra lamports");
+		artwork_mint_info.collect(extra_lamports, authority_info)?;
+	}
+
+	msg!("artwork: create treasury associated token account");
+	create_associated_token_account(
+		authority_info,
+		treasury_artwork_token_account_info,
+		treasury_info,
+		artwork_mint_info,
+		token_program_info,
+		system_program_info,
+		&[treasury_seeds_with_bump],
+	)?;
+
+	msg!("artwork: mint the artwork to the treasury");
+	mint_to(
+		artwork_mint_info,
+		treasury_artwork_token_account_info,
+		treasury_info,
+		token_program_info,
+		1,
+		&[treasury_seeds_with_bump],
+	)?;
+
+	msg!("artwork: remove the mint authority");
+	set_authority(
+		artwork_mint_info,
+		treasury_info,
+		token_program_info,
+		None,
+		AuthorityType::MintTokens,
+		&[treasury_seeds_with_bump],
+	)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameArtworkMint {}
+
+instruction!(BitflipInstruction, GameArtworkMint);
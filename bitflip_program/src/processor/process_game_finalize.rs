@@ -0,0 +1,308 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::get_canvas_leaf;
+use crate::get_canvas_root;
+use crate::seeds_game;
+use crate::seeds_game_canvas;
+use crate::seeds_section;
+use crate::seeds_team_pool;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameCanvasState;
+use crate::GameState;
+use crate::GameStatus;
+use crate::SectionState;
+use crate::TeamPoolState;
+use crate::BITFLIP_SECTION_LENGTH;
+use crate::ID;
+
+/// Hash the sections of an ended game into the canvas merkle tree.
+///
+/// The sections are passed after the `team_pool_info` in order, starting
+/// from the next section which has not been hashed. Sections which were never
+/// unlocked must still be passed as their empty PDA. Since each transaction
+/// can only include a limited number of accounts this is called multiple
+/// times. Once every section has been hashed the merkle root is stored in the
+/// [`GameState`]. This instruction is permissionless.
+///
+/// The bits of each unlocked section are tallied in the [`TeamPoolState`] of
+/// team games. The `team_pool_info` is always required but is empty until the
+/// first player joins a team.
+pub fn process_game_finalize(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [payer_info, game_info, game_canvas_info, system_program_info, team_pool_info, section_infos @ ..] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account_mut::<GameState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+
+	payer_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	let mut team_pool = if team_pool_info.data_is_empty() {
+		team_pool_info.assert_canonical_bump(seeds_team_pool!(game.game_index), &ID)?;
+		None
+	} else {
+		let team_pool = team_pool_info.as_account_mut::<TeamPoolState>(&ID)?;
+		let team_pool_seeds_with_bump = seeds_team_pool!(game.game_index, team_pool.bump);
+		team_pool_info
+			.assert_type::<TeamPoolState>(&ID)?
+			.assert_writable()?
+			.assert_seeds_with_bump(team_pool_seeds_with_bump, &ID)?;
+		Some(team_pool)
+	};
+
+	game.assert_err(
+		|state| !state.finalized(),
+		BitflipError::GameAlreadyFinalized,
+	)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| state.ended(current_time),
+		BitflipError::GameNotEnded,
+	)?;
+
+	if game_canvas_info.data_is_empty() {
+		let game_canvas_seeds = seeds_game_canvas!(game.game_index);
+		let game_canvas_bump = game_canvas_info.assert_canonical_bump(game_canvas_seeds, &ID)?;
+		game_canvas_info.assert_writable()?;
+
+		create_account_with_bump::<GameCanvasState>(
+			game_canvas_info,
+			system_program_info,
+			payer_info,
+			&ID,
+			game_canvas_seeds,
+			game_canvas_bump,
+		)?;
+
+		let game_canvas = game_canvas_info.as_account_mut::<GameCanvasState>(&ID)?;
+		game_canvas.init(game.game_index, game_canvas_bump);
+	}
+
+	let game_canvas = game_canvas_info.as_account_mut::<GameCanvasState>(&ID)?;
+	let game_canvas_seeds_with_bump = seeds_game_canvas!(game.game_index, game_canvas.bump);
+	game_canvas_info
+		.assert_type::<GameCanvasState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(game_canvas_seeds_with_bump, &ID)?;
+
+	for section_info in section_infos {
+		let section_index = game_canvas.leaf_count() as u8;
+		let leaf = if section_info.data_is_empty() {
+			section_info
+				.assert_canonical_bump(seeds_section!(game.game_index, section_index), &ID)?;
+			get_canvas_leaf(section_index, &[0.into(); BITFLIP_SECTION_LENGTH])
+		} else {
+			let section = section_info.as_account::<SectionState>(&ID)?;
+			let section_seeds_with_bump =
+				seeds_section!(game.game_index, section_index, section.bump);
+			section_info
+				.assert_type::<SectionState>(&ID)?
+				.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+
+			if let Some(team_pool) = &mut team_pool {
+				team_pool.tally(section)?;
+			}
+
+			get_canvas_leaf(section_index, &section.data)
+		};
+
+		game_canvas.push_leaf(leaf)?;
+	}
+
+	msg!("canvas leaves: {}", game_canvas.leaf_count());
+
+	if game_canvas.complete() {
+		game.canvas_root = get_canvas_root(&game_canvas.leaves);
+		game.status = GameStatus::Ended.into();
+		msg!("canvas finalized");
+	}
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameFinalize {}
+
+instruction!(BitflipInstruction, GameFinalize);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_game_canvas;
+	use crate::get_pda_team_pool;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_finalize(&accounts);
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_finalize(&accounts[..4]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn payer_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let payer_info = &mut accounts[0];
+		payer_info.is_signer = false;
+
+		let result = process_game_finalize(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[1];
+		game_info.is_writable = false;
+
+		let result = process_game_finalize(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn team_pool_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let team_pool_info = &mut accounts[4];
+		team_pool_info.key = leak(Pubkey::new_unique());
+
+		let result = process_game_finalize(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_not_be_finalized() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[1];
+		game_info.data.borrow_mut()[8..].copy_from_slice(create_game_state([1; 32]).to_bytes());
+
+		let result = process_game_finalize(&accounts);
+		check!(result.unwrap_err() == BitflipError::GameAlreadyFinalized.into());
+
+		Ok(())
+	}
+
+	fn create_game_state(canvas_root: [u8; 32]) -> GameState {
+		let game_bump = get_pda_game(0).1;
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, game_bump);
+		game.canvas_root = canvas_root;
+
+		game
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 5] {
+		let game_index = 0;
+		let payer_key = leak(Pubkey::new_unique());
+		let payer_lamports = leak(1_000_000_000);
+		let payer_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(&mut create_game_state([0; 32]).to_bytes().to_vec());
+			leak(data)
+		};
+		let game_canvas_key = leak(get_pda_game_canvas(game_index).0);
+		let game_canvas_lamports = leak(0);
+		let game_canvas_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+		let team_pool_key = leak(get_pda_team_pool(game_index).0);
+		let team_pool_lamports = leak(0);
+		let team_pool_data = leak(vec![]);
+
+		let payer_info = AccountInfo::new(
+			payer_key,
+			true,
+			true,
+			payer_lamports,
+			payer_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			true,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let game_canvas_info = AccountInfo::new(
+			game_canvas_key,
+			false,
+			true,
+			game_canvas_lamports,
+			game_canvas_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		let team_pool_info = AccountInfo::new(
+			team_pool_key,
+			false,
+			true,
+			team_pool_lamports,
+			team_pool_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+
+		[
+			payer_info,
+			game_info,
+			game_canvas_info,
+			system_program_info,
+			team_pool_info,
+		]
+	}
+}
@@ -1,3 +1,4 @@
+use spl_pod::primitives::PodU64;
 use steel::*;
 use sysvar::rent::Rent;
 
@@ -6,13 +7,20 @@ use crate::seeds_game;
 use crate::BitflipError;
 use crate::BitflipInstruction;
 use crate::ConfigState;
+use crate::GameFlags;
+use crate::GameParameter;
 use crate::GameState;
 use crate::ID;
 use crate::SEED_GAME;
 use crate::SEED_PREFIX;
 use crate::TRANSACTION_FEE;
 
-pub fn process_game_initialize(accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_game_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = GameInitialize::try_from_bytes(data)?;
+	let flags = args.flags()?;
+
+	// load accounts
 	let [authority_info, temp_signer_info, funded_signer_info, config_info, game_info, system_program_info] =
 		accounts
 	else {
@@ -43,6 +51,10 @@ pub fn process_game_initialize(accounts: &[AccountInfo]) -> ProgramResult {
 		return Err(BitflipError::Unauthorized.into());
 	}
 
+	if flags.contains(GameFlags::INHERIT) && config.game_index == 0 {
+		return Err(BitflipError::InheritInvalid.into());
+	}
+
 	// create the onchain account
 	create_account_with_bump::<GameState>(
 		game_info,
@@ -60,6 +72,8 @@ pub fn process_game_initialize(accounts: &[AccountInfo]) -> ProgramResult {
 		config.game_index,
 		game_bump,
 	);
+	game.flags = flags.bits();
+	game.update_parameter(GameParameter::StepLamports, args.step_lamports())?;
 
 	// store lamports in the refresh signer
 	let rent_sysvar = Rent::get()?;
@@ -74,7 +88,32 @@ pub fn process_game_initialize(accounts: &[AccountInfo]) -> ProgramResult {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct GameInitialize {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GameInitialize {
+	/// The [`GameFlags`] to enable for the game.
+	pub flags: u8,
+	/// The price of stepping a section forward by one generation. It can be
+	/// updated later with [`GameParameter::StepLamports`].
+	pub step_lamports: PodU64,
+}
+
+impl GameInitialize {
+	pub fn new(flags: GameFlags, step_lamports: u64) -> Self {
+		Self {
+			flags: flags.bits(),
+			step_lamports: step_lamports.into(),
+		}
+	}
+
+	pub fn step_lamports(&self) -> u64 {
+		self.step_lamports.into()
+	}
+
+	pub fn flags(&self) -> Result<GameFlags, ProgramError> {
+		GameFlags::from_bits(self.flags).ok_or(ProgramError::InvalidInstructionData)
+	}
+}
 
 instruction!(BitflipInstruction, GameInitialize);
 
@@ -90,23 +129,42 @@ mod tests {
 	use crate::get_pda_config;
 	use crate::get_pda_game;
 	use crate::leak;
+	use crate::BASE_LAMPORTS_PER_STEP;
 
 	#[test_log::test]
 	fn should_have_enough_accounts() -> anyhow::Result<()> {
 		let accounts = create_account_infos();
-		let result = process_game_initialize(&accounts[..5]);
+		let result = process_game_initialize(&accounts[..5], &create_data(0));
 		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
 
 		Ok(())
 	}
 
+	#[test_log::test]
+	fn should_have_valid_flags() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_initialize(&accounts, &create_data(u8::MAX));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn first_game_should_not_inherit() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_initialize(&accounts, &create_data(GameFlags::INHERIT.bits()));
+		check!(result.unwrap_err() == BitflipError::InheritInvalid.into());
+
+		Ok(())
+	}
+
 	#[test_log::test]
 	fn authority_should_be_signer() -> anyhow::Result<()> {
 		let mut accounts = create_account_infos();
 		let authority_info = &mut accounts[0];
 		authority_info.is_signer = false;
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
 
 		Ok(())
@@ -118,7 +176,7 @@ mod tests {
 		let authority_info = &mut accounts[0];
 		authority_info.is_writable = false;
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
 
 		Ok(())
@@ -130,7 +188,7 @@ mod tests {
 		let authority_info = &mut accounts[0];
 		authority_info.key = leak(Pubkey::new_unique());
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::Custom(BitflipError::Unauthorized.into()));
 
 		Ok(())
@@ -142,7 +200,7 @@ mod tests {
 		let temp_signer = &mut accounts[1];
 		temp_signer.is_signer = false;
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
 
 		Ok(())
@@ -154,7 +212,7 @@ mod tests {
 		let funded_signer_info = &mut accounts[2];
 		funded_signer_info.is_signer = false;
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
 
 		Ok(())
@@ -166,7 +224,7 @@ mod tests {
 		let funded_signer_info = &mut accounts[2];
 		funded_signer_info.is_writable = false;
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
 
 		Ok(())
@@ -178,7 +236,7 @@ mod tests {
 		let config_info = &mut accounts[3];
 		config_info.key = leak(Pubkey::new_unique());
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
 
 		Ok(())
@@ -190,7 +248,7 @@ mod tests {
 		let config_info = &mut accounts[3];
 		config_info.data = Rc::new(RefCell::new(leak(vec![1u8; 8])));
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::InvalidAccountData);
 
 		Ok(())
@@ -202,7 +260,7 @@ mod tests {
 		let config_info = &mut accounts[3];
 		config_info.owner = leak(Pubkey::new_unique());
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::InvalidAccountOwner);
 
 		Ok(())
@@ -214,7 +272,7 @@ mod tests {
 		let game_info = &mut accounts[4];
 		game_info.key = leak(Pubkey::new_unique());
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
 
 		Ok(())
@@ -226,7 +284,7 @@ mod tests {
 		let game_info = &mut accounts[4];
 		game_info.data = Rc::new(RefCell::new(leak(vec![1, 0, 0, 0, 0, 0, 0, 0])));
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::AccountAlreadyInitialized);
 
 		Ok(())
@@ -238,12 +296,21 @@ mod tests {
 		let game_info = &mut accounts[4];
 		game_info.is_writable = false;
 
-		let result = process_game_initialize(&accounts);
+		let result = process_game_initialize(&accounts, &create_data(0));
 		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
 
 		Ok(())
 	}
 
+	fn create_data(flags: u8) -> Vec<u8> {
+		let args = GameInitialize {
+			flags,
+			step_lamports: BASE_LAMPORTS_PER_STEP.into(),
+		};
+
+		args.to_bytes()[1..].to_vec()
+	}
+
 	fn create_account_infos<'info>() -> [AccountInfo<'info>; 6] {
 		let authority = Pubkey::new_unique();
 		let authority_key = leak(authority);
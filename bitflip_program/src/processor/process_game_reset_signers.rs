@@ -76,6 +76,7 @@ pub fn process_game_reset_signers(accounts: &[AccountInfo]) -> ProgramResult {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameResetSigners {}
 
 instruction!(BitflipInstruction, GameResetSigners);
@@ -0,0 +1,255 @@
+use spl_pod::primitives::PodI64;
+use steel::*;
+
+use crate::seeds_config;
+use crate::seeds_game;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::GameState;
+use crate::GameStatus;
+use crate::ID;
+
+/// Schedule a game to start at a future `start_time` as the config authority.
+///
+/// The game is treated as running once the start time has passed, so unlike
+/// [`crate::GameStart`] no transaction is needed at launch. A scheduled game
+/// can be rescheduled until it has started.
+pub fn process_game_schedule(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = GameSchedule::try_from_bytes(data)?;
+
+	// load accounts
+	let [authority_info, config_info, game_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let game = game_info.as_account_mut::<GameState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+
+	authority_info.assert_signer()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+
+	config.assert_err(
+		|state| state.authority.eq(authority_info.key),
+		BitflipError::Unauthorized,
+	)?;
+	game.assert_err(
+		|state| state.section_index == 0 && state.status() != GameStatus::Ended,
+		BitflipError::GameAlreadyStarted,
+	)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| !state.started(current_time),
+		BitflipError::GameAlreadyStarted,
+	)?;
+
+	if args.start_time() <= current_time {
+		return Err(BitflipError::GameStartTimeInvalid.into());
+	}
+
+	game.schedule(args.start_time());
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GameSchedule {
+	/// The unix timestamp at which the game starts running.
+	pub start_time: PodI64,
+}
+
+impl GameSchedule {
+	pub fn new(start_time: i64) -> Self {
+		Self {
+			start_time: start_time.into(),
+		}
+	}
+
+	pub fn start_time(&self) -> i64 {
+		self.start_time.into()
+	}
+}
+
+instruction!(BitflipInstruction, GameSchedule);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_game;
+	use crate::get_pda_treasury;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_schedule(&accounts, &create_args(100));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_schedule(&accounts[..2], &create_args(100));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.is_signer = false;
+
+		let result = process_game_schedule(&accounts, &create_args(100));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_match_config() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.key = leak(Pubkey::new_unique());
+
+		let result = process_game_schedule(&accounts, &create_args(100));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[2];
+		game_info.is_writable = false;
+
+		let result = process_game_schedule(&accounts, &create_args(100));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[2];
+		game_info.key = leak(Pubkey::new_unique());
+
+		let result = process_game_schedule(&accounts, &create_args(100));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_not_have_ended() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let game = accounts[2].as_account_mut::<GameState>(&ID)?;
+		game.status = GameStatus::Ended.into();
+
+		let result = process_game_schedule(&accounts, &create_args(100));
+		check!(result.unwrap_err() == BitflipError::GameAlreadyStarted.into());
+
+		Ok(())
+	}
+
+	fn create_args(start_time: i64) -> Vec<u8> {
+		bytemuck::bytes_of(&GameSchedule::new(start_time)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 3] {
+		let game_index = 0;
+		let authority = Pubkey::new_unique();
+		let authority_key = leak(authority);
+		let authority_lamports = leak(0);
+		let authority_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					authority,
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let game = GameState::new(
+				Pubkey::new_unique(),
+				Pubkey::new_unique(),
+				game_index,
+				game_bump,
+			);
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(&mut game.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let authority_info = AccountInfo::new(
+			authority_key,
+			true,
+			false,
+			authority_lamports,
+			authority_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			true,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[authority_info, config_info, game_info]
+	}
+}
@@ -50,7 +50,7 @@ pub fn process_game_start(accounts: &[AccountInfo]) -> Result<(), ProgramError>
 
 	let current_timestamp = Clock::get()?.unix_timestamp;
 	game.assert_err(
-		|game| !game.running(current_timestamp),
+		|game| !game.running(current_timestamp) && !game.scheduled(current_timestamp),
 		BitflipError::GameAlreadyStarted,
 	)?;
 
@@ -61,6 +61,7 @@ pub fn process_game_start(accounts: &[AccountInfo]) -> Result<(), ProgramError>
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameStart {}
 
 instruction!(BitflipInstruction, GameStart);
@@ -0,0 +1,260 @@
+use spl_pod::primitives::PodU64;
+use steel::*;
+
+use crate::seeds_config;
+use crate::seeds_game;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::GameParameter;
+use crate::GameState;
+use crate::ID;
+
+/// Update a [`GameParameter`] of a game as the config authority.
+///
+/// Passed governance proposals apply their change through the same
+/// [`GameState::update_parameter`].
+pub fn process_game_update_parameter(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = GameUpdateParameter::try_from_bytes(data)?;
+	let parameter = args.parameter()?;
+
+	// load accounts
+	let [authority_info, config_info, game_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let game = game_info.as_account_mut::<GameState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+
+	authority_info.assert_signer()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+
+	config.assert_err(
+		|state| state.authority.eq(authority_info.key),
+		BitflipError::Unauthorized,
+	)?;
+
+	game.update_parameter(parameter, args.value())?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GameUpdateParameter {
+	/// The new value of the parameter.
+	pub value: PodU64,
+	/// The [`GameParameter`] being updated.
+	pub parameter: u8,
+}
+
+impl GameUpdateParameter {
+	pub fn new(parameter: GameParameter, value: u64) -> Self {
+		Self {
+			value: value.into(),
+			parameter: parameter.into(),
+		}
+	}
+
+	pub fn value(&self) -> u64 {
+		self.value.into()
+	}
+
+	pub fn parameter(&self) -> Result<GameParameter, ProgramError> {
+		GameParameter::try_from(self.parameter).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, GameUpdateParameter);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_game;
+	use crate::get_pda_treasury;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		process_game_update_parameter(&accounts, &create_args(GameParameter::Duration, 3600))?;
+
+		let game = accounts[2].as_account::<GameState>(&ID)?;
+		check!(game.duration() == 3600);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_parameter() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result =
+			process_game_update_parameter(&accounts, &create_args(GameParameter::MinLamports, 0));
+		check!(result.unwrap_err() == BitflipError::GameParameterInvalid.into());
+
+		let mut args = GameUpdateParameter::new(GameParameter::Duration, 3600);
+		args.parameter = u8::MAX;
+		let result = process_game_update_parameter(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_game_update_parameter(
+			&accounts[..2],
+			&create_args(GameParameter::Duration, 3600),
+		);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.is_signer = false;
+
+		let result =
+			process_game_update_parameter(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_match_config() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.key = leak(Pubkey::new_unique());
+
+		let result =
+			process_game_update_parameter(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[2];
+		game_info.is_writable = false;
+
+		let result =
+			process_game_update_parameter(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[2];
+		game_info.key = leak(Pubkey::new_unique());
+
+		let result =
+			process_game_update_parameter(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(parameter: GameParameter, value: u64) -> Vec<u8> {
+		bytemuck::bytes_of(&GameUpdateParameter::new(parameter, value)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 3] {
+		let game_index = 0;
+		let authority = Pubkey::new_unique();
+		let authority_key = leak(authority);
+		let authority_lamports = leak(0);
+		let authority_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					authority,
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let game = GameState::new(
+				Pubkey::new_unique(),
+				Pubkey::new_unique(),
+				game_index,
+				game_bump,
+			);
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(&mut game.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let authority_info = AccountInfo::new(
+			authority_key,
+			true,
+			false,
+			authority_lamports,
+			authority_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			true,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[authority_info, config_info, game_info]
+	}
+}
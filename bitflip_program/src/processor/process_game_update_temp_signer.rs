@@ -38,6 +38,7 @@ pub fn process_game_update_temp_signer(accounts: &[AccountInfo]) -> ProgramResul
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameUpdateTempSigner {}
 
 instruction!(BitflipInstruction, GameUpdateTempSigner);
@@ -0,0 +1,239 @@
+use steel::*;
+
+use crate::cpi::create_associated_token_account_idempotent;
+use crate::seeds_config;
+use crate::seeds_mint;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::TokenMember;
+use crate::ID;
+
+/// Create the associated `$BIT` token account of a player.
+///
+/// `FlipBit` pays out tokens to this account but doesn't create it so that
+/// the associated token program isn't invoked on every flip. Players create
+/// it once before their first flip. The instruction is idempotent and can be
+/// safely included in every transaction which might be the first flip of a
+/// player.
+pub fn process_player_token_account_initialize(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [player_info, player_bit_token_account_info, config_info, mint_bit_info, associated_token_program_info, token_program_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let member = TokenMember::Bit;
+	let mint_seeds_with_bump = seeds_mint!(member, member.bump(config));
+
+	player_info.assert_signer()?.assert_writable()?;
+	player_bit_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(player_info.key, mint_bit_info.key)?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	mint_bit_info.assert_seeds_with_bump(mint_seeds_with_bump, &ID)?;
+	associated_token_program_info.assert_program(&spl_associated_token_account::ID)?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	create_associated_token_account_idempotent(
+		player_info,
+		player_bit_token_account_info,
+		player_info,
+		mint_bit_info,
+		token_program_info,
+		system_program_info,
+		&[],
+	)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerTokenAccountInitialize {}
+
+instruction!(BitflipInstruction, PlayerTokenAccountInitialize);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::bpf_loader_upgradeable;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_mint;
+	use crate::get_player_token_account;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_player_token_account_initialize(&accounts[..6]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_player_token_account_initialize(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_bit_token_account_should_belong_to_player() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let player_info = &mut accounts[0];
+		player_info.key = leak(Pubkey::new_unique());
+
+		let result = process_player_token_account_initialize(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn mint_bit_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let mint_bit_info = &mut accounts[3];
+		mint_bit_info.key = leak(Pubkey::new_unique());
+
+		let result = process_player_token_account_initialize(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 7] {
+		let player_key = leak(Pubkey::new_unique());
+		let player_lamports = leak(1_000_000_000);
+		let player_data = leak(vec![]);
+		let player_bit_token_account_key =
+			leak(get_player_token_account(player_key, TokenMember::Bit));
+		let player_bit_token_account_lamports = leak(0);
+		let player_bit_token_account_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					Pubkey::new_unique(),
+					get_pda_config().1,
+					u8::MAX,
+					get_pda_mint(TokenMember::Bit).1,
+					u8::MAX,
+					u8::MAX,
+					u8::MAX,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let mint_bit_key = leak(get_pda_mint(TokenMember::Bit).0);
+		let mint_bit_lamports = leak(0);
+		let mint_bit_data = leak(vec![]);
+		let associated_token_program_lamports = leak(0);
+		let associated_token_program_data = leak(vec![]);
+		let token_program_lamports = leak(0);
+		let token_program_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let player_bit_token_account_info = AccountInfo::new(
+			player_bit_token_account_key,
+			false,
+			true,
+			player_bit_token_account_lamports,
+			player_bit_token_account_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let mint_bit_info = AccountInfo::new(
+			mint_bit_key,
+			false,
+			false,
+			mint_bit_lamports,
+			mint_bit_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let associated_token_program_info = AccountInfo::new(
+			&spl_associated_token_account::ID,
+			false,
+			false,
+			associated_token_program_lamports,
+			associated_token_program_data,
+			&bpf_loader_upgradeable::ID,
+			true,
+			u64::MAX,
+		);
+		let token_program_info = AccountInfo::new(
+			&spl_token_2022::ID,
+			false,
+			false,
+			token_program_lamports,
+			token_program_data,
+			&bpf_loader_upgradeable::ID,
+			true,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			player_info,
+			player_bit_token_account_info,
+			config_info,
+			mint_bit_info,
+			associated_token_program_info,
+			token_program_info,
+			system_program_info,
+		]
+	}
+}
@@ -0,0 +1,250 @@
+use spl_pod::primitives::PodU16;
+use spl_pod::primitives::PodU64;
+use steel::*;
+
+use crate::seeds_game;
+use crate::seeds_proposal;
+use crate::BitflipInstruction;
+use crate::GameParameter;
+use crate::GameState;
+use crate::ProposalState;
+use crate::GOVERNANCE_VOTING_PERIOD;
+use crate::ID;
+
+/// Create a governance proposal to change a [`GameParameter`] of a game.
+///
+/// The proposed value must be valid for the current state of the game. Holders
+/// can vote on the proposal until the end of the
+/// [`GOVERNANCE_VOTING_PERIOD`].
+pub fn process_proposal_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = ProposalCreate::try_from_bytes(data)?;
+	let parameter = args.parameter()?;
+
+	// load accounts
+	let [proposer_info, game_info, proposal_info, system_program_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let proposal_seeds = seeds_proposal!(game.game_index, proposer_info.key, args.proposal_index());
+	let proposal_bump = proposal_info.assert_canonical_bump(proposal_seeds, &ID)?;
+
+	proposer_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	proposal_info.assert_empty()?.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	// the change must be valid when the proposal is created.
+	let mut updated = *game;
+	updated.update_parameter(parameter, args.value())?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+
+	create_account_with_bump::<ProposalState>(
+		proposal_info,
+		system_program_info,
+		proposer_info,
+		&ID,
+		proposal_seeds,
+		proposal_bump,
+	)?;
+
+	let proposal = proposal_info.as_account_mut::<ProposalState>(&ID)?;
+	*proposal = ProposalState::new(
+		*proposer_info.key,
+		game.game_index,
+		args.proposal_index(),
+		parameter,
+		args.value(),
+		current_time.saturating_add(GOVERNANCE_VOTING_PERIOD),
+		proposal_bump,
+	);
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ProposalCreate {
+	/// The proposed value of the parameter.
+	pub value: PodU64,
+	/// The index of the proposal chosen by the proposer.
+	pub proposal_index: PodU16,
+	/// The [`GameParameter`] being changed.
+	pub parameter: u8,
+}
+
+impl ProposalCreate {
+	pub fn new(proposal_index: u16, parameter: GameParameter, value: u64) -> Self {
+		Self {
+			value: value.into(),
+			proposal_index: proposal_index.into(),
+			parameter: parameter.into(),
+		}
+	}
+
+	pub fn value(&self) -> u64 {
+		self.value.into()
+	}
+
+	pub fn proposal_index(&self) -> u16 {
+		self.proposal_index.into()
+	}
+
+	pub fn parameter(&self) -> Result<GameParameter, ProgramError> {
+		GameParameter::try_from(self.parameter).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, ProposalCreate);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_proposal;
+	use crate::leak;
+	use crate::BitflipError;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result =
+			process_proposal_create(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_value() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_proposal_create(&accounts, &create_args(GameParameter::Duration, 0));
+		check!(result.unwrap_err() == BitflipError::GameParameterInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result =
+			process_proposal_create(&accounts[..3], &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn proposer_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let proposer_info = &mut accounts[0];
+		proposer_info.is_signer = false;
+
+		let result =
+			process_proposal_create(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn proposal_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let proposal_info = &mut accounts[2];
+		proposal_info.key = leak(Pubkey::new_unique());
+
+		let result =
+			process_proposal_create(&accounts, &create_args(GameParameter::Duration, 3600));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(parameter: GameParameter, value: u64) -> Vec<u8> {
+		bytemuck::bytes_of(&ProposalCreate::new(0, parameter, value)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 4] {
+		let game_index = 0;
+		let proposer = Pubkey::new_unique();
+		let proposer_key = leak(proposer);
+		let proposer_lamports = leak(1_000_000_000);
+		let proposer_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(
+					Pubkey::new_unique(),
+					Pubkey::new_unique(),
+					game_index,
+					game_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let proposal_key = leak(get_pda_proposal(game_index, &proposer, 0).0);
+		let proposal_lamports = leak(0);
+		let proposal_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let proposer_info = AccountInfo::new(
+			proposer_key,
+			true,
+			true,
+			proposer_lamports,
+			proposer_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let proposal_info = AccountInfo::new(
+			proposal_key,
+			false,
+			true,
+			proposal_lamports,
+			proposal_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[proposer_info, game_info, proposal_info, system_program_info]
+	}
+}
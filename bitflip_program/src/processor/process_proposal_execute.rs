@@ -0,0 +1,171 @@
+use steel::*;
+
+use crate::seeds_game;
+use crate::seeds_proposal;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::ProposalState;
+use crate::ID;
+
+/// Close the voting on a governance proposal once the voting period has ended.
+///
+/// Anyone can execute a proposal. When the votes reached the quorum and a
+/// majority approved, the parameter of the game is updated the same way as
+/// [`GameUpdateParameter`](crate::GameUpdateParameter). Otherwise the proposal
+/// is rejected. A passed change which is invalid for the game is recorded as
+/// [`ProposalStatus::Failed`](crate::ProposalStatus::Failed) without failing
+/// the instruction.
+pub fn process_proposal_execute(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [game_info, proposal_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account_mut::<GameState>(&ID)?;
+	let proposal = proposal_info.as_account_mut::<ProposalState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(proposal.game_index, game.bump);
+	let proposal_seeds_with_bump = seeds_proposal!(
+		proposal.game_index,
+		proposal.proposer,
+		proposal.proposal_index(),
+		proposal.bump
+	);
+
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	proposal_info
+		.assert_type::<ProposalState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(proposal_seeds_with_bump, &ID)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	proposal.execute(game, current_time)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProposalExecute {}
+
+instruction!(BitflipInstruction, ProposalExecute);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_proposal;
+	use crate::leak;
+	use crate::GameParameter;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos(0);
+		let result = process_proposal_execute(&accounts);
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos(0);
+		let result = process_proposal_execute(&accounts[..1]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_match_proposal() -> anyhow::Result<()> {
+		let accounts = create_account_infos(1);
+		let result = process_proposal_execute(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn proposal_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(0);
+		let proposal_info = &mut accounts[1];
+		proposal_info.is_writable = false;
+
+		let result = process_proposal_execute(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	fn create_account_infos<'info>(game_index: u8) -> [AccountInfo<'info>; 2] {
+		let proposal_game_index = 0;
+		let proposer = Pubkey::new_unique();
+		let proposal = get_pda_proposal(proposal_game_index, &proposer, 0);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(
+					Pubkey::new_unique(),
+					Pubkey::new_unique(),
+					game_index,
+					game_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let proposal_key = leak(proposal.0);
+		let proposal_lamports = leak(0);
+		let proposal_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ProposalState::discriminator();
+			data.append(
+				&mut ProposalState::new(
+					proposer,
+					proposal_game_index,
+					0,
+					GameParameter::Duration,
+					3600,
+					0,
+					proposal.1,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			true,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let proposal_info = AccountInfo::new(
+			proposal_key,
+			false,
+			true,
+			proposal_lamports,
+			proposal_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[game_info, proposal_info]
+	}
+}
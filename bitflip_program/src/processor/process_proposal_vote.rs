@@ -0,0 +1,468 @@
+use solana_program::msg;
+use spl_pod::primitives::PodU64;
+use steel::*;
+
+use crate::cpi::create_associated_token_account_idempotent;
+use crate::cpi::transfer_checked;
+use crate::seeds_config;
+use crate::seeds_mint;
+use crate::seeds_proposal;
+use crate::seeds_vote;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::ProposalState;
+use crate::TokenMember;
+use crate::VoteState;
+use crate::ID;
+use crate::TOKEN_DECIMALS;
+
+/// Vote on a governance proposal by escrowing tokens of a [`TokenMember`].
+///
+/// Each token is weighted by the number of bits it is worth. A holder can add
+/// to their vote while the proposal is open but can't change sides. The tokens
+/// are returned with [`VoteWithdraw`](crate::VoteWithdraw) once the voting
+/// period has ended.
+pub fn process_proposal_vote(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = ProposalVote::try_from_bytes(data)?;
+	let member = args.member()?;
+	args.validate()?;
+
+	// load accounts
+	let [voter_info, voter_token_account_info, config_info, mint_info, proposal_info, vote_info, vote_token_account_info, associated_token_program_info, token_program_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let proposal = proposal_info.as_account_mut::<ProposalState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let mint_seeds_with_bump = seeds_mint!(member, member.bump(config));
+	let proposal_seeds_with_bump = seeds_proposal!(
+		proposal.game_index,
+		proposal.proposer,
+		proposal.proposal_index(),
+		proposal.bump
+	);
+
+	voter_info.assert_signer()?.assert_writable()?;
+	voter_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(voter_info.key, mint_info.key)?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	mint_info.assert_seeds_with_bump(mint_seeds_with_bump, &ID)?;
+	proposal_info
+		.assert_type::<ProposalState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(proposal_seeds_with_bump, &ID)?;
+	vote_info.assert_writable()?;
+	vote_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(vote_info.key, mint_info.key)?;
+	associated_token_program_info.assert_program(&spl_associated_token_account::ID)?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	if vote_info.data_is_empty() {
+		let vote_seeds = seeds_vote!(proposal_info.key, voter_info.key);
+		let vote_bump = vote_info.assert_canonical_bump(vote_seeds, &ID)?;
+
+		create_account_with_bump::<VoteState>(
+			vote_info,
+			system_program_info,
+			voter_info,
+			&ID,
+			vote_seeds,
+			vote_bump,
+		)?;
+
+		let vote = vote_info.as_account_mut::<VoteState>(&ID)?;
+		*vote = VoteState::new(
+			*voter_info.key,
+			*proposal_info.key,
+			args.approve(),
+			vote_bump,
+		);
+	}
+
+	let vote = vote_info.as_account_mut::<VoteState>(&ID)?;
+	let vote_seeds_with_bump = seeds_vote!(proposal_info.key, voter_info.key, vote.bump);
+	vote_info
+		.assert_type::<VoteState>(&ID)?
+		.assert_seeds_with_bump(vote_seeds_with_bump, &ID)?;
+
+	vote.assert_err(
+		|state| state.approve() == args.approve(),
+		BitflipError::VoteInvalid,
+	)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	let weight = vote.deposit(member, args.amount())?;
+	proposal.vote(args.approve(), weight, current_time)?;
+
+	create_associated_token_account_idempotent(
+		voter_info,
+		vote_token_account_info,
+		vote_info,
+		mint_info,
+		token_program_info,
+		system_program_info,
+		&[],
+	)?;
+
+	msg!("escrowing tokens for the vote: {}", args.amount());
+	transfer_checked(
+		voter_token_account_info,
+		mint_info,
+		vote_token_account_info,
+		voter_info,
+		token_program_info,
+		args.amount(),
+		TOKEN_DECIMALS,
+		&[],
+	)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ProposalVote {
+	/// The number of tokens to escrow.
+	pub amount: PodU64,
+	/// The [`TokenMember`] being escrowed.
+	pub member: u8,
+	/// Whether the vote approves the proposal: `0` or `1`.
+	pub approve: u8,
+}
+
+impl ProposalVote {
+	pub fn new(member: TokenMember, amount: u64, approve: bool) -> Self {
+		Self {
+			amount: amount.into(),
+			member: member.into(),
+			approve: approve.into(),
+		}
+	}
+
+	pub fn amount(&self) -> u64 {
+		self.amount.into()
+	}
+
+	pub fn approve(&self) -> bool {
+		self.approve == 1
+	}
+
+	pub fn member(&self) -> Result<TokenMember, ProgramError> {
+		TokenMember::try_from(self.member).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+
+	pub fn validate(&self) -> ProgramResult {
+		if self.amount() == 0 || self.approve > 1 {
+			return Err(ProgramError::InvalidInstructionData);
+		}
+
+		Ok(())
+	}
+}
+
+instruction!(BitflipInstruction, ProposalVote);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_mint;
+	use crate::get_pda_proposal;
+	use crate::get_pda_treasury;
+	use crate::get_pda_vote;
+	use crate::get_player_token_account;
+	use crate::get_vote_token_account;
+	use crate::leak;
+	use crate::GameParameter;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_proposal_vote(&accounts, &create_args(TokenMember::Bit, 10, true));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_args() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_proposal_vote(&accounts, &create_args(TokenMember::Bit, 0, true));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		let mut args = ProposalVote::new(TokenMember::Bit, 10, true);
+		args.member = u8::MAX;
+		let result = process_proposal_vote(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		let mut args = ProposalVote::new(TokenMember::Bit, 10, true);
+		args.approve = 2;
+		let result = process_proposal_vote(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_not_change_sides() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_proposal_vote(&accounts, &create_args(TokenMember::Bit, 10, false));
+		check!(result.unwrap_err() == BitflipError::VoteInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result =
+			process_proposal_vote(&accounts[..9], &create_args(TokenMember::Bit, 10, true));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn voter_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let voter_info = &mut accounts[0];
+		voter_info.is_signer = false;
+
+		let result = process_proposal_vote(&accounts, &create_args(TokenMember::Bit, 10, true));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn mint_should_match_member() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_proposal_vote(&accounts, &create_args(TokenMember::Kibibit, 10, true));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn vote_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let vote_info = &mut accounts[5];
+		vote_info.key = leak(Pubkey::new_unique());
+
+		let result = process_proposal_vote(&accounts, &create_args(TokenMember::Bit, 10, true));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(member: TokenMember, amount: u64, approve: bool) -> Vec<u8> {
+		bytemuck::bytes_of(&ProposalVote::new(member, amount, approve)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 10] {
+		let game_index = 0;
+		let member = TokenMember::Bit;
+		let voter = Pubkey::new_unique();
+		let proposer = Pubkey::new_unique();
+		let proposal = get_pda_proposal(game_index, &proposer, 0);
+		let voter_key = leak(voter);
+		let voter_lamports = leak(1_000_000_000);
+		let voter_data = leak(vec![]);
+		let voter_token_account_key = leak(get_player_token_account(&voter, member));
+		let voter_token_account_lamports = leak(0);
+		let voter_token_account_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					Pubkey::new_unique(),
+					get_pda_config().1,
+					get_pda_treasury().1,
+					get_pda_mint(TokenMember::Bit).1,
+					get_pda_mint(TokenMember::Kibibit).1,
+					get_pda_mint(TokenMember::Mebibit).1,
+					get_pda_mint(TokenMember::Gibibit).1,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let mint_key = leak(get_pda_mint(member).0);
+		let mint_lamports = leak(0);
+		let mint_data = leak(vec![]);
+		let proposal_key = leak(proposal.0);
+		let proposal_lamports = leak(0);
+		let proposal_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ProposalState::discriminator();
+			data.append(
+				&mut ProposalState::new(
+					proposer,
+					game_index,
+					0,
+					GameParameter::Duration,
+					3600,
+					i64::MAX,
+					proposal.1,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let vote_key = leak(get_pda_vote(&proposal.0, &voter).0);
+		let vote_lamports = leak(0);
+		let vote_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = VoteState::discriminator();
+			data.append(
+				&mut VoteState::new(voter, proposal.0, true, get_pda_vote(&proposal.0, &voter).1)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let vote_token_account_key = leak(get_vote_token_account(&proposal.0, &voter, member));
+		let vote_token_account_lamports = leak(0);
+		let vote_token_account_data = leak(vec![]);
+		let associated_token_program_lamports = leak(0);
+		let associated_token_program_data = leak(vec![]);
+		let token_program_lamports = leak(0);
+		let token_program_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let voter_info = AccountInfo::new(
+			voter_key,
+			true,
+			true,
+			voter_lamports,
+			voter_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let voter_token_account_info = AccountInfo::new(
+			voter_token_account_key,
+			false,
+			true,
+			voter_token_account_lamports,
+			voter_token_account_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let mint_info = AccountInfo::new(
+			mint_key,
+			false,
+			false,
+			mint_lamports,
+			mint_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let proposal_info = AccountInfo::new(
+			proposal_key,
+			false,
+			true,
+			proposal_lamports,
+			proposal_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let vote_info = AccountInfo::new(
+			vote_key,
+			false,
+			true,
+			vote_lamports,
+			vote_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let vote_token_account_info = AccountInfo::new(
+			vote_token_account_key,
+			false,
+			true,
+			vote_token_account_lamports,
+			vote_token_account_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let associated_token_program_info = AccountInfo::new(
+			&spl_associated_token_account::ID,
+			false,
+			false,
+			associated_token_program_lamports,
+			associated_token_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+		let token_program_info = AccountInfo::new(
+			&spl_token_2022::ID,
+			false,
+			false,
+			token_program_lamports,
+			token_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			voter_info,
+			voter_token_account_info,
+			config_info,
+			mint_info,
+			proposal_info,
+			vote_info,
+			vote_token_account_info,
+			associated_token_program_info,
+			token_program_info,
+			system_program_info,
+		]
+	}
+}
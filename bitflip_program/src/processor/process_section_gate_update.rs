@@ -0,0 +1,362 @@
+use spl_pod::primitives::PodU64;
+use steel::*;
+
+use crate::seeds_config;
+use crate::seeds_game;
+use crate::seeds_section;
+use crate::seeds_section_metadata;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::GameState;
+use crate::SectionMetadataState;
+use crate::SectionState;
+use crate::TokenMember;
+use crate::ID;
+
+/// Gate the flips of a section behind a minimum balance of a [`TokenMember`].
+///
+/// Either the owner of the section or the config authority can update the
+/// gate. The metadata account is created if it doesn't exist yet.
+pub fn process_section_gate_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionGateUpdate::try_from_bytes(data)?;
+	let member = args.member()?;
+
+	// load accounts
+	let [signer_info, config_info, game_info, section_info, section_metadata_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let section = section_info.as_account::<SectionState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_seeds_with_bump = seeds_section!(game.game_index, args.section_index, section.bump);
+
+	signer_info.assert_signer()?.assert_writable()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_info
+		.assert_type::<SectionState>(&ID)?
+		.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+	section_metadata_info.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	if signer_info.key.ne(&section.owner) && signer_info.key.ne(&config.authority) {
+		return Err(BitflipError::Unauthorized.into());
+	}
+
+	if section_metadata_info.data_is_empty() {
+		let section_metadata_seeds = seeds_section_metadata!(game.game_index, args.section_index);
+		let section_metadata_bump =
+			section_metadata_info.assert_canonical_bump(section_metadata_seeds, &ID)?;
+
+		create_account_with_bump::<SectionMetadataState>(
+			section_metadata_info,
+			system_program_info,
+			signer_info,
+			&ID,
+			section_metadata_seeds,
+			section_metadata_bump,
+		)?;
+
+		let section_metadata = section_metadata_info.as_account_mut::<SectionMetadataState>(&ID)?;
+		section_metadata.init(game.game_index, args.section_index, section_metadata_bump);
+	}
+
+	let section_metadata = section_metadata_info.as_account_mut::<SectionMetadataState>(&ID)?;
+	let section_metadata_seeds_with_bump =
+		seeds_section_metadata!(game.game_index, args.section_index, section_metadata.bump);
+	section_metadata_info
+		.assert_type::<SectionMetadataState>(&ID)?
+		.assert_seeds_with_bump(section_metadata_seeds_with_bump, &ID)?;
+
+	section_metadata.update_gate(member, args.amount());
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionGateUpdate {
+	/// The minimum token balance including decimals. Set to `0` to remove the
+	/// gate.
+	pub amount: PodU64,
+	/// The section being gated.
+	pub section_index: u8,
+	/// The [`TokenMember`] which players must hold.
+	pub member: u8,
+}
+
+impl SectionGateUpdate {
+	pub fn new(section_index: u8, member: TokenMember, amount: u64) -> Self {
+		Self {
+			amount: amount.into(),
+			section_index,
+			member: member.into(),
+		}
+	}
+
+	pub fn amount(&self) -> u64 {
+		self.amount.into()
+	}
+
+	pub fn member(&self) -> Result<TokenMember, ProgramError> {
+		TokenMember::try_from(self.member).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, SectionGateUpdate);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_game;
+	use crate::get_pda_section;
+	use crate::get_pda_section_metadata;
+	use crate::get_pda_treasury;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation_for_owner() -> anyhow::Result<()> {
+		let accounts = create_account_infos(Pubkey::new_unique());
+		process_section_gate_update(&accounts, &create_args(TokenMember::Kibibit, 1_000))?;
+
+		let section_metadata = accounts[4].as_account::<SectionMetadataState>(&ID)?;
+		check!(section_metadata.gate() == Some((TokenMember::Kibibit, 1_000)));
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_pass_validation_for_authority() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(Pubkey::new_unique());
+		let authority = accounts[1].as_account::<ConfigState>(&ID)?.authority;
+		accounts[0].key = leak(authority);
+		process_section_gate_update(&accounts, &create_args(TokenMember::Mebibit, 1))?;
+
+		let section_metadata = accounts[4].as_account::<SectionMetadataState>(&ID)?;
+		check!(section_metadata.gate() == Some((TokenMember::Mebibit, 1)));
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_member() -> anyhow::Result<()> {
+		let accounts = create_account_infos(Pubkey::new_unique());
+		let mut args = SectionGateUpdate::new(0, TokenMember::Bit, 1);
+		args.member = u8::MAX;
+
+		let result = process_section_gate_update(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos(Pubkey::new_unique());
+		let result = process_section_gate_update(&accounts[..5], &create_args(TokenMember::Bit, 1));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn signer_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(Pubkey::new_unique());
+		let signer_info = &mut accounts[0];
+		signer_info.is_signer = false;
+
+		let result = process_section_gate_update(&accounts, &create_args(TokenMember::Bit, 1));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn signer_should_be_owner_or_authority() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(Pubkey::new_unique());
+		let signer_info = &mut accounts[0];
+		signer_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_gate_update(&accounts, &create_args(TokenMember::Bit, 1));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(Pubkey::new_unique());
+		let section_metadata_info = &mut accounts[4];
+		section_metadata_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_gate_update(&accounts, &create_args(TokenMember::Bit, 1));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(member: TokenMember, amount: u64) -> Vec<u8> {
+		bytemuck::bytes_of(&SectionGateUpdate::new(0, member, amount)).to_vec()
+	}
+
+	fn create_account_infos<'info>(owner: Pubkey) -> [AccountInfo<'info>; 6] {
+		let game_index = 0;
+		let section_index = 0;
+		let owner_key = leak(owner);
+		let owner_lamports = leak(1_000_000_000);
+		let owner_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					Pubkey::new_unique(),
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(
+					Pubkey::new_unique(),
+					Pubkey::new_unique(),
+					game_index,
+					game_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let section_key = leak(get_pda_section(game_index, section_index).0);
+		let section_lamports = leak(0);
+		let section_data = {
+			let bump = get_pda_section(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionState::discriminator();
+			data.append(
+				&mut SectionState::new(owner, game_index, section_index, bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let section_metadata_key = leak(get_pda_section_metadata(game_index, section_index).0);
+		let section_metadata_lamports = leak(0);
+		let section_metadata_data = {
+			let bump = get_pda_section_metadata(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionMetadataState::discriminator();
+			data.append(
+				&mut SectionMetadataState::new(game_index, section_index, bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let signer_info = AccountInfo::new(
+			owner_key,
+			true,
+			true,
+			owner_lamports,
+			owner_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_info = AccountInfo::new(
+			section_key,
+			false,
+			false,
+			section_lamports,
+			section_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_metadata_info = AccountInfo::new(
+			section_metadata_key,
+			false,
+			true,
+			section_metadata_lamports,
+			section_metadata_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			signer_info,
+			config_info,
+			game_info,
+			section_info,
+			section_metadata_info,
+			system_program_info,
+		]
+	}
+}
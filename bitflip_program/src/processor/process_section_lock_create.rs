@@ -0,0 +1,407 @@
+use solana_program::msg;
+use spl_pod::primitives::PodI64;
+use steel::*;
+
+use crate::get_lock_price_in_lamports;
+use crate::seeds_game;
+use crate::seeds_section;
+use crate::seeds_section_locks;
+use crate::validate_region;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::SectionLock;
+use crate::SectionLocksState;
+use crate::SectionState;
+use crate::ID;
+use crate::MAX_LOCK_DURATION;
+
+/// Buy a temporary lock over a rectangular region of a section.
+///
+/// The price is determined by the area of the region and the duration of the
+/// lock and is paid to the owner of the section. While the lock is active only
+/// the holder can flip the bits within the region. The locks account is
+/// created by the first lock of the section.
+pub fn process_section_lock_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionLockCreate::try_from_bytes(data)?;
+	args.validate()?;
+
+	// load accounts
+	let [holder_info, owner_info, game_info, section_info, section_locks_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let section = section_info.as_account::<SectionState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_seeds_with_bump = seeds_section!(game.game_index, args.section_index, section.bump);
+	let section_locks_seeds = seeds_section_locks!(game.game_index, args.section_index);
+
+	holder_info.assert_signer()?.assert_writable()?;
+	owner_info.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_info
+		.assert_type::<SectionState>(&ID)?
+		.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+	section_locks_info.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	section.assert_err(
+		|state| state.owner.eq(owner_info.key),
+		BitflipError::Unauthorized,
+	)?;
+
+	if section_locks_info.data_is_empty() {
+		let section_locks_bump =
+			section_locks_info.assert_canonical_bump(section_locks_seeds, &ID)?;
+
+		create_account_with_bump::<SectionLocksState>(
+			section_locks_info,
+			system_program_info,
+			holder_info,
+			&ID,
+			section_locks_seeds,
+			section_locks_bump,
+		)?;
+
+		let section_locks = section_locks_info.as_account_mut::<SectionLocksState>(&ID)?;
+		*section_locks =
+			SectionLocksState::new(game.game_index, args.section_index, section_locks_bump);
+	}
+
+	let section_locks = section_locks_info.as_account_mut::<SectionLocksState>(&ID)?;
+	let section_locks_seeds_with_bump =
+		seeds_section_locks!(game.game_index, args.section_index, section_locks.bump);
+	section_locks_info
+		.assert_type::<SectionLocksState>(&ID)?
+		.assert_seeds_with_bump(section_locks_seeds_with_bump, &ID)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| state.running(current_time),
+		BitflipError::GameNotRunning,
+	)?;
+
+	section_locks.lock(
+		SectionLock {
+			holder: *holder_info.key,
+			expiry: current_time.saturating_add(args.duration()).into(),
+			x: args.x,
+			y: args.y,
+			width: args.width,
+			height: args.height,
+		},
+		current_time,
+	)?;
+
+	let lamports = args.price();
+	msg!("transferring lamports to section owner: {}", lamports);
+	owner_info.collect(lamports, holder_info)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionLockCreate {
+	/// How long the lock lasts in seconds.
+	pub duration: PodI64,
+	/// The section to lock.
+	pub section_index: u8,
+	/// The left coordinate of the region.
+	pub x: u8,
+	/// The top coordinate of the region.
+	pub y: u8,
+	/// The width of the region.
+	pub width: u8,
+	/// The height of the region.
+	pub height: u8,
+}
+
+impl SectionLockCreate {
+	pub fn duration(&self) -> i64 {
+		self.duration.into()
+	}
+
+	/// The lamports paid to the section owner for the lock.
+	pub fn price(&self) -> u64 {
+		get_lock_price_in_lamports(self.width, self.height, self.duration())
+	}
+
+	pub fn validate(&self) -> ProgramResult {
+		validate_region(self.x, self.y, self.width, self.height)
+			.map_err(|_| BitflipError::SectionLockInvalid)?;
+
+		if !(1..=MAX_LOCK_DURATION).contains(&self.duration()) {
+			return Err(BitflipError::SectionLockInvalid.into());
+		}
+
+		Ok(())
+	}
+}
+
+instruction!(BitflipInstruction, SectionLockCreate);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_section;
+	use crate::get_pda_section_locks;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, 60));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_region() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_lock_create(&accounts, &create_args(62, 0, 4, 4, 60));
+		check!(result.unwrap_err() == BitflipError::SectionLockInvalid.into());
+
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 0, 4, 60));
+		check!(result.unwrap_err() == BitflipError::SectionLockInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_duration() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, 0));
+		check!(result.unwrap_err() == BitflipError::SectionLockInvalid.into());
+
+		let result =
+			process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, MAX_LOCK_DURATION + 1));
+		check!(result.unwrap_err() == BitflipError::SectionLockInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_lock_create(&accounts[..5], &create_args(0, 0, 4, 4, 60));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn holder_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let holder_info = &mut accounts[0];
+		holder_info.is_signer = false;
+
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, 60));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn owner_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let owner_info = &mut accounts[1];
+		owner_info.is_writable = false;
+
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, 60));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn owner_should_own_section() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let owner_info = &mut accounts[1];
+		owner_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, 60));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_locks_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_locks_info = &mut accounts[4];
+		section_locks_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_lock_create(&accounts, &create_args(0, 0, 4, 4, 60));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test]
+	fn price_should_scale_with_area_and_duration() {
+		let args = SectionLockCreate {
+			duration: 10.into(),
+			section_index: 0,
+			x: 0,
+			y: 0,
+			width: 2,
+			height: 3,
+		};
+
+		check!(args.price() == 6 * 10 * crate::LOCK_LAMPORTS_PER_BIT_SECOND);
+	}
+
+	fn create_args(x: u8, y: u8, width: u8, height: u8, duration: i64) -> Vec<u8> {
+		bytemuck::bytes_of(&SectionLockCreate {
+			duration: duration.into(),
+			section_index: 0,
+			x,
+			y,
+			width,
+			height,
+		})
+		.to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 6] {
+		let game_index = 0;
+		let section_index = 0;
+		let owner = Pubkey::new_unique();
+		let holder_key = leak(Pubkey::new_unique());
+		let holder_lamports = leak(1_000_000_000);
+		let holder_data = leak(vec![]);
+		let owner_key = leak(owner);
+		let owner_lamports = leak(0);
+		let owner_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(
+					Pubkey::new_unique(),
+					Pubkey::new_unique(),
+					game_index,
+					game_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let section_key = leak(get_pda_section(game_index, section_index).0);
+		let section_lamports = leak(0);
+		let section_data = {
+			let bump = get_pda_section(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionState::discriminator();
+			data.append(
+				&mut SectionState::new(owner, game_index, section_index, bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let section_locks_key = leak(get_pda_section_locks(game_index, section_index).0);
+		let section_locks_lamports = leak(0);
+		let section_locks_data = {
+			let bump = get_pda_section_locks(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionLocksState::discriminator();
+			data.append(
+				&mut SectionLocksState::new(game_index, section_index, bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let holder_info = AccountInfo::new(
+			holder_key,
+			true,
+			true,
+			holder_lamports,
+			holder_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let owner_info = AccountInfo::new(
+			owner_key,
+			false,
+			true,
+			owner_lamports,
+			owner_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_info = AccountInfo::new(
+			section_key,
+			false,
+			false,
+			section_lamports,
+			section_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_locks_info = AccountInfo::new(
+			section_locks_key,
+			false,
+			true,
+			section_locks_lamports,
+			section_locks_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			holder_info,
+			owner_info,
+			game_info,
+			section_info,
+			section_locks_info,
+			system_program_info,
+		]
+	}
+}
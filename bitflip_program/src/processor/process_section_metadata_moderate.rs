@@ -0,0 +1,221 @@
+use steel::*;
+
+use crate::seeds_config;
+use crate::seeds_section_metadata;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::SectionMetadataState;
+use crate::ID;
+
+/// Moderate the metadata of a section.
+///
+/// The config authority can clear the title, link and colors of a section and
+/// prevent the owner from setting them again. The moderation can also be
+/// lifted.
+pub fn process_section_metadata_moderate(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionMetadataModerate::try_from_bytes(data)?;
+
+	// load accounts
+	let [authority_info, config_info, section_metadata_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let section_metadata = section_metadata_info.as_account_mut::<SectionMetadataState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let section_metadata_seeds_with_bump = seeds_section_metadata!(
+		section_metadata.game_index,
+		section_metadata.section_index,
+		section_metadata.bump
+	);
+
+	authority_info.assert_signer()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	section_metadata_info
+		.assert_type::<SectionMetadataState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(section_metadata_seeds_with_bump, &ID)?;
+
+	config.assert_err(
+		|state| state.authority.eq(authority_info.key),
+		BitflipError::Unauthorized,
+	)?;
+
+	section_metadata.moderate(args.moderated != 0);
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionMetadataModerate {
+	/// Set to `1` to moderate the metadata and `0` to lift the moderation.
+	pub moderated: u8,
+}
+
+instruction!(BitflipInstruction, SectionMetadataModerate);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_section_metadata;
+	use crate::get_pda_treasury;
+	use crate::leak;
+	use crate::SECTION_TITLE_MAX_LENGTH;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		process_section_metadata_moderate(&accounts, &[1])?;
+
+		let section_metadata = accounts[2].as_account::<SectionMetadataState>(&ID)?;
+		check!(section_metadata.moderated());
+		check!(section_metadata.title() == "");
+
+		process_section_metadata_moderate(&accounts, &[0])?;
+		let section_metadata = accounts[2].as_account::<SectionMetadataState>(&ID)?;
+		check!(!section_metadata.moderated());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_metadata_moderate(&accounts[..2], &[1]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.is_signer = false;
+
+		let result = process_section_metadata_moderate(&accounts, &[1]);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_match_config() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_metadata_moderate(&accounts, &[1]);
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_metadata_info = &mut accounts[2];
+		section_metadata_info.is_writable = false;
+
+		let result = process_section_metadata_moderate(&accounts, &[1]);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_metadata_info = &mut accounts[2];
+		section_metadata_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_metadata_moderate(&accounts, &[1]);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 3] {
+		let game_index = 0;
+		let section_index = 0;
+		let authority = Pubkey::new_unique();
+		let authority_key = leak(authority);
+		let authority_lamports = leak(0);
+		let authority_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					authority,
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let section_metadata_key = leak(get_pda_section_metadata(game_index, section_index).0);
+		let section_metadata_lamports = leak(0);
+		let section_metadata_data = {
+			let bump = get_pda_section_metadata(game_index, section_index).1;
+			let mut section_metadata = SectionMetadataState::new(game_index, section_index, bump);
+			section_metadata.title = [b'a'; SECTION_TITLE_MAX_LENGTH];
+			section_metadata.title_len = 5;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionMetadataState::discriminator();
+			data.append(&mut section_metadata.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let authority_info = AccountInfo::new(
+			authority_key,
+			true,
+			false,
+			authority_lamports,
+			authority_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_metadata_info = AccountInfo::new(
+			section_metadata_key,
+			false,
+			true,
+			section_metadata_lamports,
+			section_metadata_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[authority_info, config_info, section_metadata_info]
+	}
+}
@@ -0,0 +1,397 @@
+use steel::*;
+
+use crate::seeds_game;
+use crate::seeds_section;
+use crate::seeds_section_metadata;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::SectionMetadataState;
+use crate::SectionState;
+use crate::ID;
+use crate::SECTION_LINK_MAX_LENGTH;
+use crate::SECTION_TITLE_MAX_LENGTH;
+
+/// Set the title, link and colors of a section.
+///
+/// Only the owner of the section can update the metadata. The metadata account
+/// is created the first time this is called.
+pub fn process_section_metadata_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionMetadataUpdate::try_from_bytes(data)?;
+
+	// load accounts
+	let [owner_info, game_info, section_info, section_metadata_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let section = section_info.as_account::<SectionState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_seeds_with_bump = seeds_section!(game.game_index, args.section_index, section.bump);
+
+	owner_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_info
+		.assert_type::<SectionState>(&ID)?
+		.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+	section_metadata_info.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	section.assert_err(
+		|state| state.owner.eq(owner_info.key),
+		BitflipError::Unauthorized,
+	)?;
+
+	if section_metadata_info.data_is_empty() {
+		let section_metadata_seeds = seeds_section_metadata!(game.game_index, args.section_index);
+		let section_metadata_bump =
+			section_metadata_info.assert_canonical_bump(section_metadata_seeds, &ID)?;
+
+		create_account_with_bump::<SectionMetadataState>(
+			section_metadata_info,
+			system_program_info,
+			owner_info,
+			&ID,
+			section_metadata_seeds,
+			section_metadata_bump,
+		)?;
+
+		let section_metadata = section_metadata_info.as_account_mut::<SectionMetadataState>(&ID)?;
+		section_metadata.init(game.game_index, args.section_index, section_metadata_bump);
+	}
+
+	let section_metadata = section_metadata_info.as_account_mut::<SectionMetadataState>(&ID)?;
+	let section_metadata_seeds_with_bump =
+		seeds_section_metadata!(game.game_index, args.section_index, section_metadata.bump);
+	section_metadata_info
+		.assert_type::<SectionMetadataState>(&ID)?
+		.assert_seeds_with_bump(section_metadata_seeds_with_bump, &ID)?;
+
+	section_metadata.assert_err(
+		|state| !state.moderated(),
+		BitflipError::SectionMetadataModerated,
+	)?;
+	section_metadata.update(args)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionMetadataUpdate {
+	/// The section being customised.
+	pub section_index: u8,
+	/// The utf8 bytes of the title.
+	pub title: [u8; SECTION_TITLE_MAX_LENGTH],
+	/// The length of the title.
+	pub title_len: u8,
+	/// The utf8 bytes of the external link.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub link: [u8; SECTION_LINK_MAX_LENGTH],
+	/// The length of the link.
+	pub link_len: u8,
+	/// The rgb color of the bits which are on.
+	pub foreground: [u8; 3],
+	/// The rgb color of the bits which are off.
+	pub background: [u8; 3],
+}
+
+impl SectionMetadataUpdate {
+	/// Create the instruction data. The `title` and `link` are truncated to
+	/// their maximum lengths.
+	pub fn new(
+		section_index: u8,
+		title: &str,
+		link: &str,
+		foreground: [u8; 3],
+		background: [u8; 3],
+	) -> Self {
+		let (title, title_len) = get_truncated_bytes(title);
+		let (link, link_len) = get_truncated_bytes(link);
+
+		Self {
+			section_index,
+			title,
+			title_len,
+			link,
+			link_len,
+			foreground,
+			background,
+		}
+	}
+}
+
+/// Copy the string into a fixed size array, truncating on a character
+/// boundary so the result is always valid utf8.
+fn get_truncated_bytes<const N: usize>(value: &str) -> ([u8; N], u8) {
+	let mut length = value.len().min(N);
+
+	while !value.is_char_boundary(length) {
+		length -= 1;
+	}
+
+	let mut bytes = [0; N];
+	bytes[..length].copy_from_slice(&value.as_bytes()[..length]);
+
+	(bytes, length as u8)
+}
+
+instruction!(BitflipInstruction, SectionMetadataUpdate);
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+	use std::rc::Rc;
+
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_section;
+	use crate::get_pda_section_metadata;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		process_section_metadata_update(&accounts, &create_args("gm", "https://bitflip.art"))?;
+
+		let section_metadata = accounts[3].as_account::<SectionMetadataState>(&ID)?;
+		check!(section_metadata.title() == "gm");
+		check!(section_metadata.link() == "https://bitflip.art");
+		check!(section_metadata.foreground == [255, 0, 0]);
+		check!(section_metadata.background == [0, 0, 255]);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_truncate_long_values() -> anyhow::Result<()> {
+		let args =
+			SectionMetadataUpdate::new(0, &format!("a{}", "ü".repeat(20)), "", [0; 3], [0; 3]);
+		check!(args.title_len == 31);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_lengths() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let mut args = SectionMetadataUpdate::new(0, "gm", "", [0; 3], [0; 3]);
+		args.title_len = u8::MAX;
+
+		let result = process_section_metadata_update(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == BitflipError::SectionMetadataInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_metadata_update(&accounts[..4], &create_args("gm", ""));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn owner_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let owner_info = &mut accounts[0];
+		owner_info.is_signer = false;
+
+		let result = process_section_metadata_update(&accounts, &create_args("gm", ""));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn owner_should_own_section() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let owner_info = &mut accounts[0];
+		owner_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_metadata_update(&accounts, &create_args("gm", ""));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_info = &mut accounts[2];
+		section_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_metadata_update(&accounts, &create_args("gm", ""));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_metadata_info = &mut accounts[3];
+		section_metadata_info.is_writable = false;
+
+		let result = process_section_metadata_update(&accounts, &create_args("gm", ""));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_not_be_moderated() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		accounts[3]
+			.as_account_mut::<SectionMetadataState>(&ID)?
+			.moderate(true);
+
+		let result = process_section_metadata_update(&accounts, &create_args("gm", ""));
+		check!(result.unwrap_err() == BitflipError::SectionMetadataModerated.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_metadata_should_have_valid_data() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_metadata_info = &mut accounts[3];
+		section_metadata_info.data = Rc::new(RefCell::new(leak(vec![0u8; 8])));
+
+		let result = process_section_metadata_update(&accounts, &create_args("gm", ""));
+		check!(result.unwrap_err() == ProgramError::InvalidAccountData);
+
+		Ok(())
+	}
+
+	fn create_args(title: &str, link: &str) -> Vec<u8> {
+		let args = SectionMetadataUpdate::new(0, title, link, [255, 0, 0], [0, 0, 255]);
+		bytemuck::bytes_of(&args).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 5] {
+		let game_index = 0;
+		let section_index = 0;
+		let owner = Pubkey::new_unique();
+		let owner_key = leak(owner);
+		let owner_lamports = leak(1_000_000_000);
+		let owner_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(
+					Pubkey::new_unique(),
+					Pubkey::new_unique(),
+					game_index,
+					game_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let section_key = leak(get_pda_section(game_index, section_index).0);
+		let section_lamports = leak(0);
+		let section_data = {
+			let section_bump = get_pda_section(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionState::discriminator();
+			data.append(
+				&mut SectionState::new(owner, game_index, section_index, section_bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let section_metadata_key = leak(get_pda_section_metadata(game_index, section_index).0);
+		let section_metadata_lamports = leak(0);
+		let section_metadata_data = {
+			let bump = get_pda_section_metadata(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionMetadataState::discriminator();
+			data.append(
+				&mut SectionMetadataState::new(game_index, section_index, bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let owner_info = AccountInfo::new(
+			owner_key,
+			true,
+			true,
+			owner_lamports,
+			owner_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_info = AccountInfo::new(
+			section_key,
+			false,
+			false,
+			section_lamports,
+			section_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_metadata_info = AccountInfo::new(
+			section_metadata_key,
+			false,
+			true,
+			section_metadata_lamports,
+			section_metadata_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			owner_info,
+			game_info,
+			section_info,
+			section_metadata_info,
+			system_program_info,
+		]
+	}
+}
@@ -0,0 +1,273 @@
+use steel::*;
+
+use crate::get_mines_commitment;
+use crate::seeds_game;
+use crate::seeds_section_mines;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::MinesStatus;
+use crate::PodSectionData;
+use crate::SectionMinesState;
+use crate::ID;
+
+/// Reveal the mine layout of a section once the game has ended.
+///
+/// The layout is checked against the commitment stored when the section was
+/// unlocked. Anyone can then audit the hits which were reported by the temp
+/// signer during the game with [`SectionMinesState::hits_match`].
+pub fn process_section_mines_reveal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionMinesReveal::try_from_bytes(data)?;
+
+	// load accounts
+	let [temp_signer_info, game_info, section_mines_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let section_mines = section_mines_info.as_account_mut::<SectionMinesState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_mines_seeds_with_bump =
+		seeds_section_mines!(game.game_index, args.section_index, section_mines.bump);
+
+	temp_signer_info.assert_signer()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_mines_info
+		.assert_type::<SectionMinesState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(section_mines_seeds_with_bump, &ID)?;
+
+	game.assert_err(
+		|state| state.temp_signer.eq(temp_signer_info.key),
+		BitflipError::GameSignerInvalid,
+	)?;
+	section_mines.assert_err(
+		|state| state.status() == MinesStatus::Committed,
+		BitflipError::MinesAlreadyRevealed,
+	)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| state.ended(current_time),
+		BitflipError::GameNotEnded,
+	)?;
+
+	let mines = args.mines.map(Into::into);
+	section_mines.assert_err(
+		|state| state.commitment == get_mines_commitment(&mines, &args.salt),
+		BitflipError::MinesCommitmentInvalid,
+	)?;
+
+	section_mines.store_mines(&args.mines);
+	msg!("section mines revealed");
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionMinesReveal {
+	/// The section which is being revealed.
+	pub section_index: u8,
+	/// The secret salt which was hashed with the mines for the commitment.
+	pub salt: [u8; 32],
+	/// The layout of the mines.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub mines: PodSectionData,
+}
+
+instruction!(BitflipInstruction, SectionMinesReveal);
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+	use std::rc::Rc;
+
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_section_mines;
+	use crate::leak;
+	use crate::SectionData;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_mines_reveal(&accounts[..2], &create_args());
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn temp_signer_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let temp_signer_info = &mut accounts[0];
+		temp_signer_info.is_signer = false;
+
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn temp_signer_should_match_game() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let temp_signer_info = &mut accounts[0];
+		temp_signer_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == BitflipError::GameSignerInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[1];
+		game_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_mines_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_mines_info = &mut accounts[2];
+		section_mines_info.is_writable = false;
+
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_mines_should_have_valid_data() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_mines_info = &mut accounts[2];
+		section_mines_info.data = Rc::new(RefCell::new(leak(vec![0u8; 8])));
+
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::InvalidAccountData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_mines_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_mines_info = &mut accounts[2];
+		section_mines_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_mines_reveal(&accounts, &create_args());
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_mines() -> SectionData {
+		std::array::from_fn(|index| if index % 3 == 0 { 0b1001 } else { 0 })
+	}
+
+	fn create_args() -> Vec<u8> {
+		let args = SectionMinesReveal {
+			section_index: 0,
+			salt: [1; 32],
+			mines: create_mines().map(Into::into),
+		};
+
+		bytemuck::bytes_of(&args).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 3] {
+		let game_index = 0;
+		let section_index = 0;
+		let temp_signer = Pubkey::new_unique();
+		let temp_signer_key = leak(temp_signer);
+		let temp_signer_lamports = leak(0);
+		let temp_signer_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(temp_signer, Pubkey::new_unique(), game_index, game_bump)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let section_mines_key = leak(get_pda_section_mines(game_index, section_index).0);
+		let section_mines_lamports = leak(0);
+		let section_mines_data = {
+			let bump = get_pda_section_mines(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			let mut section_mines = SectionMinesState::zeroed();
+			section_mines.init(
+				get_mines_commitment(&create_mines(), &[1; 32]),
+				game_index,
+				section_index,
+				bump,
+			);
+			data[0] = SectionMinesState::discriminator();
+			data.append(&mut section_mines.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let temp_signer_info = AccountInfo::new(
+			temp_signer_key,
+			true,
+			false,
+			temp_signer_lamports,
+			temp_signer_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_mines_info = AccountInfo::new(
+			section_mines_key,
+			false,
+			true,
+			section_mines_lamports,
+			section_mines_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[temp_signer_info, game_info, section_mines_info]
+	}
+}
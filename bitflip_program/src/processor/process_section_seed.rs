@@ -0,0 +1,337 @@
+use spl_pod::primitives::PodU16;
+use steel::*;
+
+use crate::seeds_config;
+use crate::seeds_game;
+use crate::seeds_section;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::GameState;
+use crate::GameStatus;
+use crate::SectionState;
+use crate::ID;
+use crate::SECTION_SEED_WORDS;
+
+/// Seed the initial art of a section before the game starts as the config
+/// authority.
+///
+/// The section account is created by the first seed and the words are written
+/// into [`SectionState::data`] starting at the `start` word index. Seeding is
+/// only possible while the game is pending or scheduled to start. The seeded
+/// section keeps its data when it is unlocked.
+pub fn process_section_seed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionSeed::try_from_bytes(data)?;
+	let words = args.words()?;
+
+	// load accounts
+	let [authority_info, config_info, game_info, section_info, system_program_info] = accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_seeds = seeds_section!(game.game_index, args.section_index);
+	let section_bump = section_info.assert_canonical_bump(section_seeds, &ID)?;
+
+	authority_info.assert_signer()?.assert_writable()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_info.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	config.assert_err(
+		|state| state.authority.eq(authority_info.key),
+		BitflipError::Unauthorized,
+	)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| state.status() == GameStatus::Pending || state.scheduled(current_time),
+		BitflipError::GameAlreadyStarted,
+	)?;
+
+	if section_info.data_is_empty() {
+		create_account_with_bump::<SectionState>(
+			section_info,
+			system_program_info,
+			authority_info,
+			&ID,
+			section_seeds,
+			section_bump,
+		)?;
+
+		let section = section_info.as_account_mut::<SectionState>(&ID)?;
+		section.init(
+			Pubkey::default(),
+			game.game_index,
+			args.section_index,
+			section_bump,
+		);
+	} else {
+		section_info.assert_type::<SectionState>(&ID)?;
+	}
+
+	let section = section_info.as_account_mut::<SectionState>(&ID)?;
+	section.seed(args.start.into(), &words)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionSeed {
+	/// The words to write into the section data. Only the first `length` words
+	/// are written.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub words: [PodU16; SECTION_SEED_WORDS],
+	/// The index of the seeded section.
+	pub section_index: u8,
+	/// The index of the first word which is written.
+	pub start: u8,
+	/// The number of words which are written.
+	pub length: u8,
+}
+
+impl SectionSeed {
+	/// Create the seed for the `words` starting at the `start` word index. At
+	/// most [`SECTION_SEED_WORDS`] words can be written by one instruction.
+	pub fn new(section_index: u8, start: u8, words: &[u16]) -> Self {
+		let length = words.len().min(SECTION_SEED_WORDS);
+		let mut padded = [PodU16::default(); SECTION_SEED_WORDS];
+
+		for (value, word) in padded.iter_mut().zip(&words[..length]) {
+			*value = (*word).into();
+		}
+
+		Self {
+			words: padded,
+			section_index,
+			start,
+			length: length as u8,
+		}
+	}
+
+	pub fn words(&self) -> Result<Vec<u16>, ProgramError> {
+		let words = self
+			.words
+			.get(..self.length.into())
+			.ok_or(BitflipError::SectionSeedRangeInvalid)?;
+
+		Ok(words.iter().map(|word| u16::from(*word)).collect())
+	}
+}
+
+instruction!(BitflipInstruction, SectionSeed);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_game;
+	use crate::get_pda_section;
+	use crate::get_pda_treasury;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_seed(&accounts, &create_args(&[1, 2, 3]));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_length() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let mut args = SectionSeed::new(0, 0, &[1, 2, 3]);
+		args.length = u8::MAX;
+
+		let result = process_section_seed(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == BitflipError::SectionSeedRangeInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_seed(&accounts[..4], &create_args(&[1, 2, 3]));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.is_signer = false;
+
+		let result = process_section_seed(&accounts, &create_args(&[1, 2, 3]));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_match_config() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_seed(&accounts, &create_args(&[1, 2, 3]));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_info = &mut accounts[3];
+		section_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_seed(&accounts, &create_args(&[1, 2, 3]));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_info = &mut accounts[3];
+		section_info.is_writable = false;
+
+		let result = process_section_seed(&accounts, &create_args(&[1, 2, 3]));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	fn create_args(words: &[u16]) -> Vec<u8> {
+		bytemuck::bytes_of(&SectionSeed::new(0, 0, words)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 5] {
+		let game_index = 0;
+		let authority = Pubkey::new_unique();
+		let authority_key = leak(authority);
+		let authority_lamports = leak(1_000_000_000);
+		let authority_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					authority,
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let game = GameState::new(
+				Pubkey::new_unique(),
+				Pubkey::new_unique(),
+				game_index,
+				game_bump,
+			);
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(&mut game.to_bytes().to_vec());
+			leak(data)
+		};
+		let section_key = leak(get_pda_section(game_index, 0).0);
+		let section_lamports = leak(0);
+		let section_data = leak(vec![]);
+		let system_program_lamports = leak(1_000_000_000);
+		let system_program_data = leak(vec![]);
+
+		let authority_info = AccountInfo::new(
+			authority_key,
+			true,
+			true,
+			authority_lamports,
+			authority_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_info = AccountInfo::new(
+			section_key,
+			false,
+			true,
+			section_lamports,
+			section_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			authority_info,
+			config_info,
+			game_info,
+			section_info,
+			system_program_info,
+		]
+	}
+}
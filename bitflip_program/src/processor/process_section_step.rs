@@ -0,0 +1,356 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::assert_canonical_seeds_with_bump;
+use crate::seeds_game;
+use crate::seeds_section;
+use crate::seeds_section_locks;
+use crate::transfer_lamports_to_section;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::SectionEdge;
+use crate::SectionLocksState;
+use crate::SectionState;
+use crate::ID;
+
+/// Advance a section by one generation of Conway's Game of Life.
+///
+/// The player pays the `step_lamports` of the game to the section. Every bit
+/// which changes is counted in the [`SectionState::steps`] and not as a flip
+/// since no tokens are earned for the step.
+///
+/// Every bit of the section can change in a step so it is rejected while
+/// another player holds an active lock on any region of the section. The
+/// `section_locks_info` is the program address of the section locks even when
+/// no lock has been created yet.
+pub fn process_section_step(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = SectionStep::try_from_bytes(data)?;
+	let edge = args.edge()?;
+
+	// load accounts
+	let [player_info, game_info, section_info, section_locks_info, system_program_info] = accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let section = section_info.as_account_mut::<SectionState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let section_seeds_with_bump = seeds_section!(game.game_index, args.section_index, section.bump);
+
+	section.assert_err(
+		|state| state.section_index == args.section_index,
+		BitflipError::InvalidSectionIndex,
+	)?;
+	player_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	section_info
+		.assert_type::<SectionState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(section_seeds_with_bump, &ID)?;
+
+	if section_locks_info.data_is_empty() {
+		assert_canonical_seeds_with_bump(
+			section_locks_info,
+			seeds_section_locks!(game.game_index, args.section_index),
+			args.section_locks_bump,
+		)?;
+	} else {
+		section_locks_info
+			.assert_type::<SectionLocksState>(&ID)?
+			.assert_seeds_with_bump(
+				seeds_section_locks!(game.game_index, args.section_index, args.section_locks_bump),
+				&ID,
+			)?;
+	}
+
+	system_program_info.assert_program(&system_program::ID)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| state.running(current_time),
+		BitflipError::GameNotRunning,
+	)?;
+
+	if !section_locks_info.data_is_empty() {
+		section_locks_info
+			.as_account::<SectionLocksState>(&ID)?
+			.assert_section_unlocked(player_info.key, current_time)?;
+	}
+
+	let changed = section.step(edge)?;
+	msg!("changed bits: {}", changed);
+
+	transfer_lamports_to_section(section_info, player_info, game.step_lamports())?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionStep {
+	/// The section being stepped.
+	pub section_index: u8,
+	/// The [`SectionEdge`] used for the cells on the border of the section.
+	pub edge: u8,
+	/// The canonical bump of the section locks account.
+	pub section_locks_bump: u8,
+}
+
+impl SectionStep {
+	pub fn new(section_index: u8, edge: SectionEdge, section_locks_bump: u8) -> Self {
+		Self {
+			section_index,
+			edge: edge.into(),
+			section_locks_bump,
+		}
+	}
+
+	pub fn edge(&self) -> Result<SectionEdge, ProgramError> {
+		SectionEdge::try_from(self.edge).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, SectionStep);
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+	use std::rc::Rc;
+
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_section;
+	use crate::get_pda_section_locks;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_step(&accounts, &create_args(SectionEdge::Dead));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_edge() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_step(&accounts, &[0, u8::MAX, 0]);
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_section_step(&accounts[..3], &create_args(SectionEdge::Torus));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_section_step(&accounts, &create_args(SectionEdge::Dead));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let game_info = &mut accounts[1];
+		game_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_step(&accounts, &create_args(SectionEdge::Dead));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_be_writable() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_info = &mut accounts[2];
+		section_info.is_writable = false;
+
+		let result = process_section_step(&accounts, &create_args(SectionEdge::Dead));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_have_valid_data() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_info = &mut accounts[2];
+		section_info.data = Rc::new(RefCell::new(leak(vec![0u8; 8])));
+
+		let result = process_section_step(&accounts, &create_args(SectionEdge::Dead));
+		check!(result.unwrap_err() == ProgramError::InvalidAccountData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_locks_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let section_locks_info = &mut accounts[3];
+		section_locks_info.key = leak(Pubkey::new_unique());
+
+		let result = process_section_step(&accounts, &create_args(SectionEdge::Dead));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_locks_should_have_canonical_bump() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let section_locks_bump = get_pda_section_locks(0, 0).1;
+		let args = SectionStep::new(0, SectionEdge::Dead, section_locks_bump.wrapping_sub(1));
+		let result = process_section_step(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn section_should_have_valid_index() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let args = SectionStep::new(1, SectionEdge::Dead, get_pda_section_locks(0, 1).1);
+		let result = process_section_step(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == BitflipError::InvalidSectionIndex.into());
+
+		Ok(())
+	}
+
+	fn create_args(edge: SectionEdge) -> Vec<u8> {
+		let section_locks_bump = get_pda_section_locks(0, 0).1;
+		bytemuck::bytes_of(&SectionStep::new(0, edge, section_locks_bump)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 5] {
+		let game_index = 0;
+		let section_index = 0;
+		let player_key = leak(Pubkey::new_unique());
+		let player_lamports = leak(1_000_000_000);
+		let player_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(
+				&mut GameState::new(
+					Pubkey::new_unique(),
+					Pubkey::new_unique(),
+					game_index,
+					game_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let section_key = leak(get_pda_section(game_index, section_index).0);
+		let section_lamports = leak(0);
+		let section_data = {
+			let section_bump = get_pda_section(game_index, section_index).1;
+			let mut data = vec![0u8; 8];
+			data[0] = SectionState::discriminator();
+			data.append(
+				&mut SectionState::new(
+					Pubkey::new_unique(),
+					game_index,
+					section_index,
+					section_bump,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let section_locks_key = leak(get_pda_section_locks(game_index, section_index).0);
+		let section_locks_lamports = leak(0);
+		let section_locks_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_info = AccountInfo::new(
+			section_key,
+			false,
+			true,
+			section_lamports,
+			section_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let section_locks_info = AccountInfo::new(
+			section_locks_key,
+			false,
+			false,
+			section_locks_lamports,
+			section_locks_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			player_info,
+			game_info,
+			section_info,
+			section_locks_info,
+			system_program_info,
+		]
+	}
+}
@@ -4,22 +4,49 @@ use steel::*;
 use crate::seeds_config;
 use crate::seeds_game;
 use crate::seeds_section;
+use crate::seeds_section_mines;
+use crate::seeds_stake_pool;
 use crate::seeds_treasury;
 use crate::BitflipError;
 use crate::BitflipInstruction;
 use crate::ConfigState;
 use crate::GameState;
+use crate::SectionMinesState;
 use crate::SectionState;
+use crate::StakePoolState;
 use crate::ID;
 
 /// This instruction is used to unlock a section. It will use a nonce
 /// transaction to help make each bid private.
+///
+/// When the game is a minesweeper game the `section_mines_info` account must
+/// be provided after the `system_program_info` and the commitment to the mine
+/// layout is stored in it.
+///
+/// When the game inherits the canvas of the previous game the
+/// `previous_game_info` and `previous_section_info` must be provided after the
+/// `section_mines_info` (when present). The previous game must be finalized so
+/// that its canvas can no longer change. A new section is created with the
+/// final data of the previous section and while [`GameState::reserved`] only
+/// the previous owner can unlock it, paying the discounted
+/// [`GameState::inherit_lamports`].
+///
+/// A section which was seeded with [`crate::SectionSeed`] before the game
+/// started already exists without an owner and keeps its seeded data when it
+/// is unlocked. Any other existing section has already been unlocked and can't
+/// change its owner.
+///
+/// The `stake_pool_info` is an optional trailing account after all the
+/// accounts above. Once the staking pool has stakers its share of the bid is
+/// paid to the pool and the rest is paid to the treasury. Without the stake
+/// pool the whole bid is paid to the treasury, which keeps the accounts of
+/// clients built before staking valid.
 pub fn process_section_unlock(accounts: &[AccountInfo], data: &[u8]) -> Result<(), ProgramError> {
 	// parse the instruction data.
 	let args = SectionUnlock::try_from_bytes(data)?;
 
 	// load accounts
-	let [owner_info, temp_signer_info, config_info, game_info, section_info, treasury_info, system_program_info] =
+	let [owner_info, temp_signer_info, config_info, game_info, section_info, treasury_info, system_program_info, remaining_accounts @ ..] =
 		accounts
 	else {
 		return Err(ProgramError::NotEnoughAccountKeys);
@@ -45,12 +72,29 @@ pub fn process_section_unlock(accounts: &[AccountInfo], data: &[u8]) -> Result<(
 		.assert_type::<GameState>(&ID)?
 		.assert_writable()?
 		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
-	section_info.assert_empty()?.assert_writable()?;
+	section_info.assert_writable()?;
 	treasury_info
 		.assert_writable()?
 		.assert_seeds_with_bump(treasury_seeds_with_bump, &ID)?;
 	system_program_info.assert_program(&system_program::ID)?;
 
+	let stake_pool_index = usize::from(game.minesweeper()) + 2 * usize::from(game.inherit());
+	let stake_pool_info = remaining_accounts.get(stake_pool_index);
+
+	if let Some(stake_pool_info) = stake_pool_info {
+		stake_pool_info.assert_writable()?;
+
+		if stake_pool_info.data_is_empty() {
+			stake_pool_info.assert_canonical_bump(seeds_stake_pool!(), &ID)?;
+		} else {
+			let stake_pool = stake_pool_info.as_account::<StakePoolState>(&ID)?;
+			let stake_pool_seeds_with_bump = seeds_stake_pool!(stake_pool.bump);
+			stake_pool_info
+				.assert_type::<StakePoolState>(&ID)?
+				.assert_seeds_with_bump(stake_pool_seeds_with_bump, &ID)?;
+		}
+	}
+
 	game.assert_err(
 		|game| game.temp_signer.eq(temp_signer_info.key),
 		BitflipError::GameSignerInvalid,
@@ -62,26 +106,121 @@ pub fn process_section_unlock(accounts: &[AccountInfo], data: &[u8]) -> Result<(
 		BitflipError::GameNotRunning,
 	)?;
 
-	// create the section account
-	create_account_with_bump::<SectionState>(
-		section_info,
-		system_program_info,
-		owner_info,
-		&ID,
-		section_seeds,
-		section_bump,
-	)?;
-
-	let section = section_info.as_account_mut::<SectionState>(&ID)?;
-	section.init(
-		*owner_info.key,
-		game.game_index,
-		game.section_index,
-		section_bump,
-	);
+	let seeded = !section_info.data_is_empty();
+
+	if !seeded {
+		section_info.assert_empty()?;
+
+		// create the section account
+		create_account_with_bump::<SectionState>(
+			section_info,
+			system_program_info,
+			owner_info,
+			&ID,
+			section_seeds,
+			section_bump,
+		)?;
+
+		let section = section_info.as_account_mut::<SectionState>(&ID)?;
+		section.init(
+			*owner_info.key,
+			game.game_index,
+			game.section_index,
+			section_bump,
+		);
+	} else {
+		msg!("unlocking the seeded section");
+		section_info.assert_type::<SectionState>(&ID)?;
+		let section = section_info.as_account_mut::<SectionState>(&ID)?;
+		section.assert_err(
+			|state| state.owner == Pubkey::default(),
+			BitflipError::SectionAlreadyUnlocked,
+		)?;
+		section.owner = *owner_info.key;
+	}
+
+	if game.minesweeper() {
+		let [section_mines_info, ..] = remaining_accounts else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+		let section_mines_seeds = seeds_section_mines!(game.game_index, game.section_index);
+		let section_mines_bump =
+			section_mines_info.assert_canonical_bump(section_mines_seeds, &ID)?;
+		section_mines_info.assert_empty()?.assert_writable()?;
+
+		msg!("committing the section mines");
+		create_account_with_bump::<SectionMinesState>(
+			section_mines_info,
+			system_program_info,
+			owner_info,
+			&ID,
+			section_mines_seeds,
+			section_mines_bump,
+		)?;
+
+		let section_mines = section_mines_info.as_account_mut::<SectionMinesState>(&ID)?;
+		section_mines.init(
+			args.mines_commitment,
+			game.game_index,
+			game.section_index,
+			section_mines_bump,
+		);
+	}
+
+	let mut lamports: u64 = args.lamports.into();
+
+	if game.inherit() {
+		let [previous_game_info, previous_section_info, ..] = remaining_accounts
+			.get(usize::from(game.minesweeper())..)
+			.unwrap_or_default()
+		else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+		let previous_game_index = game.game_index.saturating_sub(1);
+		let previous_game = previous_game_info.as_account::<GameState>(&ID)?;
+		let previous_game_seeds_with_bump = seeds_game!(previous_game_index, previous_game.bump);
+		previous_game_info
+			.assert_type::<GameState>(&ID)?
+			.assert_seeds_with_bump(previous_game_seeds_with_bump, &ID)?;
+		previous_game.assert_err(|state| state.finalized(), BitflipError::GameNotFinalized)?;
+
+		let previous_section_seeds = seeds_section!(previous_game_index, game.section_index);
+		previous_section_info.assert_canonical_bump(previous_section_seeds, &ID)?;
+
+		if !previous_section_info.data_is_empty() {
+			previous_section_info.assert_type::<SectionState>(&ID)?;
+			let previous_section = previous_section_info.as_account::<SectionState>(&ID)?;
+
+			if previous_section.owner != Pubkey::default() && game.reserved(clock.unix_timestamp) {
+				previous_section.assert_err(
+					|state| state.owner.eq(owner_info.key),
+					BitflipError::SectionReserved,
+				)?;
+				lamports = GameState::inherit_lamports(lamports);
+			}
+
+			if !seeded {
+				msg!("inheriting the previous section");
+				let section = section_info.as_account_mut::<SectionState>(&ID)?;
+				section.inherit(previous_section);
+			}
+		}
+	}
+	let mut stake_pool_lamports = 0;
+
+	if let Some(stake_pool_info) = stake_pool_info.filter(|info| !info.data_is_empty()) {
+		let stake_pool = stake_pool_info.as_account_mut::<StakePoolState>(&ID)?;
+		stake_pool_lamports = stake_pool.revenue_share(lamports);
+		stake_pool.deposit(stake_pool_lamports)?;
+
+		if stake_pool_lamports > 0 {
+			msg!("transferring lamports from owner to stake pool");
+			stake_pool_info.collect(stake_pool_lamports, owner_info)?;
+		}
+	}
 
 	msg!("transferring lamports from owner to treasury");
-	treasury_info.collect(args.lamports.into(), owner_info)?;
+	treasury_info.collect(lamports - stake_pool_lamports, owner_info)?;
 
 	msg!("incrementing section index");
 	game.increment_section();
@@ -91,11 +230,16 @@ pub fn process_section_unlock(accounts: &[AccountInfo], data: &[u8]) -> Result<(
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct SectionUnlock {
 	/// The amount of lamports bid to unlock the section. Since the bid uses
 	/// DurableNonce 's it will be a private bid and the backend will determine
 	/// which is the winner and unlock the section accordingly.
 	pub lamports: PodU64,
+	/// The commitment to the mine layout of the section. This is ignored unless
+	/// the game is a minesweeper game.
+	pub mines_commitment: [u8; 32],
 }
 
 impl Eq for SectionUnlock {}
@@ -0,0 +1,168 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::seeds_stake;
+use crate::seeds_stake_pool;
+use crate::BitflipInstruction;
+use crate::StakePoolState;
+use crate::StakeState;
+use crate::ID;
+
+/// Claim the lamports earned by the stake of a player.
+pub fn process_stake_claim(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [player_info, stake_pool_info, stake_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let stake_pool = stake_pool_info.as_account::<StakePoolState>(&ID)?;
+	let stake = stake_info.as_account_mut::<StakeState>(&ID)?;
+	let stake_pool_seeds_with_bump = seeds_stake_pool!(stake_pool.bump);
+	let stake_seeds_with_bump = seeds_stake!(player_info.key, stake.bump);
+
+	player_info.assert_signer()?.assert_writable()?;
+	stake_pool_info
+		.assert_type::<StakePoolState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(stake_pool_seeds_with_bump, &ID)?;
+	stake_info
+		.assert_type::<StakeState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(stake_seeds_with_bump, &ID)?;
+
+	let rewards = stake.claim(stake_pool)?;
+	msg!("claiming stake rewards: {}", rewards);
+	stake_pool_info.send(rewards, player_info);
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeClaim {}
+
+instruction!(BitflipInstruction, StakeClaim);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_stake;
+	use crate::get_pda_stake_pool;
+	use crate::leak;
+	use crate::TokenMember;
+
+	#[test_log::test]
+	fn should_claim_rewards() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		process_stake_claim(&accounts)?;
+
+		check!(accounts[0].lamports() == 1_500);
+		check!(accounts[1].lamports() == 500);
+		check!(accounts[2].as_account::<StakeState>(&ID)?.rewards() == 0);
+
+		// the rewards can only be claimed once
+		process_stake_claim(&accounts)?;
+		check!(accounts[0].lamports() == 1_500);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_claim(&accounts[..2]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_stake_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn stake_should_belong_to_player() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let player_info = &mut accounts[0];
+		player_info.key = leak(Pubkey::new_unique());
+
+		let result = process_stake_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 3] {
+		let player = Pubkey::new_unique();
+		let mut stake_pool = StakePoolState::new(1_000, 0, get_pda_stake_pool().1);
+		let mut stake = StakeState::new(player, 0, get_pda_stake(&player).1);
+		stake
+			.stake(&mut stake_pool, TokenMember::Kibibit, 1, 0)
+			.unwrap();
+		stake_pool.deposit(1_000).unwrap();
+
+		let player_key = leak(player);
+		let player_lamports = leak(500);
+		let player_data = leak(vec![]);
+		let stake_pool_key = leak(get_pda_stake_pool().0);
+		let stake_pool_lamports = leak(1_500);
+		let stake_pool_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = StakePoolState::discriminator();
+			data.append(&mut stake_pool.to_bytes().to_vec());
+			leak(data)
+		};
+		let stake_key = leak(get_pda_stake(&player).0);
+		let stake_lamports = leak(0);
+		let stake_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = StakeState::discriminator();
+			data.append(&mut stake.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let stake_pool_info = AccountInfo::new(
+			stake_pool_key,
+			false,
+			true,
+			stake_pool_lamports,
+			stake_pool_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let stake_info = AccountInfo::new(
+			stake_key,
+			false,
+			true,
+			stake_lamports,
+			stake_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[player_info, stake_pool_info, stake_info]
+	}
+}
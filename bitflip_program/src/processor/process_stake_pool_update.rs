@@ -0,0 +1,283 @@
+use spl_pod::primitives::PodI64;
+use spl_pod::primitives::PodU16;
+use steel::*;
+
+use crate::seeds_config;
+use crate::seeds_stake_pool;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::StakePoolState;
+use crate::ID;
+use crate::MAX_STAKE_REVENUE_BASIS_POINTS;
+
+/// Configure the staking pool. The pool is created by the first update.
+///
+/// The `revenue_basis_points` of every section unlock bid is paid to the pool
+/// and shared between the stakers by the weight of their staked tokens. Staked
+/// tokens are locked for the `unstake_cooldown` after each stake.
+pub fn process_stake_pool_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = StakePoolUpdate::try_from_bytes(data)?;
+	args.validate()?;
+
+	// load accounts
+	let [authority_info, config_info, stake_pool_info, system_program_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+
+	authority_info.assert_signer()?.assert_writable()?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	stake_pool_info.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	config.assert_err(
+		|state| state.authority.eq(authority_info.key),
+		BitflipError::Unauthorized,
+	)?;
+
+	if stake_pool_info.data_is_empty() {
+		let stake_pool_seeds = seeds_stake_pool!();
+		let stake_pool_bump = stake_pool_info.assert_canonical_bump(stake_pool_seeds, &ID)?;
+
+		create_account_with_bump::<StakePoolState>(
+			stake_pool_info,
+			system_program_info,
+			authority_info,
+			&ID,
+			stake_pool_seeds,
+			stake_pool_bump,
+		)?;
+
+		let stake_pool = stake_pool_info.as_account_mut::<StakePoolState>(&ID)?;
+		*stake_pool = StakePoolState::new(0, 0, stake_pool_bump);
+	}
+
+	let stake_pool = stake_pool_info.as_account_mut::<StakePoolState>(&ID)?;
+	let stake_pool_seeds_with_bump = seeds_stake_pool!(stake_pool.bump);
+	stake_pool_info
+		.assert_type::<StakePoolState>(&ID)?
+		.assert_seeds_with_bump(stake_pool_seeds_with_bump, &ID)?;
+
+	stake_pool.revenue_basis_points = args.revenue_basis_points;
+	stake_pool.unstake_cooldown = args.unstake_cooldown;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StakePoolUpdate {
+	/// The number of seconds the tokens are locked for after staking.
+	pub unstake_cooldown: PodI64,
+	/// The share of the section unlock revenue paid to the pool in basis
+	/// points.
+	pub revenue_basis_points: PodU16,
+}
+
+impl StakePoolUpdate {
+	pub fn new(revenue_basis_points: u16, unstake_cooldown: i64) -> Self {
+		Self {
+			unstake_cooldown: unstake_cooldown.into(),
+			revenue_basis_points: revenue_basis_points.into(),
+		}
+	}
+
+	pub fn validate(&self) -> ProgramResult {
+		let revenue_basis_points: u16 = self.revenue_basis_points.into();
+		let unstake_cooldown: i64 = self.unstake_cooldown.into();
+
+		if revenue_basis_points > MAX_STAKE_REVENUE_BASIS_POINTS || unstake_cooldown < 0 {
+			return Err(BitflipError::StakePoolInvalid.into());
+		}
+
+		Ok(())
+	}
+}
+
+instruction!(BitflipInstruction, StakePoolUpdate);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_stake_pool;
+	use crate::get_pda_treasury;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		process_stake_pool_update(&accounts, &create_args(2_500, 60))?;
+
+		let stake_pool = accounts[2].as_account::<StakePoolState>(&ID)?;
+		check!(stake_pool.revenue_basis_points() == 2_500);
+		check!(stake_pool.unstake_cooldown() == 60);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_configuration() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_pool_update(&accounts, &create_args(10_001, 60));
+		check!(result.unwrap_err() == BitflipError::StakePoolInvalid.into());
+
+		let result = process_stake_pool_update(&accounts, &create_args(100, -1));
+		check!(result.unwrap_err() == BitflipError::StakePoolInvalid.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_pool_update(&accounts[..3], &create_args(100, 60));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.is_signer = false;
+
+		let result = process_stake_pool_update(&accounts, &create_args(100, 60));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn authority_should_match_config() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let authority_info = &mut accounts[0];
+		authority_info.key = leak(Pubkey::new_unique());
+
+		let result = process_stake_pool_update(&accounts, &create_args(100, 60));
+		check!(result.unwrap_err() == BitflipError::Unauthorized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn stake_pool_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let stake_pool_info = &mut accounts[2];
+		stake_pool_info.key = leak(Pubkey::new_unique());
+
+		let result = process_stake_pool_update(&accounts, &create_args(100, 60));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(revenue_basis_points: u16, unstake_cooldown: i64) -> Vec<u8> {
+		bytemuck::bytes_of(&StakePoolUpdate::new(
+			revenue_basis_points,
+			unstake_cooldown,
+		))
+		.to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 4] {
+		let authority = Pubkey::new_unique();
+		let authority_key = leak(authority);
+		let authority_lamports = leak(1_000_000_000);
+		let authority_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					authority,
+					get_pda_config().1,
+					get_pda_treasury().1,
+					0,
+					0,
+					0,
+					0,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let stake_pool_key = leak(get_pda_stake_pool().0);
+		let stake_pool_lamports = leak(0);
+		let stake_pool_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = StakePoolState::discriminator();
+			data.append(
+				&mut StakePoolState::new(0, 0, get_pda_stake_pool().1)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let authority_info = AccountInfo::new(
+			authority_key,
+			true,
+			true,
+			authority_lamports,
+			authority_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let stake_pool_info = AccountInfo::new(
+			stake_pool_key,
+			false,
+			true,
+			stake_pool_lamports,
+			stake_pool_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			authority_info,
+			config_info,
+			stake_pool_info,
+			system_program_info,
+		]
+	}
+}
@@ -0,0 +1,417 @@
+use solana_program::msg;
+use spl_pod::primitives::PodU64;
+use steel::*;
+
+use crate::cpi::create_associated_token_account_idempotent;
+use crate::cpi::transfer_checked;
+use crate::seeds_config;
+use crate::seeds_mint;
+use crate::seeds_stake;
+use crate::seeds_stake_pool;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::StakePoolState;
+use crate::StakeState;
+use crate::TokenMember;
+use crate::ID;
+use crate::TOKEN_DECIMALS;
+
+/// Stake tokens of a [`TokenMember`] in the staking pool.
+///
+/// The stake of the player is created by their first stake. Each token is
+/// weighted by the number of bits it is worth and the whole stake is locked for
+/// the `unstake_cooldown` of the pool.
+pub fn process_stake_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = StakeTokens::try_from_bytes(data)?;
+	let member = args.member()?;
+	args.validate()?;
+
+	// load accounts
+	let [player_info, player_token_account_info, config_info, mint_info, stake_pool_info, stake_pool_token_account_info, stake_info, associated_token_program_info, token_program_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let stake_pool = stake_pool_info.as_account_mut::<StakePoolState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let mint_seeds_with_bump = seeds_mint!(member, member.bump(config));
+	let stake_pool_seeds_with_bump = seeds_stake_pool!(stake_pool.bump);
+
+	player_info.assert_signer()?.assert_writable()?;
+	player_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(player_info.key, mint_info.key)?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	mint_info.assert_seeds_with_bump(mint_seeds_with_bump, &ID)?;
+	stake_pool_info
+		.assert_type::<StakePoolState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(stake_pool_seeds_with_bump, &ID)?;
+	stake_pool_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(stake_pool_info.key, mint_info.key)?;
+	stake_info.assert_writable()?;
+	associated_token_program_info.assert_program(&spl_associated_token_account::ID)?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	if stake_info.data_is_empty() {
+		let stake_seeds = seeds_stake!(player_info.key);
+		let stake_bump = stake_info.assert_canonical_bump(stake_seeds, &ID)?;
+
+		create_account_with_bump::<StakeState>(
+			stake_info,
+			system_program_info,
+			player_info,
+			&ID,
+			stake_seeds,
+			stake_bump,
+		)?;
+
+		let stake = stake_info.as_account_mut::<StakeState>(&ID)?;
+		*stake = StakeState::new(*player_info.key, stake_pool.reward_index(), stake_bump);
+	}
+
+	let stake = stake_info.as_account_mut::<StakeState>(&ID)?;
+	let stake_seeds_with_bump = seeds_stake!(player_info.key, stake.bump);
+	stake_info
+		.assert_type::<StakeState>(&ID)?
+		.assert_seeds_with_bump(stake_seeds_with_bump, &ID)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	let unlock_time = current_time.saturating_add(stake_pool.unstake_cooldown());
+	stake.stake(stake_pool, member, args.amount(), unlock_time)?;
+
+	create_associated_token_account_idempotent(
+		player_info,
+		stake_pool_token_account_info,
+		stake_pool_info,
+		mint_info,
+		token_program_info,
+		system_program_info,
+		&[],
+	)?;
+
+	msg!("transferring tokens to the stake pool: {}", args.amount());
+	transfer_checked(
+		player_token_account_info,
+		mint_info,
+		stake_pool_token_account_info,
+		player_info,
+		token_program_info,
+		args.amount(),
+		TOKEN_DECIMALS,
+		&[],
+	)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StakeTokens {
+	/// The number of tokens to stake.
+	pub amount: PodU64,
+	/// The [`TokenMember`] being staked.
+	pub member: u8,
+}
+
+impl StakeTokens {
+	pub fn new(member: TokenMember, amount: u64) -> Self {
+		Self {
+			amount: amount.into(),
+			member: member.into(),
+		}
+	}
+
+	pub fn amount(&self) -> u64 {
+		self.amount.into()
+	}
+
+	pub fn member(&self) -> Result<TokenMember, ProgramError> {
+		TokenMember::try_from(self.member).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+
+	pub fn validate(&self) -> ProgramResult {
+		if self.amount() == 0 {
+			return Err(ProgramError::InvalidInstructionData);
+		}
+
+		Ok(())
+	}
+}
+
+instruction!(BitflipInstruction, StakeTokens);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_mint;
+	use crate::get_pda_stake;
+	use crate::get_pda_stake_pool;
+	use crate::get_pda_treasury;
+	use crate::get_player_token_account;
+	use crate::get_stake_pool_token_account;
+	use crate::leak;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_tokens(&accounts, &create_args(TokenMember::Bit, 10));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_args() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_tokens(&accounts, &create_args(TokenMember::Bit, 0));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		let mut args = StakeTokens::new(TokenMember::Bit, 10);
+		args.member = u8::MAX;
+		let result = process_stake_tokens(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_tokens(&accounts[..9], &create_args(TokenMember::Bit, 10));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_stake_tokens(&accounts, &create_args(TokenMember::Bit, 10));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn mint_should_match_member() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_stake_tokens(&accounts, &create_args(TokenMember::Kibibit, 10));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn stake_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let stake_info = &mut accounts[6];
+		stake_info.key = leak(Pubkey::new_unique());
+
+		let result = process_stake_tokens(&accounts, &create_args(TokenMember::Bit, 10));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(member: TokenMember, amount: u64) -> Vec<u8> {
+		bytemuck::bytes_of(&StakeTokens::new(member, amount)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 10] {
+		let member = TokenMember::Bit;
+		let player = Pubkey::new_unique();
+		let player_key = leak(player);
+		let player_lamports = leak(1_000_000_000);
+		let player_data = leak(vec![]);
+		let player_token_account_key = leak(get_player_token_account(&player, member));
+		let player_token_account_lamports = leak(0);
+		let player_token_account_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					Pubkey::new_unique(),
+					get_pda_config().1,
+					get_pda_treasury().1,
+					get_pda_mint(TokenMember::Bit).1,
+					get_pda_mint(TokenMember::Kibibit).1,
+					get_pda_mint(TokenMember::Mebibit).1,
+					get_pda_mint(TokenMember::Gibibit).1,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let mint_key = leak(get_pda_mint(member).0);
+		let mint_lamports = leak(0);
+		let mint_data = leak(vec![]);
+		let stake_pool_key = leak(get_pda_stake_pool().0);
+		let stake_pool_lamports = leak(0);
+		let stake_pool_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = StakePoolState::discriminator();
+			data.append(
+				&mut StakePoolState::new(1_000, 60, get_pda_stake_pool().1)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let stake_pool_token_account_key = leak(get_stake_pool_token_account(member));
+		let stake_pool_token_account_lamports = leak(0);
+		let stake_pool_token_account_data = leak(vec![]);
+		let stake_key = leak(get_pda_stake(&player).0);
+		let stake_lamports = leak(0);
+		let stake_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = StakeState::discriminator();
+			data.append(
+				&mut StakeState::new(player, 0, get_pda_stake(&player).1)
+					.to_bytes()
+					.to_vec(),
+			);
+			leak(data)
+		};
+		let associated_token_program_lamports = leak(0);
+		let associated_token_program_data = leak(vec![]);
+		let token_program_lamports = leak(0);
+		let token_program_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let player_token_account_info = AccountInfo::new(
+			player_token_account_key,
+			false,
+			true,
+			player_token_account_lamports,
+			player_token_account_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let mint_info = AccountInfo::new(
+			mint_key,
+			false,
+			false,
+			mint_lamports,
+			mint_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let stake_pool_info = AccountInfo::new(
+			stake_pool_key,
+			false,
+			true,
+			stake_pool_lamports,
+			stake_pool_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let stake_pool_token_account_info = AccountInfo::new(
+			stake_pool_token_account_key,
+			false,
+			true,
+			stake_pool_token_account_lamports,
+			stake_pool_token_account_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let stake_info = AccountInfo::new(
+			stake_key,
+			false,
+			true,
+			stake_lamports,
+			stake_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let associated_token_program_info = AccountInfo::new(
+			&spl_associated_token_account::ID,
+			false,
+			false,
+			associated_token_program_lamports,
+			associated_token_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+		let token_program_info = AccountInfo::new(
+			&spl_token_2022::ID,
+			false,
+			false,
+			token_program_lamports,
+			token_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			player_info,
+			player_token_account_info,
+			config_info,
+			mint_info,
+			stake_pool_info,
+			stake_pool_token_account_info,
+			stake_info,
+			associated_token_program_info,
+			token_program_info,
+			system_program_info,
+		]
+	}
+}
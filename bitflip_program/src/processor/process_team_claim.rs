@@ -0,0 +1,225 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::seeds_game;
+use crate::seeds_player_team;
+use crate::seeds_team_pool;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::PlayerTeamState;
+use crate::TeamPoolState;
+use crate::ID;
+
+/// Claim the share of the team pool earned by the flips of a player on the
+/// winning team once the game has been finalized.
+pub fn process_team_claim(accounts: &[AccountInfo]) -> ProgramResult {
+	// load accounts
+	let [player_info, game_info, team_pool_info, player_team_info] = accounts else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let team_pool = team_pool_info.as_account::<TeamPoolState>(&ID)?;
+	let player_team = player_team_info.as_account_mut::<PlayerTeamState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let team_pool_seeds_with_bump = seeds_team_pool!(game.game_index, team_pool.bump);
+	let player_team_seeds_with_bump =
+		seeds_player_team!(game.game_index, player_info.key, player_team.bump);
+
+	player_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	team_pool_info
+		.assert_type::<TeamPoolState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(team_pool_seeds_with_bump, &ID)?;
+	player_team_info
+		.assert_type::<PlayerTeamState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(player_team_seeds_with_bump, &ID)?;
+
+	game.assert_err(|state| state.finalized(), BitflipError::GameNotFinalized)?;
+
+	let reward = player_team.claim(team_pool)?;
+	msg!("claiming team reward: {}", reward);
+	team_pool_info.send(reward, player_info);
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TeamClaim {}
+
+instruction!(BitflipInstruction, TeamClaim);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_player_team;
+	use crate::get_pda_team_pool;
+	use crate::leak;
+	use crate::Team;
+
+	#[test_log::test]
+	fn should_claim_reward() -> anyhow::Result<()> {
+		let accounts = create_account_infos([1; 32], Team::On);
+		process_team_claim(&accounts)?;
+
+		check!(accounts[0].lamports() == 1_000);
+		check!(accounts[2].lamports() == 0);
+		check!(accounts[3].as_account::<PlayerTeamState>(&ID)?.claimed());
+
+		let result = process_team_claim(&accounts);
+		check!(result.unwrap_err() == BitflipError::TeamRewardClaimed.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_be_finalized() -> anyhow::Result<()> {
+		let accounts = create_account_infos([0; 32], Team::On);
+		let result = process_team_claim(&accounts);
+		check!(result.unwrap_err() == BitflipError::GameNotFinalized.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn team_should_win() -> anyhow::Result<()> {
+		let accounts = create_account_infos([1; 32], Team::Off);
+		let result = process_team_claim(&accounts);
+		check!(result.unwrap_err() == BitflipError::TeamNotWinner.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos([1; 32], Team::On);
+		let result = process_team_claim(&accounts[..3]);
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos([1; 32], Team::On);
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_team_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_team_should_belong_to_player() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos([1; 32], Team::On);
+		let player_info = &mut accounts[0];
+		player_info.key = leak(Pubkey::new_unique());
+
+		let result = process_team_claim(&accounts);
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_account_infos<'info>(canvas_root: [u8; 32], team: Team) -> [AccountInfo<'info>; 4] {
+		let game_index = 0;
+		let player = Pubkey::new_unique();
+		let player_key = leak(player);
+		let player_lamports = leak(0);
+		let player_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut game = GameState::new(
+				Pubkey::new_unique(),
+				Pubkey::new_unique(),
+				game_index,
+				game_bump,
+			);
+			game.canvas_root = canvas_root;
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(&mut game.to_bytes().to_vec());
+			leak(data)
+		};
+		let team_pool_key = leak(get_pda_team_pool(game_index).0);
+		let team_pool_lamports = leak(1_000);
+		let team_pool_data = {
+			let mut team_pool = TeamPoolState::new(game_index, get_pda_team_pool(game_index).1);
+			team_pool.deposit(1_000).unwrap();
+			team_pool.record_flips(Team::On, 4).unwrap();
+			team_pool.bits = [0.into(), 1.into()];
+			let mut data = vec![0u8; 8];
+			data[0] = TeamPoolState::discriminator();
+			data.append(&mut team_pool.to_bytes().to_vec());
+			leak(data)
+		};
+		let player_team_key = leak(get_pda_player_team(game_index, &player).0);
+		let player_team_lamports = leak(0);
+		let player_team_data = {
+			let bump = get_pda_player_team(game_index, &player).1;
+			let mut player_team = PlayerTeamState::new(player, game_index, team, bump);
+			player_team.flips = 4.into();
+			let mut data = vec![0u8; 8];
+			data[0] = PlayerTeamState::discriminator();
+			data.append(&mut player_team.to_bytes().to_vec());
+			leak(data)
+		};
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let team_pool_info = AccountInfo::new(
+			team_pool_key,
+			false,
+			true,
+			team_pool_lamports,
+			team_pool_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let player_team_info = AccountInfo::new(
+			player_team_key,
+			false,
+			true,
+			player_team_lamports,
+			player_team_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+
+		[player_info, game_info, team_pool_info, player_team_info]
+	}
+}
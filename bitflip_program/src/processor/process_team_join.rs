@@ -0,0 +1,275 @@
+use steel::*;
+
+use crate::seeds_game;
+use crate::seeds_player_team;
+use crate::seeds_team_pool;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::GameState;
+use crate::PlayerTeamState;
+use crate::Team;
+use crate::TeamPoolState;
+use crate::ID;
+
+/// Join a [`Team`] in a team game.
+///
+/// A player can only join one team per game and must join before the game has
+/// ended. The team pool of the game is created by the first player to join.
+pub fn process_team_join(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = TeamJoin::try_from_bytes(data)?;
+	let team = args.team()?;
+
+	// load accounts
+	let [player_info, game_info, team_pool_info, player_team_info, system_program_info] = accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let game = game_info.as_account::<GameState>(&ID)?;
+	let game_seeds_with_bump = seeds_game!(game.game_index, game.bump);
+	let player_team_seeds = seeds_player_team!(game.game_index, player_info.key);
+	let player_team_bump = player_team_info.assert_canonical_bump(player_team_seeds, &ID)?;
+
+	player_info.assert_signer()?.assert_writable()?;
+	game_info
+		.assert_type::<GameState>(&ID)?
+		.assert_seeds_with_bump(game_seeds_with_bump, &ID)?;
+	team_pool_info.assert_writable()?;
+	player_team_info.assert_empty()?.assert_writable()?;
+	system_program_info.assert_program(&system_program::ID)?;
+
+	game.assert_err(|state| state.teams(), BitflipError::TeamModeDisabled)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	game.assert_err(
+		|state| !state.started(current_time) || state.running(current_time),
+		BitflipError::GameNotRunning,
+	)?;
+
+	if team_pool_info.data_is_empty() {
+		let team_pool_seeds = seeds_team_pool!(game.game_index);
+		let team_pool_bump = team_pool_info.assert_canonical_bump(team_pool_seeds, &ID)?;
+
+		create_account_with_bump::<TeamPoolState>(
+			team_pool_info,
+			system_program_info,
+			player_info,
+			&ID,
+			team_pool_seeds,
+			team_pool_bump,
+		)?;
+
+		let team_pool = team_pool_info.as_account_mut::<TeamPoolState>(&ID)?;
+		*team_pool = TeamPoolState::new(game.game_index, team_pool_bump);
+	}
+
+	let team_pool = team_pool_info.as_account::<TeamPoolState>(&ID)?;
+	let team_pool_seeds_with_bump = seeds_team_pool!(game.game_index, team_pool.bump);
+	team_pool_info
+		.assert_type::<TeamPoolState>(&ID)?
+		.assert_seeds_with_bump(team_pool_seeds_with_bump, &ID)?;
+
+	create_account_with_bump::<PlayerTeamState>(
+		player_team_info,
+		system_program_info,
+		player_info,
+		&ID,
+		player_team_seeds,
+		player_team_bump,
+	)?;
+
+	let player_team = player_team_info.as_account_mut::<PlayerTeamState>(&ID)?;
+	*player_team = PlayerTeamState::new(*player_info.key, game.game_index, team, player_team_bump);
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TeamJoin {
+	/// The [`Team`] being joined.
+	pub team: u8,
+}
+
+impl TeamJoin {
+	pub fn new(team: Team) -> Self {
+		Self { team: team.into() }
+	}
+
+	pub fn team(&self) -> Result<Team, ProgramError> {
+		Team::try_from(self.team).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, TeamJoin);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_game;
+	use crate::get_pda_player_team;
+	use crate::get_pda_team_pool;
+	use crate::leak;
+	use crate::GameFlags;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos(GameFlags::TEAMS);
+		let result = process_team_join(&accounts, &create_args(Team::On));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_team() -> anyhow::Result<()> {
+		let accounts = create_account_infos(GameFlags::TEAMS);
+		let result = process_team_join(&accounts, &[2]);
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn game_should_have_teams() -> anyhow::Result<()> {
+		let accounts = create_account_infos(GameFlags::empty());
+		let result = process_team_join(&accounts, &create_args(Team::Off));
+		check!(result.unwrap_err() == BitflipError::TeamModeDisabled.into());
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos(GameFlags::TEAMS);
+		let result = process_team_join(&accounts[..4], &create_args(Team::On));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(GameFlags::TEAMS);
+		let player_info = &mut accounts[0];
+		player_info.is_signer = false;
+
+		let result = process_team_join(&accounts, &create_args(Team::On));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn player_team_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos(GameFlags::TEAMS);
+		let player_team_info = &mut accounts[3];
+		player_team_info.key = leak(Pubkey::new_unique());
+
+		let result = process_team_join(&accounts, &create_args(Team::On));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(team: Team) -> Vec<u8> {
+		bytemuck::bytes_of(&TeamJoin::new(team)).to_vec()
+	}
+
+	fn create_account_infos<'info>(flags: GameFlags) -> [AccountInfo<'info>; 5] {
+		let game_index = 0;
+		let player = Pubkey::new_unique();
+		let player_key = leak(player);
+		let player_lamports = leak(1_000_000_000);
+		let player_data = leak(vec![]);
+		let game_key = leak(get_pda_game(game_index).0);
+		let game_lamports = leak(0);
+		let game_data = {
+			let game_bump = get_pda_game(game_index).1;
+			let mut game = GameState::new(
+				Pubkey::new_unique(),
+				Pubkey::new_unique(),
+				game_index,
+				game_bump,
+			);
+			game.flags = flags.bits();
+			let mut data = vec![0u8; 8];
+			data[0] = GameState::discriminator();
+			data.append(&mut game.to_bytes().to_vec());
+			leak(data)
+		};
+		let team_pool_key = leak(get_pda_team_pool(game_index).0);
+		let team_pool_lamports = leak(0);
+		let team_pool_data = leak(vec![]);
+		let player_team_key = leak(get_pda_player_team(game_index, &player).0);
+		let player_team_lamports = leak(0);
+		let player_team_data = leak(vec![]);
+		let system_program_lamports = leak(0);
+		let system_program_data = leak(vec![]);
+
+		let player_info = AccountInfo::new(
+			player_key,
+			true,
+			true,
+			player_lamports,
+			player_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let game_info = AccountInfo::new(
+			game_key,
+			false,
+			false,
+			game_lamports,
+			game_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let team_pool_info = AccountInfo::new(
+			team_pool_key,
+			false,
+			true,
+			team_pool_lamports,
+			team_pool_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let player_team_info = AccountInfo::new(
+			player_team_key,
+			false,
+			true,
+			player_team_lamports,
+			player_team_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let system_program_info = AccountInfo::new(
+			&system_program::ID,
+			false,
+			false,
+			system_program_lamports,
+			system_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			player_info,
+			game_info,
+			team_pool_info,
+			player_team_info,
+			system_program_info,
+		]
+	}
+}
@@ -107,6 +107,7 @@ pub fn process_token_group_initialize(accounts: &[AccountInfo]) -> ProgramResult
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TokenGroupInitialize {}
 
 instruction!(BitflipInstruction, TokenGroupInitialize);
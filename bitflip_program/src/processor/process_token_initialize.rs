@@ -19,6 +19,9 @@ use crate::seeds_treasury;
 use crate::BitflipError;
 use crate::BitflipInstruction;
 use crate::ConfigState;
+use crate::BITS_PER_GIBIBIT;
+use crate::BITS_PER_KIBIBIT;
+use crate::BITS_PER_MEBIBIT;
 use crate::BIT_TOKEN_NAME;
 use crate::BIT_TOKEN_SYMBOL;
 use crate::BIT_TOKEN_URI;
@@ -242,6 +245,17 @@ impl TokenMember {
 		}
 	}
 
+	/// The number of bits a single token of the member is worth.
+	#[inline(always)]
+	pub const fn bits(&self) -> u64 {
+		match self {
+			TokenMember::Bit => 1,
+			TokenMember::Kibibit => BITS_PER_KIBIBIT,
+			TokenMember::Mebibit => BITS_PER_MEBIBIT,
+			TokenMember::Gibibit => BITS_PER_GIBIBIT,
+		}
+	}
+
 	#[inline(always)]
 	pub const fn decimals(&self) -> u8 {
 		TOKEN_DECIMALS
@@ -259,6 +273,8 @@ impl TokenMember {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct TokenInitialize {
 	pub member: u8,
 }
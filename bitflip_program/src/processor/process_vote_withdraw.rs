@@ -0,0 +1,357 @@
+use solana_program::msg;
+use steel::*;
+
+use crate::cpi::transfer_checked;
+use crate::seeds_config;
+use crate::seeds_mint;
+use crate::seeds_proposal;
+use crate::seeds_vote;
+use crate::BitflipError;
+use crate::BitflipInstruction;
+use crate::ConfigState;
+use crate::ProposalState;
+use crate::TokenMember;
+use crate::VoteState;
+use crate::ID;
+use crate::SEED_PREFIX;
+use crate::SEED_VOTE;
+use crate::TOKEN_DECIMALS;
+
+/// Return the tokens of a [`TokenMember`] escrowed by a vote once the voting
+/// period of the proposal has ended.
+pub fn process_vote_withdraw(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+	// parse the instruction data.
+	let args = VoteWithdraw::try_from_bytes(data)?;
+	let member = args.member()?;
+
+	// load accounts
+	let [voter_info, voter_token_account_info, config_info, mint_info, proposal_info, vote_info, vote_token_account_info, token_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let config = config_info.as_account::<ConfigState>(&ID)?;
+	let proposal = proposal_info.as_account::<ProposalState>(&ID)?;
+	let vote = vote_info.as_account_mut::<VoteState>(&ID)?;
+	let config_seeds_with_bump = seeds_config!(config.bump);
+	let mint_seeds_with_bump = seeds_mint!(member, member.bump(config));
+	let proposal_seeds_with_bump = seeds_proposal!(
+		proposal.game_index,
+		proposal.proposer,
+		proposal.proposal_index(),
+		proposal.bump
+	);
+	let vote_seeds_with_bump = seeds_vote!(proposal_info.key, voter_info.key, vote.bump);
+
+	voter_info.assert_signer()?;
+	voter_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(voter_info.key, mint_info.key)?;
+	config_info
+		.assert_type::<ConfigState>(&ID)?
+		.assert_seeds_with_bump(config_seeds_with_bump, &ID)?;
+	mint_info.assert_seeds_with_bump(mint_seeds_with_bump, &ID)?;
+	proposal_info
+		.assert_type::<ProposalState>(&ID)?
+		.assert_seeds_with_bump(proposal_seeds_with_bump, &ID)?;
+	vote_info
+		.assert_type::<VoteState>(&ID)?
+		.assert_writable()?
+		.assert_seeds_with_bump(vote_seeds_with_bump, &ID)?;
+	vote_token_account_info
+		.assert_writable()?
+		.assert_associated_token_address(vote_info.key, mint_info.key)?;
+	token_program_info.assert_program(&spl_token_2022::ID)?;
+
+	let current_time = Clock::get()?.unix_timestamp;
+	proposal.assert_err(
+		|state| !state.voting(current_time),
+		BitflipError::ProposalActive,
+	)?;
+
+	let amount = vote.withdraw(member);
+	msg!("returning escrowed tokens: {}", amount);
+	let signer = &[
+		SEED_PREFIX,
+		SEED_VOTE,
+		proposal_info.key.as_ref(),
+		voter_info.key.as_ref(),
+		&[vote.bump],
+	];
+	transfer_checked(
+		vote_token_account_info,
+		mint_info,
+		voter_token_account_info,
+		vote_info,
+		token_program_info,
+		amount,
+		TOKEN_DECIMALS,
+		&[&signer[..]],
+	)?;
+
+	Ok(())
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct VoteWithdraw {
+	/// The [`TokenMember`] being withdrawn.
+	pub member: u8,
+}
+
+impl VoteWithdraw {
+	pub fn new(member: TokenMember) -> Self {
+		Self {
+			member: member.into(),
+		}
+	}
+
+	pub fn member(&self) -> Result<TokenMember, ProgramError> {
+		TokenMember::try_from(self.member).map_err(|_| ProgramError::InvalidInstructionData)
+	}
+}
+
+instruction!(BitflipInstruction, VoteWithdraw);
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_sdk::native_loader;
+
+	use super::*;
+	use crate::get_pda_config;
+	use crate::get_pda_mint;
+	use crate::get_pda_proposal;
+	use crate::get_pda_treasury;
+	use crate::get_pda_vote;
+	use crate::get_player_token_account;
+	use crate::get_vote_token_account;
+	use crate::leak;
+	use crate::GameParameter;
+
+	#[test_log::test]
+	fn should_pass_validation() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_vote_withdraw(&accounts, &create_args(TokenMember::Bit));
+		check!(result.unwrap_err() == ProgramError::UnsupportedSysvar);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_valid_member() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let mut args = VoteWithdraw::new(TokenMember::Bit);
+		args.member = u8::MAX;
+
+		let result = process_vote_withdraw(&accounts, bytemuck::bytes_of(&args));
+		check!(result.unwrap_err() == ProgramError::InvalidInstructionData);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn should_have_enough_accounts() -> anyhow::Result<()> {
+		let accounts = create_account_infos();
+		let result = process_vote_withdraw(&accounts[..7], &create_args(TokenMember::Bit));
+		check!(result.unwrap_err() == ProgramError::NotEnoughAccountKeys);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn voter_should_be_signer() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let voter_info = &mut accounts[0];
+		voter_info.is_signer = false;
+
+		let result = process_vote_withdraw(&accounts, &create_args(TokenMember::Bit));
+		check!(result.unwrap_err() == ProgramError::MissingRequiredSignature);
+
+		Ok(())
+	}
+
+	#[test_log::test]
+	fn vote_should_be_pda() -> anyhow::Result<()> {
+		let mut accounts = create_account_infos();
+		let vote_info = &mut accounts[5];
+		vote_info.key = leak(Pubkey::new_unique());
+
+		let result = process_vote_withdraw(&accounts, &create_args(TokenMember::Bit));
+		check!(result.unwrap_err() == ProgramError::InvalidSeeds);
+
+		Ok(())
+	}
+
+	fn create_args(member: TokenMember) -> Vec<u8> {
+		bytemuck::bytes_of(&VoteWithdraw::new(member)).to_vec()
+	}
+
+	fn create_account_infos<'info>() -> [AccountInfo<'info>; 8] {
+		let game_index = 0;
+		let member = TokenMember::Bit;
+		let voter = Pubkey::new_unique();
+		let proposer = Pubkey::new_unique();
+		let proposal = get_pda_proposal(game_index, &proposer, 0);
+		let voter_key = leak(voter);
+		let voter_lamports = leak(1_000_000_000);
+		let voter_data = leak(vec![]);
+		let voter_token_account_key = leak(get_player_token_account(&voter, member));
+		let voter_token_account_lamports = leak(0);
+		let voter_token_account_data = leak(vec![]);
+		let config_key = leak(get_pda_config().0);
+		let config_lamports = leak(0);
+		let config_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ConfigState::discriminator();
+			data.append(
+				&mut ConfigState::new(
+					Pubkey::new_unique(),
+					get_pda_config().1,
+					get_pda_treasury().1,
+					get_pda_mint(TokenMember::Bit).1,
+					get_pda_mint(TokenMember::Kibibit).1,
+					get_pda_mint(TokenMember::Mebibit).1,
+					get_pda_mint(TokenMember::Gibibit).1,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let mint_key = leak(get_pda_mint(member).0);
+		let mint_lamports = leak(0);
+		let mint_data = leak(vec![]);
+		let proposal_key = leak(proposal.0);
+		let proposal_lamports = leak(0);
+		let proposal_data = {
+			let mut data = vec![0u8; 8];
+			data[0] = ProposalState::discriminator();
+			data.append(
+				&mut ProposalState::new(
+					proposer,
+					game_index,
+					0,
+					GameParameter::Duration,
+					3600,
+					0,
+					proposal.1,
+				)
+				.to_bytes()
+				.to_vec(),
+			);
+			leak(data)
+		};
+		let vote_key = leak(get_pda_vote(&proposal.0, &voter).0);
+		let vote_lamports = leak(0);
+		let vote_data = {
+			let mut vote =
+				VoteState::new(voter, proposal.0, true, get_pda_vote(&proposal.0, &voter).1);
+			vote.deposit(member, 10).unwrap();
+			let mut data = vec![0u8; 8];
+			data[0] = VoteState::discriminator();
+			data.append(&mut vote.to_bytes().to_vec());
+			leak(data)
+		};
+		let vote_token_account_key = leak(get_vote_token_account(&proposal.0, &voter, member));
+		let vote_token_account_lamports = leak(0);
+		let vote_token_account_data = leak(vec![]);
+		let token_program_lamports = leak(0);
+		let token_program_data = leak(vec![]);
+
+		let voter_info = AccountInfo::new(
+			voter_key,
+			true,
+			true,
+			voter_lamports,
+			voter_data,
+			&system_program::ID,
+			false,
+			u64::MAX,
+		);
+		let voter_token_account_info = AccountInfo::new(
+			voter_token_account_key,
+			false,
+			true,
+			voter_token_account_lamports,
+			voter_token_account_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let config_info = AccountInfo::new(
+			config_key,
+			false,
+			false,
+			config_lamports,
+			config_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let mint_info = AccountInfo::new(
+			mint_key,
+			false,
+			false,
+			mint_lamports,
+			mint_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let proposal_info = AccountInfo::new(
+			proposal_key,
+			false,
+			false,
+			proposal_lamports,
+			proposal_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let vote_info = AccountInfo::new(
+			vote_key,
+			false,
+			true,
+			vote_lamports,
+			vote_data,
+			&ID,
+			false,
+			u64::MAX,
+		);
+		let vote_token_account_info = AccountInfo::new(
+			vote_token_account_key,
+			false,
+			true,
+			vote_token_account_lamports,
+			vote_token_account_data,
+			&spl_token_2022::ID,
+			false,
+			u64::MAX,
+		);
+		let token_program_info = AccountInfo::new(
+			&spl_token_2022::ID,
+			false,
+			false,
+			token_program_lamports,
+			token_program_data,
+			&native_loader::ID,
+			true,
+			u64::MAX,
+		);
+
+		[
+			voter_info,
+			voter_token_account_info,
+			config_info,
+			mint_info,
+			proposal_info,
+			vote_info,
+			vote_token_account_info,
+			token_program_info,
+		]
+	}
+}
@@ -1,20 +1,58 @@
+use bitflags::bitflags;
 use fixed::types::U64F64;
-use solana_program::msg;
 use spl_pod::primitives::PodI64;
+use spl_pod::primitives::PodU128;
 use spl_pod::primitives::PodU16;
 use spl_pod::primitives::PodU32;
 use spl_pod::primitives::PodU64;
 use static_assertions::const_assert;
 use steel::*;
 
+use crate::debug_msg;
+use crate::get_coordinates;
+use crate::get_index_offset;
+use crate::get_next_generation;
+use crate::get_region_mask;
+use crate::get_section_data;
+use crate::get_section_rows;
+use crate::BitflipError;
 use crate::FlipBit;
+use crate::SectionMetadataUpdate;
+use crate::TokenMember;
 use crate::BASE_LAMPORTS_PER_BIT;
+use crate::BASE_LAMPORTS_PER_STEP;
 use crate::BITFLIP_SECTION_LENGTH;
 use crate::BITFLIP_SECTION_TOTAL_BITS;
+use crate::BITFLIP_SECTION_WIDTH;
+use crate::BITFLIP_TOTAL_SECTIONS;
+use crate::DEFAULT_SECTION_BACKGROUND;
+use crate::DEFAULT_SECTION_FOREGROUND;
 use crate::EARNED_TOKENS_PER_SECTION;
+use crate::FIRST_FLIP_BADGE_NAME;
+use crate::FIRST_FLIP_BADGE_SYMBOL;
+use crate::FIRST_FLIP_BADGE_URI;
+use crate::GOVERNANCE_QUORUM;
+use crate::INHERIT_CLAIM_DURATION;
+use crate::INHERIT_DISCOUNT_BASIS_POINTS;
 use crate::MAX_LAMPORTS_PER_BIT;
+use crate::MAX_SECTION_LOCKS;
+use crate::MINIMUM_FLIPS_PER_SECTION;
 use crate::MIN_LAMPORTS_PER_BIT;
+use crate::PLAYER_FLIPS_BADGE_NAME;
+use crate::PLAYER_FLIPS_BADGE_SYMBOL;
+use crate::PLAYER_FLIPS_BADGE_URI;
+use crate::PLAYER_FLIPS_MILESTONE;
+use crate::SECTION_LINK_MAX_LENGTH;
+use crate::SECTION_THRESHOLD_BADGE_NAME;
+use crate::SECTION_THRESHOLD_BADGE_SYMBOL;
+use crate::SECTION_THRESHOLD_BADGE_URI;
+use crate::SECTION_TITLE_MAX_LENGTH;
+use crate::SEED_FIRST_FLIP_BADGE_MINT;
+use crate::SEED_PLAYER_FLIPS_BADGE_MINT;
+use crate::SEED_SECTION_THRESHOLD_BADGE_MINT;
 use crate::SESSION_DURATION;
+use crate::STAKE_REWARD_PRECISION;
+use crate::TEAM_REVENUE_BASIS_POINTS;
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
@@ -22,15 +60,54 @@ pub enum BitflipAccount {
 	ConfigState = 0,
 	GameState = 1,
 	SectionState = 2,
+	SectionMinesState = 3,
+	BountyState = 4,
+	GameCanvasState = 5,
+	SectionMetadataState = 6,
+	PlayerState = 7,
+	SectionCooldownState = 8,
+	SectionLocksState = 9,
+	StakePoolState = 10,
+	StakeState = 11,
+	ProposalState = 12,
+	VoteState = 13,
+	TeamPoolState = 14,
+	PlayerTeamState = 15,
 }
 
 const_assert!(ConfigState::space() == 80);
-const_assert!(GameState::space() == 149);
+const_assert!(GameState::space() == 181);
 const_assert!(SectionState::space() == 600);
+const_assert!(SectionMinesState::space() == 1621);
+const_assert!(BountyState::space() == 610);
+const_assert!(GameCanvasState::space() == 8237);
+const_assert!(SectionMetadataState::space() == 181);
+const_assert!(PlayerState::space() == 81);
+const_assert!(SectionCooldownState::space() == 1068);
+const_assert!(SectionLocksState::space() == 396);
+const_assert!(StakePoolState::space() == 76);
+const_assert!(StakeState::space() == 146);
+const_assert!(ProposalState::space() == 111);
+const_assert!(VoteState::space() == 147);
+const_assert!(TeamPoolState::space() == 83);
+const_assert!(PlayerTeamState::space() == 85);
 
 account!(BitflipAccount, ConfigState);
 account!(BitflipAccount, GameState);
 account!(BitflipAccount, SectionState);
+account!(BitflipAccount, SectionMinesState);
+account!(BitflipAccount, BountyState);
+account!(BitflipAccount, GameCanvasState);
+account!(BitflipAccount, SectionMetadataState);
+account!(BitflipAccount, PlayerState);
+account!(BitflipAccount, SectionCooldownState);
+account!(BitflipAccount, SectionLocksState);
+account!(BitflipAccount, StakePoolState);
+account!(BitflipAccount, StakeState);
+account!(BitflipAccount, ProposalState);
+account!(BitflipAccount, VoteState);
+account!(BitflipAccount, TeamPoolState);
+account!(BitflipAccount, PlayerTeamState);
 
 pub trait AccountVersion: Pod {
 	/// The latest version of the account which should be used as the migration
@@ -118,6 +195,57 @@ pub enum GameStatus {
 	Ended = 2,
 }
 
+/// The parameters of a [`GameState`] which can be updated by the config
+/// authority or by a passed governance proposal.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameParameter {
+	/// The minimum price of flipping a bit.
+	MinLamports = 0,
+	/// The initial price of flipping a bit.
+	BaseLamports = 1,
+	/// The maximum price of flipping a bit.
+	MaxLamports = 2,
+	/// The duration of the game in seconds.
+	Duration = 3,
+	/// The price of stepping a section forward by one generation.
+	StepLamports = 4,
+	/// The number of slots a flipped word is cooling down for.
+	CooldownSlots = 5,
+	/// The price multiplier for flipping a word which is cooling down.
+	CooldownMultiplier = 6,
+}
+
+/// How the cells on the border of a section are treated when stepping the
+/// section with Conway's Game of Life.
+#[repr(u8)]
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+pub enum SectionEdge {
+	/// Cells outside of the section are always dead.
+	#[default]
+	Dead = 0,
+	/// The section wraps around so the opposite borders are neighbours.
+	Torus = 1,
+}
+
+bitflags! {
+	/// The optional game modes which are enabled for a [`GameState`].
+	#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+	pub struct GameFlags: u8 {
+		/// Each section has a hidden layout of mines which is committed when the
+		/// section is unlocked and revealed once the game has ended.
+		const MINESWEEPER = 1 << 0;
+		/// Players join the [`Team`] of `on` or `off` bits and the team holding
+		/// the majority of bits when the game ends shares the [`TeamPoolState`].
+		const TEAMS = 1 << 1;
+		/// Each section starts with the final data of the same section in the
+		/// previous game and its previous owner has the right of first refusal
+		/// to unlock it at a discount.
+		const INHERIT = 1 << 2;
+	}
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -171,15 +299,48 @@ pub struct GameState {
 	pub section_index: u8,
 	/// The bump for this account.
 	pub bump: u8,
+	/// The [`GameFlags`] which are enabled for this game.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub flags: u8,
+	/// The price of stepping a section forward by one generation.
+	#[cfg_attr(feature = "client", builder(default = BASE_LAMPORTS_PER_STEP.into(), setter(into)))]
+	pub step_lamports: PodU64,
+	/// The number of slots after a flip during which the same `u16` word of a
+	/// section is cooling down. The cooldown is disabled when this is `0`.
+	#[cfg_attr(feature = "client", builder(default, setter(into)))]
+	pub cooldown_slots: PodU32,
+	/// The price multiplier for flipping a word which is cooling down. The flip
+	/// is rejected with [`BitflipError::BitCooldown`] when this is `0`.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub cooldown_multiplier: u8,
+	/// Whether the first flip of the game has been made. This is `1` once a
+	/// player earned the [`BadgeType::FirstFlip`] badge.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub flipped: u8,
 	/// Extra space for future use.
 	#[cfg_attr(feature = "client", builder(default))]
-	pub _padding: [u8; 32],
+	pub _padding: [u8; 17],
+	/// The merkle root of the final canvas which is set once the game has been
+	/// finalized. See [`crate::get_canvas_root`]. This doesn't fit into the
+	/// padding and extends the account from version `1`.
+	#[cfg_attr(feature = "client", builder(default))]
+	pub canvas_root: [u8; 32],
 }
 
 impl AccountVersion for GameState {
-	const VERSION: u8 = 0;
+	/// - `1`: adds `step_lamports` and extends the account by `canvas_root`.
+	const VERSION: u8 = 1;
 
 	fn migrate(&mut self) -> Result<(), ProgramError> {
+		if self.version < 1 {
+			self.step_lamports = BASE_LAMPORTS_PER_STEP.into();
+			// flips of a game which started before the migration weren't
+			// tracked so the first flip can't be earned anymore.
+			self.flipped = u8::from(self.start_time() > 0);
+		}
+
+		self.version = Self::VERSION;
+
 		Ok(())
 	}
 }
@@ -199,7 +360,13 @@ impl GameState {
 			section_index: 0,
 			game_index: index,
 			bump,
-			_padding: [0; 32],
+			flags: GameFlags::empty().bits(),
+			step_lamports: BASE_LAMPORTS_PER_STEP.into(),
+			cooldown_slots: 0.into(),
+			cooldown_multiplier: 0,
+			flipped: 0,
+			_padding: [0; 17],
+			canvas_root: [0; 32],
 		}
 	}
 
@@ -228,11 +395,68 @@ impl GameState {
 		self.max_lamports.into()
 	}
 
+	#[inline(always)]
+	pub fn step_lamports(&self) -> u64 {
+		self.step_lamports.into()
+	}
+
 	#[inline(always)]
 	pub fn status(&self) -> GameStatus {
 		GameStatus::try_from(self.status).unwrap()
 	}
 
+	#[inline(always)]
+	pub fn flags(&self) -> GameFlags {
+		GameFlags::from_bits_truncate(self.flags)
+	}
+
+	/// Whether the sections of this game contain hidden mines.
+	#[inline(always)]
+	pub fn minesweeper(&self) -> bool {
+		self.flags().contains(GameFlags::MINESWEEPER)
+	}
+
+	#[inline(always)]
+	pub fn cooldown_slots(&self) -> u32 {
+		self.cooldown_slots.into()
+	}
+
+	/// Whether players compete in teams of `on` and `off` bits.
+	#[inline(always)]
+	pub fn teams(&self) -> bool {
+		self.flags().contains(GameFlags::TEAMS)
+	}
+
+	/// Whether the first flip of the game has been made.
+	#[inline(always)]
+	pub fn flipped(&self) -> bool {
+		self.flipped != 0
+	}
+
+	/// Whether the sections inherit the canvas of the previous game.
+	#[inline(always)]
+	pub fn inherit(&self) -> bool {
+		self.flags().contains(GameFlags::INHERIT)
+	}
+
+	/// Whether the sections of an inheriting game are still reserved for their
+	/// previous owners.
+	pub fn reserved(&self, current_time: i64) -> bool {
+		self.inherit() && current_time < self.start_time().saturating_add(INHERIT_CLAIM_DURATION)
+	}
+
+	/// The discounted bid paid by a previous owner re-claiming their section.
+	pub fn inherit_lamports(lamports: u64) -> u64 {
+		let discount = u128::from(lamports) * u128::from(INHERIT_DISCOUNT_BASIS_POINTS) / 10_000;
+		lamports - discount as u64
+	}
+
+	/// Whether recently flipped words of a section are cooling down.
+	#[inline(always)]
+	pub fn cooldown(&self) -> bool {
+		self.cooldown_slots() > 0
+	}
+
 	/// The end time of the game.
 	#[inline(always)]
 	pub fn end_time(&self) -> i64 {
@@ -244,19 +468,82 @@ impl GameState {
 		self.end_time().saturating_sub(current_time)
 	}
 
+	/// Whether the start time of the game has passed. A game which was
+	/// scheduled with [`GameState::schedule`] starts without any further
+	/// transaction once the start time is reached.
+	pub fn started(&self, current_time: i64) -> bool {
+		self.start_time() > 0 && self.start_time() <= current_time
+	}
+
+	/// Whether the game is waiting for a future start time.
+	pub fn scheduled(&self, current_time: i64) -> bool {
+		self.status() == GameStatus::Running && self.start_time() > current_time
+	}
+
 	/// Whether the game is running.
 	pub fn running(&self, current_time: i64) -> bool {
-		self.start_time() > 0 && self.status() == GameStatus::Running && !self.ended(current_time)
+		self.started(current_time)
+			&& self.status() == GameStatus::Running
+			&& !self.ended(current_time)
 	}
 
-	/// Whether the game has ended.
+	/// Whether the game has ended. A game which hasn't started yet can't have
+	/// ended, even though its end time is in the past.
 	pub fn ended(&self, current_time: i64) -> bool {
-		current_time > self.end_time() || self.status() == GameStatus::Ended
+		self.status() == GameStatus::Ended
+			|| (self.started(current_time) && current_time > self.end_time())
+	}
+
+	/// Whether the merkle root of the final canvas has been stored.
+	pub fn finalized(&self) -> bool {
+		self.canvas_root != [0; 32]
 	}
 
 	pub fn start(&mut self, current_time: i64) {
+		self.schedule(current_time);
+	}
+
+	/// Set the time at which the game starts running.
+	pub fn schedule(&mut self, start_time: i64) {
 		self.status = GameStatus::Running.into();
-		self.start_time = current_time.into();
+		self.start_time = start_time.into();
+	}
+
+	/// Update a [`GameParameter`]. This is the shared code path of the
+	/// authority update and the execution of a passed governance proposal. The
+	/// game is left unchanged when the updated price bounds or duration are
+	/// invalid.
+	pub fn update_parameter(&mut self, parameter: GameParameter, value: u64) -> ProgramResult {
+		let invalid = |_| BitflipError::GameParameterInvalid;
+		let mut updated = *self;
+
+		match parameter {
+			GameParameter::MinLamports => updated.min_lamports = value.into(),
+			GameParameter::BaseLamports => updated.base_lamports = value.into(),
+			GameParameter::MaxLamports => updated.max_lamports = value.into(),
+			GameParameter::Duration => {
+				updated.duration = i64::try_from(value).map_err(invalid)?.into();
+			}
+			GameParameter::StepLamports => updated.step_lamports = value.into(),
+			GameParameter::CooldownSlots => {
+				updated.cooldown_slots = u32::try_from(value).map_err(invalid)?.into();
+			}
+			GameParameter::CooldownMultiplier => {
+				updated.cooldown_multiplier = u8::try_from(value).map_err(invalid)?;
+			}
+		}
+
+		if updated.min_lamports() == 0
+			|| updated.min_lamports() > updated.base_lamports()
+			|| updated.base_lamports() > updated.max_lamports()
+			|| updated.duration() <= 0
+		{
+			return Err(BitflipError::GameParameterInvalid.into());
+		}
+
+		*self = updated;
+
+		Ok(())
 	}
 
 	/// Increment the section index, without overflowing.
@@ -269,6 +556,8 @@ impl GameState {
 
 pub type PodSectionData = [PodU16; BITFLIP_SECTION_LENGTH];
 pub type SectionData = [u16; BITFLIP_SECTION_LENGTH];
+pub type PodSectionRows = [PodU64; BITFLIP_SECTION_WIDTH as usize];
+pub type CanvasLeaves = [[u8; 32]; BITFLIP_TOTAL_SECTIONS];
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
@@ -299,8 +588,12 @@ pub struct SectionState {
 	pub section_index: u8,
 	/// The bump for this section state.
 	pub bump: u8,
+	/// The number of bits changed by stepping the section with
+	/// [`crate::SectionStep`]. These are kept out of the `flips` since no
+	/// tokens are earned for them and they don't affect the price of a bit.
+	pub steps: PodU32,
 	/// Extra space for future versions.
-	pub _padding: [u8; 32],
+	pub _padding: [u8; 28],
 }
 
 impl AccountVersion for SectionState {
@@ -324,7 +617,8 @@ impl SectionState {
 			bump,
 			game_index,
 			section_index,
-			_padding: [0; 32],
+			steps: 0.into(),
+			_padding: [0; 28],
 		}
 	}
 
@@ -339,6 +633,40 @@ impl SectionState {
 		self.on = 0.into();
 		self.off = BITFLIP_SECTION_TOTAL_BITS.into();
 		self.flips = 0.into();
+		self.steps = 0.into();
+	}
+
+	/// Write the `words` into the section data starting at the word index
+	/// `start`. The `on` and `off` counts are recomputed from the data while
+	/// the `flips` are left untouched since seeding isn't played by anyone.
+	pub fn seed(&mut self, start: usize, words: &[u16]) -> ProgramResult {
+		let end = start
+			.checked_add(words.len())
+			.filter(|end| *end <= BITFLIP_SECTION_LENGTH)
+			.ok_or(BitflipError::SectionSeedRangeInvalid)?;
+
+		for (value, word) in self.data[start..end].iter_mut().zip(words) {
+			*value = (*word).into();
+		}
+
+		let on = self
+			.data
+			.iter()
+			.map(|value| u16::from(*value).count_ones())
+			.sum::<u32>();
+		self.on = on.into();
+		self.off = (BITFLIP_SECTION_TOTAL_BITS - on).into();
+
+		Ok(())
+	}
+
+	/// Copy the final data of the same section in the previous game. The
+	/// `flips` are left untouched since the copied bits weren't played in this
+	/// game.
+	pub fn inherit(&mut self, previous: &SectionState) {
+		self.data = previous.data;
+		self.on = previous.on;
+		self.off = previous.off;
 	}
 
 	/// Whether the bit at the given index and offset is `1`.
@@ -359,6 +687,10 @@ impl SectionState {
 		self.flips.into()
 	}
 
+	pub fn steps(&self) -> u32 {
+		self.steps.into()
+	}
+
 	pub fn flip_on(&mut self, changed_bits: u32) -> ProgramResult {
 		self.on = self
 			.on()
@@ -403,7 +735,7 @@ impl SectionState {
 	///
 	/// Returns true if the bit was toggled.
 	pub fn set_bit(&mut self, args: &FlipBit) -> Result<bool, ProgramError> {
-		msg!("set_bit: {:?}", args);
+		debug_msg!("set_bit: {:?}", args);
 
 		let index = args.array_index as usize;
 		let current: u16 = self.data[index].into();
@@ -422,20 +754,59 @@ impl SectionState {
 		Ok(true)
 	}
 
+	/// Advance the section by one generation of Conway's Game of Life.
+	///
+	/// Every bit which changes is counted in the `steps` instead of the
+	/// `flips`. Returns the number of changed bits.
+	pub fn step(&mut self, edge: SectionEdge) -> Result<u32, ProgramError> {
+		let current: SectionData = self.data.map(Into::into);
+		let next = get_section_data(&get_next_generation(&get_section_rows(&current), edge));
+		let (mut on, mut changed) = (0, 0);
+
+		for (current, next) in current.iter().zip(next.iter()) {
+			on += next.count_ones();
+			changed += (current ^ next).count_ones();
+		}
+
+		self.data = next.map(Into::into);
+		self.on = on.into();
+		self.off = (BITFLIP_SECTION_TOTAL_BITS - on).into();
+		self.steps = self.steps().saturating_add(changed).into();
+
+		Ok(changed)
+	}
+
 	/// Get the price of a bit in lamports.
-	pub fn get_token_price_in_lamports(&self, remaining_time: i64) -> u64 {
-		let flips: u64 = self.flips().into();
-		let remaining_flips = EARNED_TOKENS_PER_SECTION - flips;
-		let elapsed_time = SESSION_DURATION - remaining_time;
+	///
+	/// The price starts at the `base_lamports` of the `game` and is kept
+	/// within its `min_lamports` and `max_lamports`, so the price bounds can
+	/// be changed by the authority or by governance.
+	///
+	/// Flips which don't earn a token, e.g. paid with tokens or hitting a mine,
+	/// can push the `flips` past [`EARNED_TOKENS_PER_SECTION`]. The price stops
+	/// rising at that cap and is the static price of the cap.
+	pub fn get_token_price_in_lamports(&self, game: &GameState, remaining_time: i64) -> u64 {
+		self.get_scaled_price(game.base_lamports(), remaining_time)
+			.max(game.min_lamports())
+			.min(game.max_lamports())
+	}
+
+	/// Scale the `base_lamports` by the flips of the section and by how fast
+	/// the section is being flipped compared to the rate required to reach
+	/// [`EARNED_TOKENS_PER_SECTION`] before the game ends.
+	fn get_scaled_price(&self, base_lamports: u64, remaining_time: i64) -> u64 {
+		let flips = u64::from(self.flips()).min(EARNED_TOKENS_PER_SECTION);
+		let remaining_flips = EARNED_TOKENS_PER_SECTION.saturating_sub(flips);
+		let elapsed_time = SESSION_DURATION.saturating_sub(remaining_time);
 		let Some(static_price) = U64F64::from_num(flips)
 			.checked_sqrt()
 			.and_then(|val| val.checked_mul_int(512))
-			.and_then(|val| val.checked_add(BASE_LAMPORTS_PER_BIT.into()))
+			.and_then(|val| val.checked_add(base_lamports.into()))
 		else {
-			return BASE_LAMPORTS_PER_BIT;
+			return base_lamports;
 		};
 
-		if elapsed_time == 0 || remaining_time == 0 {
+		if elapsed_time == 0 || remaining_time == 0 || remaining_flips == 0 {
 			return static_price.to_num();
 		}
 
@@ -455,78 +826,2586 @@ impl SectionState {
 			return static_price.to_num();
 		};
 
-		let price = static_price
+		static_price
 			.checked_mul(ratio.sqrt())
 			.unwrap_or(static_price)
-			.to_num::<u64>();
+			.to_num::<u64>()
+	}
+}
 
-		price.max(MIN_LAMPORTS_PER_BIT)
+#[repr(u8)]
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+pub enum MinesStatus {
+	/// The mines are hidden and only the commitment is known.
+	#[default]
+	Committed = 0,
+	/// The mines were revealed and matched the commitment.
+	Revealed = 1,
+}
+
+/// What is known about a revealed cell of a minesweeper section.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RevealedCell {
+	/// The backend reported the cell as a mine, or the revealed layout shows
+	/// it is one.
+	Mine,
+	/// The cell isn't a mine. The number of surrounding mines is only known
+	/// once the layout is revealed after the game ends.
+	Safe,
+	/// The cell isn't a mine and is surrounded by the given number of mines.
+	Adjacent(u8),
+}
+
+/// The hidden mines for a section within a minesweeper game.
+///
+/// The layout of the mines is only known to the backend while the game is
+/// running. Each flip reveals a cell and the backend reports whether it was a
+/// mine by co-signing the flip with the temp signer. Once the game ends the
+/// layout is revealed and checked against the `commitment`. The recorded
+/// `hits` can then be audited against the layout with
+/// [`SectionMinesState::hits_match`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionMinesState {
+	/// The version of the state.
+	pub version: u8,
+	/// The hash of the mine layout and a secret salt. See
+	/// [`crate::get_mines_commitment`].
+	pub commitment: [u8; 32],
+	/// The layout of the mines. This is empty until the mines are revealed.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub mines: PodSectionData,
+	/// The cells which have been revealed by a flip.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub revealed: PodSectionData,
+	/// The revealed cells which were reported as mines.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub hits: PodSectionData,
+	/// The lamports forfeited to the section by flips which revealed a mine.
+	pub pot: PodU64,
+	/// The [`MinesStatus`] of the layout.
+	pub status: u8,
+	/// The index for this game this section is a part of.
+	pub game_index: u8,
+	/// The index for the section.
+	pub section_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for SectionMinesState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
 	}
 }
 
-#[cfg(test)]
-mod tests {
-	use std::thread;
+impl SectionMinesState {
+	/// Create a new section mines state in the client. Useful for testing.
+	pub fn new(commitment: [u8; 32], game_index: u8, section_index: u8, bump: u8) -> Self {
+		Self {
+			version: SectionMinesState::VERSION,
+			commitment,
+			mines: [0.into(); BITFLIP_SECTION_LENGTH],
+			revealed: [0.into(); BITFLIP_SECTION_LENGTH],
+			hits: [0.into(); BITFLIP_SECTION_LENGTH],
+			pot: 0.into(),
+			status: MinesStatus::Committed.into(),
+			game_index,
+			section_index,
+			bump,
+			_padding: [0; 32],
+		}
+	}
 
-	use rstest::fixture;
-	use rstest::rstest;
+	/// Initialize the mines without touching the data to prevent using compute
+	/// units.
+	pub fn init(&mut self, commitment: [u8; 32], game_index: u8, section_index: u8, bump: u8) {
+		self.version = SectionMinesState::VERSION;
+		self.commitment = commitment;
+		self.pot = 0.into();
+		self.status = MinesStatus::Committed.into();
+		self.game_index = game_index;
+		self.section_index = section_index;
+		self.bump = bump;
+	}
 
-	use super::*;
+	#[inline(always)]
+	pub fn pot(&self) -> u64 {
+		self.pot.into()
+	}
 
-	macro_rules! set_snapshot_suffix {
-    ($($expr:expr),*) => {
-        let mut settings = insta::Settings::clone_current();
-        settings.set_snapshot_suffix(format!($($expr,)*));
-        let _guard = settings.bind_to_scope();
+	#[inline(always)]
+	pub fn status(&self) -> MinesStatus {
+		MinesStatus::try_from(self.status).unwrap_or_default()
+	}
+
+	/// Whether the cell at the given index and offset has been revealed.
+	pub fn is_revealed(&self, index: u8, offset: u8) -> bool {
+		let value: u16 = self.revealed[index as usize].into();
+		(value & (1 << offset)) != 0
+	}
+
+	/// Whether the cell at the given index and offset is a mine. This is always
+	/// `false` until the mines have been revealed.
+	pub fn is_mine(&self, index: u8, offset: u8) -> bool {
+		let value: u16 = self.mines[index as usize].into();
+		(value & (1 << offset)) != 0
+	}
+
+	/// Whether the backend reported the cell at the given index and offset as a
+	/// mine when it was revealed.
+	pub fn is_hit(&self, index: u8, offset: u8) -> bool {
+		let value: u16 = self.hits[index as usize].into();
+		(value & (1 << offset)) != 0
+	}
+
+	/// The [`RevealedCell`] at the given index and offset or `None` when the
+	/// cell hasn't been revealed. The hits are known while the game is running
+	/// but the adjacent mines are only counted once the layout is revealed.
+	pub fn revealed_cell(&self, index: u8, offset: u8) -> Option<RevealedCell> {
+		if !self.is_revealed(index, offset) {
+			return None;
+		}
+
+		if self.is_hit(index, offset) || self.is_mine(index, offset) {
+			return Some(RevealedCell::Mine);
 		}
+
+		if self.status() != MinesStatus::Revealed {
+			return Some(RevealedCell::Safe);
+		}
+
+		let (x, y) = get_coordinates(index, offset);
+
+		Some(RevealedCell::Adjacent(self.adjacent_mines(x, y)))
 	}
 
-	#[fixture]
-	pub fn testname() -> String {
-		thread::current()
-			.name()
-			.unwrap()
-			.split("::")
-			.last()
-			.unwrap()
-			.split("_")
-			.skip(2)
-			.collect::<Vec<&str>>()
-			.join("_")
-			.to_string()
+	/// Record the cell flipped by the instruction as revealed. When the backend
+	/// has reported the cell as a mine the `lamports` paid for the flip are
+	/// added to the pot.
+	pub fn reveal(&mut self, args: &FlipBit, lamports: u64) -> ProgramResult {
+		let index = args.array_index as usize;
+		let bit = 1 << args.offset;
+		let revealed: u16 = self.revealed[index].into();
+		self.revealed[index] = (revealed | bit).into();
+
+		if !args.mine() {
+			return Ok(());
+		}
+
+		let hits: u16 = self.hits[index].into();
+		self.hits[index] = (hits | bit).into();
+		self.pot = self
+			.pot()
+			.checked_add(lamports)
+			.ok_or(ProgramError::ArithmeticOverflow)?
+			.into();
+
+		Ok(())
 	}
 
-	#[rstest]
-	#[case::no_flips(0, SESSION_DURATION)]
-	#[case::no_flips_no_time(0, 1)]
-	#[case::first_flips(5, SESSION_DURATION - 100)]
-	#[case::first_flips_more_time(5, SESSION_DURATION - 200)]
-	#[case::first_flips_even_more_time(5, SESSION_DURATION - 400)]
-	#[case::first_flips_most_time(5, SESSION_DURATION - 1000)]
-	#[case::flips_doubled(10, SESSION_DURATION - 1000)]
-	#[case::max_flips(EARNED_TOKENS_PER_SECTION as u32, 1)]
-	fn test_token_price_calculation(
-		testname: String,
-		#[case] flips: u32,
-		#[case] remaining_time: i64,
-	) {
-		set_snapshot_suffix!("{}", testname);
-		let section = SectionState {
-			version: 0,
-			data: [PodU16::from(0); BITFLIP_SECTION_LENGTH],
-			owner: Pubkey::default(),
-			flips: PodU32::from(flips),
-			on: PodU32::from(0),
-			off: PodU32::from(BITFLIP_SECTION_TOTAL_BITS),
-			game_index: 0,
-			section_index: 0,
-			bump: 0,
-			_padding: [0; 32],
-		};
+	/// Store the revealed `mines` once they have been checked against the
+	/// commitment.
+	pub fn store_mines(&mut self, mines: &PodSectionData) {
+		self.mines = *mines;
+		self.status = MinesStatus::Revealed.into();
+	}
 
-		let lamports = section.get_token_price_in_lamports(remaining_time);
-		insta::assert_snapshot!(format!(
-			"flips: {flips}\nremaining_time: {remaining_time}\nlamports: {lamports}",
-		));
+	/// Whether every revealed cell which is a mine was reported as a hit, and
+	/// every reported hit is a mine. This is always `false` until the mines
+	/// have been revealed.
+	pub fn hits_match(&self) -> bool {
+		if self.status() != MinesStatus::Revealed {
+			return false;
+		}
+
+		self.mines.iter().enumerate().all(|(index, mine)| {
+			let mine: u16 = (*mine).into();
+			let revealed: u16 = self.revealed[index].into();
+			let hits: u16 = self.hits[index].into();
+
+			(revealed & mine) == hits
+		})
+	}
+
+	/// The number of mines surrounding the cell at the `(x, y)` coordinate of
+	/// the `64x64` section grid.
+	pub fn adjacent_mines(&self, x: u16, y: u16) -> u8 {
+		let mut count = 0;
+
+		for (dx, dy) in ADJACENT_CELLS {
+			let (Some(x), Some(y)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+				continue;
+			};
+
+			if x >= BITFLIP_SECTION_WIDTH || y >= BITFLIP_SECTION_WIDTH {
+				continue;
+			}
+
+			let (index, offset) = get_index_offset(x, y);
+
+			if self.is_mine(index, offset) {
+				count += 1;
+			}
+		}
+
+		count
+	}
+}
+
+/// A reward for anyone who makes a rectangular region of a section match a
+/// target pattern before the bounty expires.
+///
+/// The reward is escrowed as lamports within this account on top of the rent.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BountyState {
+	/// The version of the state.
+	pub version: u8,
+	/// The account which posted the bounty and is refunded after expiry.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub poster: Pubkey,
+	/// The lamports paid to the claimer.
+	pub reward: PodU64,
+	/// The unix timestamp after which the bounty can no longer be claimed and
+	/// is refunded to the poster.
+	pub expiry: PodI64,
+	/// The target pattern where bit `c` of row `r` is the target value for the
+	/// `(x + c, y + r)` coordinate of the section. Only the first `height` rows
+	/// and `width` bits are used.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub pattern: PodSectionRows,
+	/// The index of the bounty chosen by the poster.
+	pub bounty_index: PodU16,
+	/// The index of the game.
+	pub game_index: u8,
+	/// The index of the section.
+	pub section_index: u8,
+	/// The left coordinate of the region.
+	pub x: u8,
+	/// The top coordinate of the region.
+	pub y: u8,
+	/// The width of the region.
+	pub width: u8,
+	/// The height of the region.
+	pub height: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for BountyState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl BountyState {
+	#[inline(always)]
+	pub fn reward(&self) -> u64 {
+		self.reward.into()
+	}
+
+	#[inline(always)]
+	pub fn expiry(&self) -> i64 {
+		self.expiry.into()
+	}
+
+	#[inline(always)]
+	pub fn bounty_index(&self) -> u16 {
+		self.bounty_index.into()
+	}
+
+	/// Whether the bounty can no longer be claimed.
+	pub fn expired(&self, current_time: i64) -> bool {
+		current_time > self.expiry()
+	}
+
+	/// Whether the region of the section matches the target pattern.
+	pub fn matches(&self, section: &SectionState) -> bool {
+		let data: SectionData = section.data.map(Into::into);
+		let rows = get_section_rows(&data);
+		let mask = get_region_mask(self.width);
+		let (x, y) = (self.x as usize, self.y as usize);
+
+		(0..self.height as usize).all(|row| {
+			let pattern: u64 = self.pattern[row].into();
+			((rows[y + row] >> x) & mask) == (pattern & mask)
+		})
+	}
+}
+
+/// The leaves of the canvas merkle tree which are collected over multiple
+/// `GameFinalize` transactions once the game has ended.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GameCanvasState {
+	/// The version of the state.
+	pub version: u8,
+	/// The hash of each section. See [`crate::get_canvas_leaf`].
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub leaves: CanvasLeaves,
+	/// The number of leaves which have been collected.
+	pub leaf_count: PodU16,
+	/// The index of the game.
+	pub game_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for GameCanvasState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl GameCanvasState {
+	/// Initialize the canvas without touching the leaves to prevent using
+	/// compute units.
+	pub fn init(&mut self, game_index: u8, bump: u8) {
+		self.version = GameCanvasState::VERSION;
+		self.leaf_count = 0.into();
+		self.game_index = game_index;
+		self.bump = bump;
+	}
+
+	#[inline(always)]
+	pub fn leaf_count(&self) -> u16 {
+		self.leaf_count.into()
+	}
+
+	/// Whether every section has been hashed.
+	pub fn complete(&self) -> bool {
+		usize::from(self.leaf_count()) >= BITFLIP_TOTAL_SECTIONS
+	}
+
+	/// Add the leaf for the next section.
+	pub fn push_leaf(&mut self, leaf: [u8; 32]) -> ProgramResult {
+		if self.complete() {
+			return Err(BitflipError::GameAlreadyFinalized.into());
+		}
+
+		let index = self.leaf_count();
+		self.leaves[usize::from(index)] = leaf;
+		self.leaf_count = (index + 1).into();
+
+		Ok(())
+	}
+}
+
+/// The customisation of a section which is set by the section owner.
+///
+/// The config authority can moderate the metadata which clears the title and
+/// link, resets the colors and prevents the owner from updating it again until
+/// the moderation is lifted.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionMetadataState {
+	/// The version of the state.
+	pub version: u8,
+	/// The utf8 bytes of the title. Only the first `title_len` bytes are used.
+	pub title: [u8; SECTION_TITLE_MAX_LENGTH],
+	/// The length of the title.
+	pub title_len: u8,
+	/// The utf8 bytes of the external link. Only the first `link_len` bytes are
+	/// used.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub link: [u8; SECTION_LINK_MAX_LENGTH],
+	/// The length of the link.
+	pub link_len: u8,
+	/// The rgb color of the bits which are on.
+	pub foreground: [u8; 3],
+	/// The rgb color of the bits which are off.
+	pub background: [u8; 3],
+	/// The index of the game.
+	pub game_index: u8,
+	/// The index of the section.
+	pub section_index: u8,
+	/// Set to `1` when the metadata has been moderated by the config
+	/// authority.
+	pub moderated: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// The minimum balance of the `gate_member` token, including decimals,
+	/// which players must hold to flip the bits of the section. The section is
+	/// not gated when this is `0`.
+	pub gate_amount: PodU64,
+	/// The [`TokenMember`] which gates the section.
+	pub gate_member: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 23],
+}
+
+impl AccountVersion for SectionMetadataState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl SectionMetadataState {
+	/// Create a new section metadata state in the client. Useful for testing.
+	pub fn new(game_index: u8, section_index: u8, bump: u8) -> Self {
+		Self {
+			version: SectionMetadataState::VERSION,
+			title: [0; SECTION_TITLE_MAX_LENGTH],
+			title_len: 0,
+			link: [0; SECTION_LINK_MAX_LENGTH],
+			link_len: 0,
+			foreground: DEFAULT_SECTION_FOREGROUND,
+			background: DEFAULT_SECTION_BACKGROUND,
+			game_index,
+			section_index,
+			moderated: 0,
+			bump,
+			gate_amount: 0.into(),
+			gate_member: TokenMember::Bit.into(),
+			_padding: [0; 23],
+		}
+	}
+
+	/// Initialize the metadata with the default colors.
+	pub fn init(&mut self, game_index: u8, section_index: u8, bump: u8) {
+		self.version = SectionMetadataState::VERSION;
+		self.foreground = DEFAULT_SECTION_FOREGROUND;
+		self.background = DEFAULT_SECTION_BACKGROUND;
+		self.game_index = game_index;
+		self.section_index = section_index;
+		self.bump = bump;
+	}
+
+	pub fn title(&self) -> &str {
+		let length = usize::from(self.title_len).min(SECTION_TITLE_MAX_LENGTH);
+		core::str::from_utf8(&self.title[..length]).unwrap_or_default()
+	}
+
+	pub fn link(&self) -> &str {
+		let length = usize::from(self.link_len).min(SECTION_LINK_MAX_LENGTH);
+		core::str::from_utf8(&self.link[..length]).unwrap_or_default()
+	}
+
+	#[inline(always)]
+	pub fn moderated(&self) -> bool {
+		self.moderated != 0
+	}
+
+	/// Update the title, link and colors after checking the title and link are
+	/// valid utf8 within the length limits.
+	pub fn update(&mut self, args: &SectionMetadataUpdate) -> ProgramResult {
+		let title_len = usize::from(args.title_len);
+		let link_len = usize::from(args.link_len);
+
+		if title_len > SECTION_TITLE_MAX_LENGTH
+			|| link_len > SECTION_LINK_MAX_LENGTH
+			|| core::str::from_utf8(&args.title[..title_len]).is_err()
+			|| core::str::from_utf8(&args.link[..link_len]).is_err()
+		{
+			return Err(BitflipError::SectionMetadataInvalid.into());
+		}
+
+		self.title = args.title;
+		self.title_len = args.title_len;
+		self.link = args.link;
+		self.link_len = args.link_len;
+		self.foreground = args.foreground;
+		self.background = args.background;
+
+		Ok(())
+	}
+
+	#[inline(always)]
+	pub fn gate_amount(&self) -> u64 {
+		self.gate_amount.into()
+	}
+
+	/// The token and minimum balance required to flip the bits of the section.
+	pub fn gate(&self) -> Option<(TokenMember, u64)> {
+		let amount = self.gate_amount();
+
+		if amount == 0 {
+			return None;
+		}
+
+		TokenMember::try_from(self.gate_member)
+			.ok()
+			.map(|member| (member, amount))
+	}
+
+	/// Gate the section behind a minimum token balance. An `amount` of `0`
+	/// removes the gate.
+	pub fn update_gate(&mut self, member: TokenMember, amount: u64) {
+		self.gate_member = member.into();
+		self.gate_amount = amount.into();
+	}
+
+	/// Moderate the metadata. When `moderated` is `true` the title and link
+	/// are cleared and the colors are reset.
+	pub fn moderate(&mut self, moderated: bool) {
+		self.moderated = moderated.into();
+
+		if !moderated {
+			return;
+		}
+
+		self.title = [0; SECTION_TITLE_MAX_LENGTH];
+		self.title_len = 0;
+		self.link = [0; SECTION_LINK_MAX_LENGTH];
+		self.link_len = 0;
+		self.foreground = DEFAULT_SECTION_FOREGROUND;
+		self.background = DEFAULT_SECTION_BACKGROUND;
+	}
+}
+
+/// The achievement badges which are earned by reaching milestones while
+/// flipping bits. Each badge has its own non-transferable mint.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+pub enum BadgeType {
+	/// The first flip of a game.
+	FirstFlip = 0,
+	/// The flip which pushes a section past [`MINIMUM_FLIPS_PER_SECTION`].
+	SectionThreshold = 1,
+	/// The [`PLAYER_FLIPS_MILESTONE`] flip of a player within a game.
+	PlayerFlips = 2,
+}
+
+impl BadgeType {
+	#[inline(always)]
+	pub const fn name(&self) -> &'static str {
+		match self {
+			BadgeType::FirstFlip => FIRST_FLIP_BADGE_NAME,
+			BadgeType::SectionThreshold => SECTION_THRESHOLD_BADGE_NAME,
+			BadgeType::PlayerFlips => PLAYER_FLIPS_BADGE_NAME,
+		}
+	}
+
+	#[inline(always)]
+	pub const fn symbol(&self) -> &'static str {
+		match self {
+			BadgeType::FirstFlip => FIRST_FLIP_BADGE_SYMBOL,
+			BadgeType::SectionThreshold => SECTION_THRESHOLD_BADGE_SYMBOL,
+			BadgeType::PlayerFlips => PLAYER_FLIPS_BADGE_SYMBOL,
+		}
+	}
+
+	#[inline(always)]
+	pub const fn uri(&self) -> &'static str {
+		match self {
+			BadgeType::FirstFlip => FIRST_FLIP_BADGE_URI,
+			BadgeType::SectionThreshold => SECTION_THRESHOLD_BADGE_URI,
+			BadgeType::PlayerFlips => PLAYER_FLIPS_BADGE_URI,
+		}
+	}
+
+	#[inline(always)]
+	pub const fn seed(&self) -> &'static [u8] {
+		match self {
+			BadgeType::FirstFlip => SEED_FIRST_FLIP_BADGE_MINT,
+			BadgeType::SectionThreshold => SEED_SECTION_THRESHOLD_BADGE_MINT,
+			BadgeType::PlayerFlips => SEED_PLAYER_FLIPS_BADGE_MINT,
+		}
+	}
+
+	/// The bit of the badge within [`PlayerState::earned`] and
+	/// [`PlayerState::claimed`].
+	#[inline(always)]
+	pub const fn mask(&self) -> u8 {
+		1 << (*self as u8)
+	}
+}
+
+/// The progress of a player within a game.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PlayerState {
+	/// The version of the state.
+	pub version: u8,
+	/// The player wallet.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub player: Pubkey,
+	/// The number of bits flipped by the player within the game.
+	pub flips: PodU32,
+	/// The [`BadgeType`] masks of the badges which have been earned.
+	pub earned: u8,
+	/// The [`BadgeType`] masks of the badges which have been minted.
+	pub claimed: u8,
+	/// The index of the game.
+	pub game_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for PlayerState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl PlayerState {
+	/// Create a new player state in the client. Useful for testing.
+	pub fn new(player: Pubkey, game_index: u8, bump: u8) -> Self {
+		Self {
+			version: PlayerState::VERSION,
+			player,
+			flips: 0.into(),
+			earned: 0,
+			claimed: 0,
+			game_index,
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn flips(&self) -> u32 {
+		self.flips.into()
+	}
+
+	#[inline(always)]
+	pub fn earned(&self, badge: BadgeType) -> bool {
+		self.earned & badge.mask() != 0
+	}
+
+	#[inline(always)]
+	pub fn claimed(&self, badge: BadgeType) -> bool {
+		self.claimed & badge.mask() != 0
+	}
+
+	pub fn earn(&mut self, badge: BadgeType) {
+		if self.earned(badge) {
+			return;
+		}
+
+		debug_msg!("milestone reached: {:?}", badge);
+		self.earned |= badge.mask();
+	}
+
+	/// Mark the badge as minted.
+	pub fn claim(&mut self, badge: BadgeType) -> ProgramResult {
+		if !self.earned(badge) {
+			return Err(BitflipError::BadgeNotEarned.into());
+		}
+
+		if self.claimed(badge) {
+			return Err(BitflipError::BadgeAlreadyClaimed.into());
+		}
+
+		self.claimed |= badge.mask();
+
+		Ok(())
+	}
+
+	/// Record the flips of the player on the section and earn the badges for
+	/// any milestones which were reached. The `previous_section_flips` are the
+	/// flips of the section before this update and `first_flip` is set when
+	/// these are the first flips of the game.
+	pub fn record_flips(
+		&mut self,
+		section: &SectionState,
+		previous_section_flips: u32,
+		first_flip: bool,
+		flips: u32,
+	) -> ProgramResult {
+		let previous_flips = self.flips();
+		let current_flips = previous_flips
+			.checked_add(flips)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		self.flips = current_flips.into();
+
+		if first_flip {
+			self.earn(BadgeType::FirstFlip);
+		}
+
+		if previous_section_flips < MINIMUM_FLIPS_PER_SECTION
+			&& section.flips() >= MINIMUM_FLIPS_PER_SECTION
+		{
+			self.earn(BadgeType::SectionThreshold);
+		}
+
+		if previous_flips < PLAYER_FLIPS_MILESTONE && current_flips >= PLAYER_FLIPS_MILESTONE {
+			self.earn(BadgeType::PlayerFlips);
+		}
+
+		Ok(())
+	}
+}
+
+/// The slots at which each `u16` word of a section was last flipped. This is
+/// only used by games with a cooldown and is kept separate from the
+/// [`SectionState`] so that games without a cooldown don't pay for it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionCooldownState {
+	/// The version of the state.
+	pub version: u8,
+	/// The lower 32 bits of the slot each word was last flipped at. `0` when
+	/// the word has never been flipped.
+	#[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+	pub slots: [PodU32; BITFLIP_SECTION_LENGTH],
+	/// The index of the game.
+	pub game_index: u8,
+	/// The index of the section.
+	pub section_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for SectionCooldownState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl SectionCooldownState {
+	/// Create a new section cooldown state in the client. Useful for testing.
+	pub fn new(game_index: u8, section_index: u8, bump: u8) -> Self {
+		Self {
+			version: SectionCooldownState::VERSION,
+			slots: [0.into(); BITFLIP_SECTION_LENGTH],
+			game_index,
+			section_index,
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	/// The truncated slot the word at the `array_index` was last flipped at.
+	#[inline(always)]
+	pub fn last_slot(&self, array_index: u8) -> u32 {
+		self.slots[usize::from(array_index)].into()
+	}
+
+	/// Record a flip of the word at the `array_index` and return the price
+	/// multiplier for the flip. When the word is still cooling down the
+	/// `multiplier` is returned or the flip is rejected if it is `0`.
+	pub fn touch(
+		&mut self,
+		array_index: u8,
+		slot: u64,
+		cooldown_slots: u32,
+		multiplier: u8,
+	) -> Result<u64, ProgramError> {
+		// the truncated slot wraps after many years so the wrapping difference is
+		// always accurate for a cooldown. `0` is reserved for untouched words.
+		let current_slot = (slot as u32).max(1);
+		let last_slot = self.last_slot(array_index);
+		let cooling = last_slot != 0 && current_slot.wrapping_sub(last_slot) < cooldown_slots;
+
+		if cooling && multiplier == 0 {
+			return Err(BitflipError::BitCooldown.into());
+		}
+
+		self.slots[usize::from(array_index)] = current_slot.into();
+
+		Ok(if cooling { u64::from(multiplier) } else { 1 })
+	}
+}
+
+/// A temporary shield over a rectangular region of a section. Only the
+/// `holder` can flip the bits within the region until the `expiry`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionLock {
+	/// The player which bought the lock.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub holder: Pubkey,
+	/// The unix timestamp when the lock expires.
+	pub expiry: PodI64,
+	/// The left coordinate of the region.
+	pub x: u8,
+	/// The top coordinate of the region.
+	pub y: u8,
+	/// The width of the region.
+	pub width: u8,
+	/// The height of the region.
+	pub height: u8,
+}
+
+impl SectionLock {
+	#[inline(always)]
+	pub fn expiry(&self) -> i64 {
+		self.expiry.into()
+	}
+
+	/// Whether the lock still protects the region.
+	pub fn active(&self, current_time: i64) -> bool {
+		current_time < self.expiry()
+	}
+
+	/// Whether the `(x, y)` coordinate of the section is within the region.
+	pub fn contains(&self, x: u16, y: u16) -> bool {
+		let (left, top) = (u16::from(self.x), u16::from(self.y));
+
+		x >= left
+			&& x < left + u16::from(self.width)
+			&& y >= top
+			&& y < top + u16::from(self.height)
+	}
+
+	/// Whether the regions of the two locks share any coordinate.
+	pub fn overlaps(&self, other: &SectionLock) -> bool {
+		let overlaps = |start: u8, length: u8, other_start: u8, other_length: u8| {
+			u16::from(start) < u16::from(other_start) + u16::from(other_length)
+				&& u16::from(other_start) < u16::from(start) + u16::from(length)
+		};
+
+		overlaps(self.x, self.width, other.x, other.width)
+			&& overlaps(self.y, self.height, other.y, other.height)
+	}
+}
+
+/// The region locks of a section. Expired locks are replaced by new locks so
+/// only a bounded number of locks can be active at the same time.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SectionLocksState {
+	/// The version of the state.
+	pub version: u8,
+	/// The locks of the section. Expired entries are free to be reused.
+	pub locks: [SectionLock; MAX_SECTION_LOCKS],
+	/// The index of the game.
+	pub game_index: u8,
+	/// The index of the section.
+	pub section_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for SectionLocksState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl SectionLocksState {
+	/// Create a new section locks state in the client. Useful for testing.
+	pub fn new(game_index: u8, section_index: u8, bump: u8) -> Self {
+		Self {
+			version: SectionLocksState::VERSION,
+			locks: [SectionLock::default(); MAX_SECTION_LOCKS],
+			game_index,
+			section_index,
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	/// The locks which are still active.
+	pub fn active_locks(&self, current_time: i64) -> impl Iterator<Item = &SectionLock> {
+		self.locks
+			.iter()
+			.filter(move |lock| lock.active(current_time))
+	}
+
+	/// Add a lock in place of an expired entry. The region can't overlap the
+	/// active locks of another holder.
+	pub fn lock(&mut self, lock: SectionLock, current_time: i64) -> ProgramResult {
+		if self
+			.active_locks(current_time)
+			.any(|active| active.holder != lock.holder && active.overlaps(&lock))
+		{
+			return Err(BitflipError::BitLocked.into());
+		}
+
+		let Some(entry) = self
+			.locks
+			.iter_mut()
+			.find(|entry| !entry.active(current_time))
+		else {
+			return Err(BitflipError::SectionLocksFull.into());
+		};
+
+		*entry = lock;
+
+		Ok(())
+	}
+
+	/// Fail when another player holds an active lock anywhere in the section.
+	/// Stepping rewrites every bit of the section so it can't go around the
+	/// locked regions.
+	pub fn assert_section_unlocked(&self, player: &Pubkey, current_time: i64) -> ProgramResult {
+		if self
+			.active_locks(current_time)
+			.any(|lock| lock.holder != *player)
+		{
+			return Err(BitflipError::BitLocked.into());
+		}
+
+		Ok(())
+	}
+
+	/// Fail when the bit is within the active lock of another player.
+	pub fn assert_unlocked(
+		&self,
+		player: &Pubkey,
+		array_index: u8,
+		offset: u8,
+		current_time: i64,
+	) -> ProgramResult {
+		let (x, y) = get_coordinates(array_index, offset);
+
+		if self
+			.active_locks(current_time)
+			.any(|lock| lock.holder != *player && lock.contains(x, y))
+		{
+			return Err(BitflipError::BitLocked.into());
+		}
+
+		Ok(())
+	}
+}
+
+/// The pool where players stake their tokens to earn a share of the section
+/// unlock revenue.
+///
+/// Each token is weighted by the number of bits it is worth. Revenue deposited
+/// into the pool increases the `reward_index` which tracks the lamports earned
+/// per staked bit since the pool was created.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StakePoolState {
+	/// The version of the state.
+	pub version: u8,
+	/// The lamports earned per staked bit multiplied by
+	/// [`STAKE_REWARD_PRECISION`].
+	pub reward_index: PodU128,
+	/// The total weight of the staked tokens in bits.
+	pub total_weight: PodU64,
+	/// The share of the section unlock revenue paid to the pool in basis
+	/// points.
+	pub revenue_basis_points: PodU16,
+	/// The number of seconds the tokens are locked for after staking.
+	pub unstake_cooldown: PodI64,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for StakePoolState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl StakePoolState {
+	/// Create a new stake pool state in the client. Useful for testing.
+	pub fn new(revenue_basis_points: u16, unstake_cooldown: i64, bump: u8) -> Self {
+		Self {
+			version: StakePoolState::VERSION,
+			reward_index: 0.into(),
+			total_weight: 0.into(),
+			revenue_basis_points: revenue_basis_points.into(),
+			unstake_cooldown: unstake_cooldown.into(),
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn reward_index(&self) -> u128 {
+		self.reward_index.into()
+	}
+
+	#[inline(always)]
+	pub fn total_weight(&self) -> u64 {
+		self.total_weight.into()
+	}
+
+	#[inline(always)]
+	pub fn revenue_basis_points(&self) -> u16 {
+		self.revenue_basis_points.into()
+	}
+
+	#[inline(always)]
+	pub fn unstake_cooldown(&self) -> i64 {
+		self.unstake_cooldown.into()
+	}
+
+	/// The share of the `lamports` of revenue which is paid to the pool.
+	/// Nothing is paid while the pool has no stakers.
+	pub fn revenue_share(&self, lamports: u64) -> u64 {
+		if self.total_weight() == 0 {
+			return 0;
+		}
+
+		let share = u128::from(lamports) * u128::from(self.revenue_basis_points()) / 10_000;
+		share as u64
+	}
+
+	/// Distribute the deposited `lamports` between the stakers.
+	pub fn deposit(&mut self, lamports: u64) -> ProgramResult {
+		let total_weight = self.total_weight();
+
+		if total_weight == 0 {
+			return Ok(());
+		}
+
+		let increase = u128::from(lamports)
+			.checked_mul(STAKE_REWARD_PRECISION)
+			.ok_or(ProgramError::ArithmeticOverflow)?
+			/ u128::from(total_weight);
+		let reward_index = self
+			.reward_index()
+			.checked_add(increase)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		self.reward_index = reward_index.into();
+
+		Ok(())
+	}
+}
+
+/// The tokens staked by a player in the [`StakePoolState`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StakeState {
+	/// The version of the state.
+	pub version: u8,
+	/// The owner of the stake.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub owner: Pubkey,
+	/// The staked tokens of each [`TokenMember`].
+	pub amounts: [PodU64; 4],
+	/// The weight of the staked tokens in bits.
+	pub weight: PodU64,
+	/// The reward index of the pool when the rewards were last settled.
+	pub reward_index: PodU128,
+	/// The settled lamports which haven't been claimed.
+	pub rewards: PodU64,
+	/// The time from which the tokens can be unstaked.
+	pub unlock_time: PodI64,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for StakeState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl StakeState {
+	/// Create a new stake state in the client. Useful for testing.
+	pub fn new(owner: Pubkey, reward_index: u128, bump: u8) -> Self {
+		Self {
+			version: StakeState::VERSION,
+			owner,
+			amounts: [0.into(); 4],
+			weight: 0.into(),
+			reward_index: reward_index.into(),
+			rewards: 0.into(),
+			unlock_time: 0.into(),
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn amount(&self, member: TokenMember) -> u64 {
+		self.amounts[u8::from(member) as usize].into()
+	}
+
+	#[inline(always)]
+	pub fn weight(&self) -> u64 {
+		self.weight.into()
+	}
+
+	#[inline(always)]
+	pub fn reward_index(&self) -> u128 {
+		self.reward_index.into()
+	}
+
+	#[inline(always)]
+	pub fn rewards(&self) -> u64 {
+		self.rewards.into()
+	}
+
+	#[inline(always)]
+	pub fn unlock_time(&self) -> i64 {
+		self.unlock_time.into()
+	}
+
+	/// The lamports earned by the stake which haven't been settled yet.
+	pub fn pending_rewards(&self, pool: &StakePoolState) -> u64 {
+		let index = pool.reward_index().saturating_sub(self.reward_index());
+		let pending = u128::from(self.weight()).saturating_mul(index) / STAKE_REWARD_PRECISION;
+
+		pending.min(u128::from(u64::MAX)) as u64
+	}
+
+	/// Add the pending rewards to the unclaimed `rewards` of the stake.
+	pub fn settle(&mut self, pool: &StakePoolState) -> ProgramResult {
+		let rewards = self
+			.rewards()
+			.checked_add(self.pending_rewards(pool))
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		self.rewards = rewards.into();
+		self.reward_index = pool.reward_index;
+
+		Ok(())
+	}
+
+	/// Stake the `amount` of tokens of the `member` and lock the stake until
+	/// the `unlock_time`.
+	pub fn stake(
+		&mut self,
+		pool: &mut StakePoolState,
+		member: TokenMember,
+		amount: u64,
+		unlock_time: i64,
+	) -> ProgramResult {
+		self.settle(pool)?;
+
+		let index = u8::from(member) as usize;
+		let weight = amount
+			.checked_mul(member.bits())
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		let staked = self
+			.amount(member)
+			.checked_add(amount)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		let stake_weight = self
+			.weight()
+			.checked_add(weight)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		let total_weight = pool
+			.total_weight()
+			.checked_add(weight)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+
+		self.amounts[index] = staked.into();
+		self.weight = stake_weight.into();
+		self.unlock_time = unlock_time.into();
+		pool.total_weight = total_weight.into();
+
+		Ok(())
+	}
+
+	/// Unstake the `amount` of tokens of the `member`.
+	pub fn unstake(
+		&mut self,
+		pool: &mut StakePoolState,
+		member: TokenMember,
+		amount: u64,
+		current_time: i64,
+	) -> ProgramResult {
+		if current_time < self.unlock_time() {
+			return Err(BitflipError::StakeLocked.into());
+		}
+
+		let Some(staked) = self.amount(member).checked_sub(amount) else {
+			return Err(BitflipError::StakeInsufficient.into());
+		};
+
+		self.settle(pool)?;
+
+		let index = u8::from(member) as usize;
+		let weight = amount * member.bits();
+		self.amounts[index] = staked.into();
+		self.weight = self.weight().saturating_sub(weight).into();
+		pool.total_weight = pool.total_weight().saturating_sub(weight).into();
+
+		Ok(())
+	}
+
+	/// Settle and take the unclaimed rewards of the stake.
+	pub fn claim(&mut self, pool: &StakePoolState) -> Result<u64, ProgramError> {
+		self.settle(pool)?;
+
+		let rewards = self.rewards();
+		self.rewards = 0.into();
+
+		Ok(rewards)
+	}
+}
+
+#[repr(u8)]
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+pub enum ProposalStatus {
+	/// The proposal can be voted on until the end of the voting period.
+	#[default]
+	Active = 0,
+	/// The proposal passed and the change was applied to the game.
+	Executed = 1,
+	/// The proposal didn't reach the quorum or a majority.
+	Rejected = 2,
+	/// The proposal passed but the change was invalid for the game at the time
+	/// of the execution, e.g. it would break the price bounds, and the game was
+	/// left unchanged.
+	Failed = 3,
+}
+
+/// A governance proposal to change a [`GameParameter`] of a game.
+///
+/// Holders of the bit tokens vote by escrowing their tokens in a
+/// [`VoteState`]. Once the voting period has ended anyone can execute the
+/// proposal which applies the change when the quorum and a majority were
+/// reached.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ProposalState {
+	/// The version of the state.
+	pub version: u8,
+	/// The account which created the proposal.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub proposer: Pubkey,
+	/// The proposed value of the parameter.
+	pub value: PodU64,
+	/// The weight in bits of the votes for the proposal.
+	pub votes_for: PodU64,
+	/// The weight in bits of the votes against the proposal.
+	pub votes_against: PodU64,
+	/// The unix timestamp when the voting period ends.
+	pub end_time: PodI64,
+	/// The index of the proposal chosen by the proposer.
+	pub proposal_index: PodU16,
+	/// The index of the game.
+	pub game_index: u8,
+	/// The [`GameParameter`] being changed.
+	pub parameter: u8,
+	/// The [`ProposalStatus`].
+	pub status: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for ProposalState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl ProposalState {
+	/// Create a new proposal state in the client. Useful for testing.
+	pub fn new(
+		proposer: Pubkey,
+		game_index: u8,
+		proposal_index: u16,
+		parameter: GameParameter,
+		value: u64,
+		end_time: i64,
+		bump: u8,
+	) -> Self {
+		Self {
+			version: ProposalState::VERSION,
+			proposer,
+			value: value.into(),
+			votes_for: 0.into(),
+			votes_against: 0.into(),
+			end_time: end_time.into(),
+			proposal_index: proposal_index.into(),
+			game_index,
+			parameter: parameter.into(),
+			status: ProposalStatus::Active.into(),
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn value(&self) -> u64 {
+		self.value.into()
+	}
+
+	#[inline(always)]
+	pub fn votes_for(&self) -> u64 {
+		self.votes_for.into()
+	}
+
+	#[inline(always)]
+	pub fn votes_against(&self) -> u64 {
+		self.votes_against.into()
+	}
+
+	#[inline(always)]
+	pub fn end_time(&self) -> i64 {
+		self.end_time.into()
+	}
+
+	#[inline(always)]
+	pub fn proposal_index(&self) -> u16 {
+		self.proposal_index.into()
+	}
+
+	pub fn parameter(&self) -> Result<GameParameter, ProgramError> {
+		GameParameter::try_from(self.parameter).map_err(|_| ProgramError::InvalidAccountData)
+	}
+
+	pub fn status(&self) -> ProposalStatus {
+		ProposalStatus::try_from(self.status).unwrap_or_default()
+	}
+
+	/// Whether votes can still be cast on the proposal.
+	pub fn voting(&self, current_time: i64) -> bool {
+		self.status() == ProposalStatus::Active && current_time < self.end_time()
+	}
+
+	/// Whether the votes reached the quorum and a majority approved.
+	pub fn passed(&self) -> bool {
+		let votes = self.votes_for().saturating_add(self.votes_against());
+		votes >= GOVERNANCE_QUORUM && self.votes_for() > self.votes_against()
+	}
+
+	/// Add the `weight` of a vote to the proposal.
+	pub fn vote(&mut self, approve: bool, weight: u64, current_time: i64) -> ProgramResult {
+		if !self.voting(current_time) {
+			return Err(BitflipError::ProposalClosed.into());
+		}
+
+		let votes = if approve {
+			&mut self.votes_for
+		} else {
+			&mut self.votes_against
+		};
+		let total = u64::from(*votes)
+			.checked_add(weight)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		*votes = total.into();
+
+		Ok(())
+	}
+
+	/// Close the voting on the proposal and apply the change to the game when
+	/// it passed.
+	///
+	/// A passed proposal whose change can't be applied to the game is closed as
+	/// [`ProposalStatus::Failed`] instead of failing the execution, so the
+	/// voters can still withdraw their tokens.
+	pub fn execute(&mut self, game: &mut GameState, current_time: i64) -> ProgramResult {
+		if self.status() != ProposalStatus::Active {
+			return Err(BitflipError::ProposalClosed.into());
+		}
+
+		if current_time < self.end_time() {
+			return Err(BitflipError::ProposalActive.into());
+		}
+
+		if !self.passed() {
+			self.status = ProposalStatus::Rejected.into();
+			return Ok(());
+		}
+
+		let executed = self
+			.parameter()
+			.and_then(|parameter| game.update_parameter(parameter, self.value()));
+
+		self.status = match executed {
+			Ok(()) => ProposalStatus::Executed.into(),
+			Err(_) => ProposalStatus::Failed.into(),
+		};
+
+		Ok(())
+	}
+}
+
+/// The tokens escrowed by a holder to vote on a [`ProposalState`]. The tokens
+/// are locked until the voting period has ended.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct VoteState {
+	/// The version of the state.
+	pub version: u8,
+	/// The holder which voted.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub voter: Pubkey,
+	/// The proposal being voted on.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub proposal: Pubkey,
+	/// The escrowed tokens of each [`TokenMember`].
+	pub amounts: [PodU64; 4],
+	/// The weight of the vote in bits.
+	pub weight: PodU64,
+	/// Whether the vote approves the proposal: `0` or `1`.
+	pub approve: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for VoteState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl VoteState {
+	/// Create a new vote state in the client. Useful for testing.
+	pub fn new(voter: Pubkey, proposal: Pubkey, approve: bool, bump: u8) -> Self {
+		Self {
+			version: VoteState::VERSION,
+			voter,
+			proposal,
+			amounts: [0.into(); 4],
+			weight: 0.into(),
+			approve: approve.into(),
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn amount(&self, member: TokenMember) -> u64 {
+		self.amounts[u8::from(member) as usize].into()
+	}
+
+	#[inline(always)]
+	pub fn weight(&self) -> u64 {
+		self.weight.into()
+	}
+
+	#[inline(always)]
+	pub fn approve(&self) -> bool {
+		self.approve == 1
+	}
+
+	/// Escrow the `amount` of tokens of the `member` and return the weight
+	/// which was added to the vote.
+	pub fn deposit(&mut self, member: TokenMember, amount: u64) -> Result<u64, ProgramError> {
+		let index = u8::from(member) as usize;
+		let weight = amount
+			.checked_mul(member.bits())
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		let escrowed = self
+			.amount(member)
+			.checked_add(amount)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		let vote_weight = self
+			.weight()
+			.checked_add(weight)
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+
+		self.amounts[index] = escrowed.into();
+		self.weight = vote_weight.into();
+
+		Ok(weight)
+	}
+
+	/// Release the escrowed tokens of the `member`. The weight of the vote is
+	/// kept as a record of the vote.
+	pub fn withdraw(&mut self, member: TokenMember) -> u64 {
+		let amount = self.amount(member);
+		self.amounts[u8::from(member) as usize] = 0.into();
+
+		amount
+	}
+}
+
+/// The teams of a [`GameFlags::TEAMS`] game. Each team is named after the
+/// color of the bits it flips.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Team {
+	Off = 0,
+	On = 1,
+}
+
+impl Team {
+	/// Whether the flip sets a bit to the color of the team.
+	#[inline(always)]
+	pub fn matches(&self, args: &FlipBit) -> bool {
+		args.on() == (*self == Team::On)
+	}
+}
+
+/// The reward pool of a [`GameFlags::TEAMS`] game.
+///
+/// A share of the lamports paid for each flip is deposited into the pool. Once
+/// the game is finalized the bits of every section have been tallied and the
+/// players of the winning team can claim the pool pro rata to their flips.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TeamPoolState {
+	/// The version of the state.
+	pub version: u8,
+	/// The lamports deposited into the pool.
+	pub rewards: PodU64,
+	/// The flips counted towards each [`Team`].
+	pub flips: [PodU64; 2],
+	/// The bits of each [`Team`] color tallied when the game is finalized.
+	pub bits: [PodU64; 2],
+	/// The index of the game.
+	pub game_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for TeamPoolState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl TeamPoolState {
+	/// Create a new team pool state in the client. Useful for testing.
+	pub fn new(game_index: u8, bump: u8) -> Self {
+		Self {
+			version: TeamPoolState::VERSION,
+			rewards: 0.into(),
+			flips: [0.into(); 2],
+			bits: [0.into(); 2],
+			game_index,
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn rewards(&self) -> u64 {
+		self.rewards.into()
+	}
+
+	#[inline(always)]
+	pub fn flips(&self, team: Team) -> u64 {
+		self.flips[u8::from(team) as usize].into()
+	}
+
+	#[inline(always)]
+	pub fn bits(&self, team: Team) -> u64 {
+		self.bits[u8::from(team) as usize].into()
+	}
+
+	/// The share of the `lamports` paid for a flip which is deposited into the
+	/// pool.
+	pub fn revenue_share(lamports: u64) -> u64 {
+		let share = u128::from(lamports) * u128::from(TEAM_REVENUE_BASIS_POINTS) / 10_000;
+		share as u64
+	}
+
+	pub fn deposit(&mut self, lamports: u64) -> ProgramResult {
+		self.rewards = self
+			.rewards()
+			.checked_add(lamports)
+			.ok_or(ProgramError::ArithmeticOverflow)?
+			.into();
+
+		Ok(())
+	}
+
+	/// Count the `flips` towards the `team`.
+	pub fn record_flips(&mut self, team: Team, flips: u64) -> ProgramResult {
+		let index = u8::from(team) as usize;
+		self.flips[index] = self
+			.flips(team)
+			.checked_add(flips)
+			.ok_or(ProgramError::ArithmeticOverflow)?
+			.into();
+
+		Ok(())
+	}
+
+	/// Add the bits of an unlocked section to the tally of each team.
+	pub fn tally(&mut self, section: &SectionState) -> ProgramResult {
+		let on = self
+			.bits(Team::On)
+			.checked_add(section.on().into())
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		let off = self
+			.bits(Team::Off)
+			.checked_add(section.off().into())
+			.ok_or(ProgramError::ArithmeticOverflow)?;
+		self.bits = [off.into(), on.into()];
+
+		Ok(())
+	}
+
+	/// The team holding the majority of the tallied bits. Both teams share the
+	/// pool on a tie.
+	pub fn winner(&self) -> Option<Team> {
+		match self.bits(Team::On).cmp(&self.bits(Team::Off)) {
+			std::cmp::Ordering::Greater => Some(Team::On),
+			std::cmp::Ordering::Less => Some(Team::Off),
+			std::cmp::Ordering::Equal => None,
+		}
+	}
+
+	/// Whether the players of the `team` can claim from the pool.
+	pub fn is_winner(&self, team: Team) -> bool {
+		self.winner().is_none() || self.winner() == Some(team)
+	}
+
+	/// The flips which share the pool.
+	pub fn winning_flips(&self) -> u64 {
+		match self.winner() {
+			Some(team) => self.flips(team),
+			None => self.flips(Team::On).saturating_add(self.flips(Team::Off)),
+		}
+	}
+}
+
+/// The team a player joined in a [`GameFlags::TEAMS`] game and the flips they
+/// made for it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PlayerTeamState {
+	/// The version of the state.
+	pub version: u8,
+	/// The player wallet.
+	#[cfg_attr(
+		feature = "serde",
+		serde(with = "::serde_with::As::<serde_with::DisplayFromStr>")
+	)]
+	pub player: Pubkey,
+	/// The flips which set a bit to the color of the team.
+	pub flips: PodU64,
+	/// The [`Team`] of the player.
+	pub team: u8,
+	/// Whether the reward has been claimed: `0` or `1`.
+	pub claimed: u8,
+	/// The index of the game.
+	pub game_index: u8,
+	/// The bump for this account.
+	pub bump: u8,
+	/// Extra space for future versions.
+	pub _padding: [u8; 32],
+}
+
+impl AccountVersion for PlayerTeamState {
+	const VERSION: u8 = 0;
+
+	fn migrate(&mut self) -> Result<(), ProgramError> {
+		Ok(())
+	}
+}
+
+impl PlayerTeamState {
+	/// Create a new player team state in the client. Useful for testing.
+	pub fn new(player: Pubkey, game_index: u8, team: Team, bump: u8) -> Self {
+		Self {
+			version: PlayerTeamState::VERSION,
+			player,
+			flips: 0.into(),
+			team: team.into(),
+			claimed: 0,
+			game_index,
+			bump,
+			_padding: [0; 32],
+		}
+	}
+
+	#[inline(always)]
+	pub fn flips(&self) -> u64 {
+		self.flips.into()
+	}
+
+	#[inline(always)]
+	pub fn claimed(&self) -> bool {
+		self.claimed == 1
+	}
+
+	pub fn team(&self) -> Result<Team, ProgramError> {
+		Team::try_from(self.team).map_err(|_| ProgramError::InvalidAccountData)
+	}
+
+	/// Count a flip towards the team of the player when it sets the bit to the
+	/// color of the team. Returns the number of counted flips.
+	pub fn record_flip(
+		&mut self,
+		pool: &mut TeamPoolState,
+		args: &FlipBit,
+	) -> Result<u64, ProgramError> {
+		let team = self.team()?;
+
+		if !team.matches(args) {
+			return Ok(0);
+		}
+
+		self.flips = self
+			.flips()
+			.checked_add(1)
+			.ok_or(ProgramError::ArithmeticOverflow)?
+			.into();
+		pool.record_flips(team, 1)?;
+
+		Ok(1)
+	}
+
+	/// The lamports of the pool earned by the flips of the player. Only the
+	/// players of the winning team earn a share.
+	pub fn reward(&self, pool: &TeamPoolState) -> Result<u64, ProgramError> {
+		let winning_flips = pool.winning_flips();
+
+		if !pool.is_winner(self.team()?) || winning_flips == 0 {
+			return Ok(0);
+		}
+
+		let reward =
+			u128::from(pool.rewards()) * u128::from(self.flips()) / u128::from(winning_flips);
+		Ok(reward as u64)
+	}
+
+	/// Mark the reward as claimed and return the lamports earned.
+	pub fn claim(&mut self, pool: &TeamPoolState) -> Result<u64, ProgramError> {
+		if self.claimed() {
+			return Err(BitflipError::TeamRewardClaimed.into());
+		}
+
+		if !pool.is_winner(self.team()?) {
+			return Err(BitflipError::TeamNotWinner.into());
+		}
+
+		let reward = self.reward(pool)?;
+		self.claimed = 1;
+
+		Ok(reward)
+	}
+}
+
+/// The relative coordinates of the eight cells surrounding a cell.
+const ADJACENT_CELLS: [(i16, i16); 8] = [
+	(-1, -1),
+	(0, -1),
+	(1, -1),
+	(-1, 0),
+	(1, 0),
+	(-1, 1),
+	(0, 1),
+	(1, 1),
+];
+
+#[cfg(test)]
+mod tests {
+	use std::thread;
+
+	use rstest::fixture;
+	use rstest::rstest;
+
+	use super::*;
+
+	macro_rules! set_snapshot_suffix {
+    ($($expr:expr),*) => {
+        let mut settings = insta::Settings::clone_current();
+        settings.set_snapshot_suffix(format!($($expr,)*));
+        let _guard = settings.bind_to_scope();
+		}
+	}
+
+	#[fixture]
+	pub fn testname() -> String {
+		thread::current()
+			.name()
+			.unwrap()
+			.split("::")
+			.last()
+			.unwrap()
+			.split("_")
+			.skip(2)
+			.collect::<Vec<&str>>()
+			.join("_")
+			.to_string()
+	}
+
+	#[rstest]
+	#[case::no_flips(0, SESSION_DURATION)]
+	#[case::no_flips_no_time(0, 1)]
+	#[case::first_flips(5, SESSION_DURATION - 100)]
+	#[case::first_flips_more_time(5, SESSION_DURATION - 200)]
+	#[case::first_flips_even_more_time(5, SESSION_DURATION - 400)]
+	#[case::first_flips_most_time(5, SESSION_DURATION - 1000)]
+	#[case::flips_doubled(10, SESSION_DURATION - 1000)]
+	#[case::max_flips(EARNED_TOKENS_PER_SECTION as u32, 1)]
+	fn test_token_price_calculation(
+		testname: String,
+		#[case] flips: u32,
+		#[case] remaining_time: i64,
+	) {
+		set_snapshot_suffix!("{}", testname);
+		let game = GameState::new(Pubkey::default(), Pubkey::default(), 0, 0);
+		let section = SectionState {
+			version: 0,
+			data: [PodU16::from(0); BITFLIP_SECTION_LENGTH],
+			owner: Pubkey::default(),
+			flips: PodU32::from(flips),
+			on: PodU32::from(0),
+			off: PodU32::from(BITFLIP_SECTION_TOTAL_BITS),
+			game_index: 0,
+			section_index: 0,
+			bump: 0,
+			steps: PodU32::from(0),
+			_padding: [0; 28],
+		};
+
+		let lamports = section.get_token_price_in_lamports(&game, remaining_time);
+		insta::assert_snapshot!(format!(
+			"flips: {flips}\nremaining_time: {remaining_time}\nlamports: {lamports}",
+		));
+	}
+
+	#[test]
+	fn section_mines_hits_match() {
+		let mut section_mines = SectionMinesState::new([0; 32], 0, 0, 0);
+		let mut mines = [PodU16::from(0); BITFLIP_SECTION_LENGTH];
+		mines[3] = PodU16::from(0b0110);
+		let flip = |offset, mine| {
+			FlipBit {
+				section_index: 0,
+				array_index: 3,
+				offset,
+				value: 1,
+				mine,
+				expected: 0,
+				flags: 0,
+				max_lamports_per_bit: u64::MAX.into(),
+				player_bump: 0,
+				section_locks_bump: 0,
+				section_metadata_bump: 0,
+			}
+		};
+
+		section_mines.reveal(&flip(0, 0), 10).unwrap();
+		section_mines.reveal(&flip(1, 1), 20).unwrap();
+		assert_eq!(section_mines.pot(), 20);
+		assert!(section_mines.is_revealed(3, 0));
+		assert!(section_mines.is_revealed(3, 1));
+		assert!(!section_mines.is_revealed(3, 2));
+
+		assert!(!section_mines.hits_match());
+
+		let mut copy = section_mines;
+		copy.store_mines(&mines);
+		assert_eq!(copy.status(), MinesStatus::Revealed);
+		assert!(copy.hits_match());
+		assert!(copy.is_mine(3, 2));
+
+		section_mines.reveal(&flip(0, 1), 10).unwrap();
+		section_mines.store_mines(&mines);
+		assert!(!section_mines.hits_match());
+	}
+
+	#[test]
+	fn section_mines_revealed_cell() {
+		let mut section_mines = SectionMinesState::new([0; 32], 0, 0, 0);
+		let mut mines = [PodU16::from(0); BITFLIP_SECTION_LENGTH];
+		mines[3] = PodU16::from(0b0110);
+		let flip = |offset, mine| {
+			FlipBit {
+				section_index: 0,
+				array_index: 3,
+				offset,
+				value: 1,
+				mine,
+				expected: 0,
+				flags: 0,
+				max_lamports_per_bit: u64::MAX.into(),
+				player_bump: 0,
+				section_locks_bump: 0,
+				section_metadata_bump: 0,
+			}
+		};
+
+		section_mines.reveal(&flip(0, 0), 10).unwrap();
+		section_mines.reveal(&flip(1, 1), 10).unwrap();
+
+		// hits are shown while the game is running
+		assert_eq!(section_mines.revealed_cell(3, 0), Some(RevealedCell::Safe));
+		assert_eq!(section_mines.revealed_cell(3, 1), Some(RevealedCell::Mine));
+		assert_eq!(section_mines.revealed_cell(3, 2), None);
+
+		section_mines.store_mines(&mines);
+		assert_eq!(
+			section_mines.revealed_cell(3, 0),
+			Some(RevealedCell::Adjacent(1))
+		);
+		assert_eq!(section_mines.revealed_cell(3, 1), Some(RevealedCell::Mine));
+		assert_eq!(section_mines.revealed_cell(3, 2), None);
+	}
+
+	#[test]
+	fn section_mines_adjacent() {
+		let mut section_mines = SectionMinesState::new([0; 32], 0, 0, 0);
+		for (x, y) in [(0, 0), (1, 0), (63, 63), (5, 5)] {
+			let (index, offset) = get_index_offset(x, y);
+			let value: u16 = section_mines.mines[index as usize].into();
+			section_mines.mines[index as usize] = (value | (1 << offset)).into();
+		}
+
+		assert_eq!(section_mines.adjacent_mines(0, 1), 2);
+		assert_eq!(section_mines.adjacent_mines(0, 0), 1);
+		assert_eq!(section_mines.adjacent_mines(62, 62), 1);
+		assert_eq!(section_mines.adjacent_mines(4, 4), 1);
+		assert_eq!(section_mines.adjacent_mines(20, 20), 0);
+	}
+
+	#[test]
+	fn index_offset_coordinates_roundtrip() {
+		for x in 0..BITFLIP_SECTION_WIDTH {
+			for y in 0..BITFLIP_SECTION_WIDTH {
+				let (index, offset) = get_index_offset(x, y);
+				assert_eq!(crate::get_coordinates(index, offset), (x, y));
+			}
+		}
+	}
+
+	#[test]
+	fn section_step_blinker() {
+		let mut section = SectionState::new(Pubkey::default(), 0, 0, 0);
+		let set = |section: &mut SectionState, x, y| {
+			let (index, offset) = get_index_offset(x, y);
+			let value: u16 = section.data[index as usize].into();
+			section.data[index as usize] = (value | (1 << offset)).into();
+		};
+
+		// a horizontal blinker which crosses a `4x4` block boundary.
+		for x in 3..6 {
+			set(&mut section, x, 10);
+		}
+		section.on = 3.into();
+		section.off = (BITFLIP_SECTION_TOTAL_BITS - 3).into();
+
+		assert_eq!(section.step(SectionEdge::Dead).unwrap(), 4);
+		assert_eq!(section.on(), 3);
+		assert_eq!(section.off(), BITFLIP_SECTION_TOTAL_BITS - 3);
+		assert_eq!(section.steps(), 4);
+		assert_eq!(section.flips(), 0);
+
+		for y in 9..12 {
+			let (index, offset) = get_index_offset(4, y);
+			assert!(section.is_checked(index, offset));
+		}
+	}
+
+	#[test]
+	fn section_step_past_token_cap_should_not_change_price() {
+		let game = GameState::new(Pubkey::default(), Pubkey::default(), 0, 0);
+		let mut section = SectionState::new(Pubkey::default(), 0, 0, 0);
+		section.flips = (EARNED_TOKENS_PER_SECTION as u32 - 1).into();
+
+		// a grid of blinkers which change `4` bits each on every step.
+		for y in (2..BITFLIP_SECTION_WIDTH - 2).step_by(5) {
+			for x in (1..BITFLIP_SECTION_WIDTH - 3).step_by(5) {
+				for x in x..x + 3 {
+					let (index, offset) = get_index_offset(x, y);
+					let value: u16 = section.data[index as usize].into();
+					section.data[index as usize] = (value | (1 << offset)).into();
+				}
+			}
+		}
+
+		let remaining_time = SESSION_DURATION / 2;
+		let price = section.get_token_price_in_lamports(&game, remaining_time);
+
+		while u64::from(section.steps()) <= EARNED_TOKENS_PER_SECTION {
+			section.step(SectionEdge::Torus).unwrap();
+		}
+
+		assert_eq!(section.flips(), EARNED_TOKENS_PER_SECTION as u32 - 1);
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, remaining_time),
+			price
+		);
+
+		// flips which don't earn tokens can still pass the cap.
+		section.flip_on(1).unwrap();
+		let capped_price = section.get_token_price_in_lamports(&game, remaining_time);
+		section.flip_off(1).unwrap();
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, remaining_time),
+			capped_price
+		);
+
+		section.flips = u32::MAX.into();
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, remaining_time),
+			capped_price
+		);
+	}
+
+	#[test]
+	fn token_price_should_use_game_lamports() {
+		let mut game = GameState::new(Pubkey::default(), Pubkey::default(), 0, 0);
+		let mut section = SectionState::new(Pubkey::default(), 0, 0, 0);
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, SESSION_DURATION),
+			BASE_LAMPORTS_PER_BIT
+		);
+
+		game.update_parameter(GameParameter::MaxLamports, BASE_LAMPORTS_PER_BIT * 4)
+			.unwrap();
+		game.update_parameter(GameParameter::BaseLamports, BASE_LAMPORTS_PER_BIT * 2)
+			.unwrap();
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, SESSION_DURATION),
+			BASE_LAMPORTS_PER_BIT * 2
+		);
+
+		// a section flipped far ahead of the required rate is capped.
+		section.flips = 100.into();
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, SESSION_DURATION - 1),
+			BASE_LAMPORTS_PER_BIT * 4
+		);
+
+		// a section flipped far behind the required rate is floored.
+		section.flips = 1.into();
+		game.update_parameter(GameParameter::MinLamports, BASE_LAMPORTS_PER_BIT)
+			.unwrap();
+		assert_eq!(
+			section.get_token_price_in_lamports(&game, 1),
+			BASE_LAMPORTS_PER_BIT
+		);
+	}
+
+	#[test]
+	fn section_step_edges() {
+		let mut dead = SectionState::new(Pubkey::default(), 0, 0, 0);
+
+		// a vertical blinker on the left border of the section.
+		for y in 20..23 {
+			let (index, offset) = get_index_offset(0, y);
+			let value: u16 = dead.data[index as usize].into();
+			dead.data[index as usize] = (value | (1 << offset)).into();
+		}
+
+		let mut torus = dead;
+
+		dead.step(SectionEdge::Dead).unwrap();
+		torus.step(SectionEdge::Torus).unwrap();
+
+		let (index, offset) = get_index_offset(63, 21);
+		assert!(!dead.is_checked(index, offset));
+		assert!(torus.is_checked(index, offset));
+		assert_eq!(dead.on(), 2);
+		assert_eq!(torus.on(), 3);
+	}
+
+	#[test]
+	fn bounty_matches_region() {
+		let mut section = SectionState::new(Pubkey::default(), 0, 0, 0);
+		let mut bounty = BountyState::zeroed();
+		bounty.x = 62;
+		bounty.y = 3;
+		bounty.width = 2;
+		bounty.height = 2;
+		bounty.pattern[0] = 0b01.into();
+		bounty.pattern[1] = 0b10.into();
+
+		assert!(!bounty.matches(&section));
+
+		for (x, y) in [(62, 3), (63, 4)] {
+			let (index, offset) = get_index_offset(x, y);
+			let value: u16 = section.data[index as usize].into();
+			section.data[index as usize] = (value | (1 << offset)).into();
+		}
+
+		assert!(bounty.matches(&section));
+
+		// bits outside of the region are ignored.
+		for (x, y) in [(61, 3), (10, 10)] {
+			let (index, offset) = get_index_offset(x, y);
+			let value: u16 = section.data[index as usize].into();
+			section.data[index as usize] = (value | (1 << offset)).into();
+		}
+		assert!(bounty.matches(&section));
+	}
+
+	#[test]
+	fn game_canvas_root() {
+		let mut game_canvas = GameCanvasState::zeroed();
+		let data = [PodU16::from(0); BITFLIP_SECTION_LENGTH];
+
+		for section_index in 0..BITFLIP_TOTAL_SECTIONS {
+			assert!(!game_canvas.complete());
+			let leaf = crate::get_canvas_leaf(section_index as u8, &data);
+			game_canvas.push_leaf(leaf).unwrap();
+		}
+
+		assert!(game_canvas.complete());
+		assert!(game_canvas.push_leaf([0; 32]).is_err());
+
+		let root = crate::get_canvas_root(&game_canvas.leaves);
+		game_canvas.leaves[100] = [1; 32];
+		assert_ne!(crate::get_canvas_root(&game_canvas.leaves), root);
+	}
+
+	#[test]
+	fn player_milestones() {
+		let mut player = PlayerState::new(Pubkey::new_unique(), 0, 0);
+		let mut section = SectionState::new(Pubkey::new_unique(), 0, 0, 0);
+
+		section.flip_on(1).unwrap();
+		player.record_flips(&section, 0, false, 1).unwrap();
+		assert!(!player.earned(BadgeType::FirstFlip));
+
+		player.record_flips(&section, 1, true, 1).unwrap();
+		assert!(player.earned(BadgeType::FirstFlip));
+		assert!(!player.earned(BadgeType::SectionThreshold));
+
+		section.flips = MINIMUM_FLIPS_PER_SECTION.into();
+		player
+			.record_flips(
+				&section,
+				MINIMUM_FLIPS_PER_SECTION - 1,
+				false,
+				PLAYER_FLIPS_MILESTONE - 3,
+			)
+			.unwrap();
+		assert!(player.earned(BadgeType::SectionThreshold));
+		assert!(!player.earned(BadgeType::PlayerFlips));
+
+		section.flips = (MINIMUM_FLIPS_PER_SECTION + 1).into();
+		player
+			.record_flips(&section, MINIMUM_FLIPS_PER_SECTION, false, 1)
+			.unwrap();
+		assert!(player.earned(BadgeType::PlayerFlips));
+
+		player.claim(BadgeType::PlayerFlips).unwrap();
+		assert!(player.claim(BadgeType::PlayerFlips).is_err());
+	}
+
+	#[test]
+	fn section_metadata_gate() {
+		let mut section_metadata = SectionMetadataState::new(0, 0, 0);
+		assert_eq!(section_metadata.gate(), None);
+
+		section_metadata.update_gate(TokenMember::Kibibit, 1_000);
+		assert_eq!(section_metadata.gate(), Some((TokenMember::Kibibit, 1_000)));
+
+		section_metadata.update_gate(TokenMember::Kibibit, 0);
+		assert_eq!(section_metadata.gate(), None);
+	}
+
+	#[test]
+	fn section_locks() {
+		let holder = Pubkey::new_unique();
+		let player = Pubkey::new_unique();
+		let mut locks = SectionLocksState::new(0, 0, 0);
+		let lock = SectionLock {
+			holder,
+			expiry: 100.into(),
+			x: 4,
+			y: 4,
+			width: 4,
+			height: 4,
+		};
+		let (inside_index, inside_offset) = get_index_offset(5, 6);
+		let (outside_index, outside_offset) = get_index_offset(8, 6);
+
+		locks.lock(lock, 0).unwrap();
+		assert!(locks
+			.assert_unlocked(&player, inside_index, inside_offset, 50)
+			.is_err());
+		assert!(locks
+			.assert_unlocked(&holder, inside_index, inside_offset, 50)
+			.is_ok());
+		assert!(locks
+			.assert_unlocked(&player, outside_index, outside_offset, 50)
+			.is_ok());
+		assert!(locks
+			.assert_unlocked(&player, inside_index, inside_offset, 100)
+			.is_ok());
+		assert!(locks.assert_section_unlocked(&player, 50).is_err());
+		assert!(locks.assert_section_unlocked(&holder, 50).is_ok());
+		assert!(locks.assert_section_unlocked(&player, 100).is_ok());
+
+		let overlapping = SectionLock {
+			holder: player,
+			x: 7,
+			y: 7,
+			..lock
+		};
+		assert_eq!(
+			locks.lock(overlapping, 50).unwrap_err(),
+			BitflipError::BitLocked.into()
+		);
+
+		for _ in 1..MAX_SECTION_LOCKS {
+			locks.lock(lock, 50).unwrap();
+		}
+		assert_eq!(
+			locks.lock(lock, 50).unwrap_err(),
+			BitflipError::SectionLocksFull.into()
+		);
+
+		// expired locks are replaced
+		locks.lock(overlapping, 100).unwrap();
+	}
+
+	#[test]
+	fn stake_rewards() {
+		let mut pool = StakePoolState::new(1_000, 60, 0);
+		let mut alice = StakeState::new(Pubkey::new_unique(), pool.reward_index(), 0);
+		let mut bob = StakeState::new(Pubkey::new_unique(), pool.reward_index(), 0);
+
+		// nothing is shared without stakers
+		assert_eq!(pool.revenue_share(10_000), 0);
+
+		alice.stake(&mut pool, TokenMember::Bit, 1024, 60).unwrap();
+		bob.stake(&mut pool, TokenMember::Kibibit, 3, 60).unwrap();
+		assert_eq!(alice.weight(), 1024);
+		assert_eq!(bob.weight(), 3 * 1024);
+		assert_eq!(pool.total_weight(), 4 * 1024);
+		assert_eq!(pool.revenue_share(10_000), 1_000);
+
+		pool.deposit(4_000).unwrap();
+		assert_eq!(alice.pending_rewards(&pool), 1_000);
+		assert_eq!(bob.pending_rewards(&pool), 3_000);
+
+		assert_eq!(
+			alice
+				.unstake(&mut pool, TokenMember::Bit, 1024, 59)
+				.unwrap_err(),
+			BitflipError::StakeLocked.into()
+		);
+		assert_eq!(
+			alice
+				.unstake(&mut pool, TokenMember::Bit, 1025, 60)
+				.unwrap_err(),
+			BitflipError::StakeInsufficient.into()
+		);
+		alice
+			.unstake(&mut pool, TokenMember::Bit, 1024, 60)
+			.unwrap();
+		assert_eq!(alice.weight(), 0);
+		assert_eq!(pool.total_weight(), 3 * 1024);
+
+		// only the remaining stakers earn from new deposits
+		pool.deposit(3_000).unwrap();
+		assert_eq!(alice.claim(&pool).unwrap(), 1_000);
+		assert_eq!(alice.claim(&pool).unwrap(), 0);
+		assert_eq!(bob.claim(&pool).unwrap(), 6_000);
+	}
+
+	#[test]
+	fn game_update_parameter() {
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, 0);
+
+		game.update_parameter(GameParameter::MaxLamports, 200_000)
+			.unwrap();
+		assert_eq!(game.max_lamports(), 200_000);
+		game.update_parameter(GameParameter::CooldownSlots, 10)
+			.unwrap();
+		assert_eq!(game.cooldown_slots(), 10);
+
+		let previous = game;
+		assert_eq!(
+			game.update_parameter(GameParameter::MinLamports, game.base_lamports() + 1)
+				.unwrap_err(),
+			BitflipError::GameParameterInvalid.into()
+		);
+		assert_eq!(
+			game.update_parameter(GameParameter::Duration, 0)
+				.unwrap_err(),
+			BitflipError::GameParameterInvalid.into()
+		);
+		assert_eq!(
+			game.update_parameter(GameParameter::CooldownMultiplier, 256)
+				.unwrap_err(),
+			BitflipError::GameParameterInvalid.into()
+		);
+		assert_eq!(game, previous);
+	}
+
+	#[test]
+	fn section_seed() {
+		let mut section = SectionState::new(Pubkey::default(), 0, 0, 0);
+		section.seed(2, &[0b1011, u16::MAX]).unwrap();
+		assert_eq!(u16::from(section.data[2]), 0b1011);
+		assert_eq!(u16::from(section.data[3]), u16::MAX);
+		assert_eq!(section.on(), 19);
+		assert_eq!(section.off(), BITFLIP_SECTION_TOTAL_BITS - 19);
+		assert_eq!(section.flips(), 0);
+
+		// overwriting recomputes the counts
+		section.seed(3, &[0]).unwrap();
+		assert_eq!(section.on(), 3);
+
+		assert_eq!(
+			section
+				.seed(BITFLIP_SECTION_LENGTH - 1, &[1, 1])
+				.unwrap_err(),
+			BitflipError::SectionSeedRangeInvalid.into()
+		);
+		assert_eq!(section.on(), 3);
+	}
+
+	#[test]
+	fn game_inherit() {
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 1, 0);
+		game.schedule(100);
+		assert!(!game.reserved(100));
+
+		game.flags = GameFlags::INHERIT.bits();
+		assert!(game.reserved(100));
+		assert!(game.reserved(100 + INHERIT_CLAIM_DURATION - 1));
+		assert!(!game.reserved(100 + INHERIT_CLAIM_DURATION));
+		assert_eq!(GameState::inherit_lamports(10_000), 7_500);
+
+		let mut previous = SectionState::new(Pubkey::new_unique(), 0, 0, 0);
+		previous.seed(0, &[u16::MAX]).unwrap();
+		previous.flips = 20.into();
+		let mut section = SectionState::new(Pubkey::new_unique(), 1, 0, 0);
+		section.inherit(&previous);
+		assert_eq!(section.data, previous.data);
+		assert_eq!(section.on(), 16);
+		assert_eq!(section.flips(), 0);
+	}
+
+	#[test]
+	fn game_schedule() {
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, 0);
+		assert!(!game.scheduled(0));
+		assert!(!game.running(0));
+
+		game.schedule(100);
+		assert!(game.scheduled(99));
+		assert!(!game.started(99));
+		assert!(!game.running(99));
+		assert!(!game.scheduled(100));
+		assert!(game.started(100));
+		assert!(game.running(100));
+		assert!(game.running(game.end_time()));
+		assert!(!game.running(game.end_time() + 1));
+	}
+
+	#[test]
+	fn game_ended() {
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, 0);
+		assert!(!game.ended(0));
+		assert!(!game.ended(SESSION_DURATION + 1));
+
+		game.schedule(100);
+		assert!(!game.ended(99));
+		assert!(!game.ended(game.end_time()));
+		assert!(game.ended(game.end_time() + 1));
+
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, 0);
+		game.status = GameStatus::Ended.into();
+		assert!(game.ended(0));
+	}
+
+	#[test]
+	fn proposal_execute() {
+		let mut game = GameState::new(Pubkey::new_unique(), Pubkey::new_unique(), 0, 0);
+		let mut proposal = ProposalState::new(
+			Pubkey::new_unique(),
+			0,
+			0,
+			GameParameter::Duration,
+			60,
+			100,
+			0,
+		);
+
+		proposal.vote(true, GOVERNANCE_QUORUM, 0).unwrap();
+		proposal.vote(false, 1, 99).unwrap();
+		assert_eq!(
+			proposal.vote(true, 1, 100).unwrap_err(),
+			BitflipError::ProposalClosed.into()
+		);
+		assert_eq!(
+			proposal.execute(&mut game, 99).unwrap_err(),
+			BitflipError::ProposalActive.into()
+		);
+
+		proposal.execute(&mut game, 100).unwrap();
+		assert_eq!(proposal.status(), ProposalStatus::Executed);
+		assert_eq!(game.duration(), 60);
+		assert_eq!(
+			proposal.execute(&mut game, 100).unwrap_err(),
+			BitflipError::ProposalClosed.into()
+		);
+
+		// proposals without a quorum are rejected
+		let mut proposal = ProposalState::new(
+			Pubkey::new_unique(),
+			0,
+			1,
+			GameParameter::Duration,
+			120,
+			100,
+			0,
+		);
+		proposal.vote(true, GOVERNANCE_QUORUM - 1, 0).unwrap();
+		proposal.execute(&mut game, 100).unwrap();
+		assert_eq!(proposal.status(), ProposalStatus::Rejected);
+		assert_eq!(game.duration(), 60);
+
+		// passed proposals with an invalid change fail without an error
+		let mut proposal = ProposalState::new(
+			Pubkey::new_unique(),
+			0,
+			2,
+			GameParameter::MinLamports,
+			0,
+			100,
+			0,
+		);
+		proposal.vote(true, GOVERNANCE_QUORUM, 0).unwrap();
+		proposal.execute(&mut game, 100).unwrap();
+		assert_eq!(proposal.status(), ProposalStatus::Failed);
+		assert_eq!(game.min_lamports(), MIN_LAMPORTS_PER_BIT);
+		assert_eq!(
+			proposal.execute(&mut game, 100).unwrap_err(),
+			BitflipError::ProposalClosed.into()
+		);
+	}
+
+	#[test]
+	fn section_cooldown() {
+		let mut cooldown = SectionCooldownState::new(0, 0, 0);
+
+		assert_eq!(cooldown.touch(3, 100, 10, 0).unwrap(), 1);
+		assert_eq!(cooldown.last_slot(3), 100);
+		assert_eq!(
+			cooldown.touch(3, 105, 10, 0).unwrap_err(),
+			BitflipError::BitCooldown.into()
+		);
+		assert_eq!(cooldown.last_slot(3), 100);
+		assert_eq!(cooldown.touch(4, 105, 10, 0).unwrap(), 1);
+		assert_eq!(cooldown.touch(3, 105, 10, 3).unwrap(), 3);
+		assert_eq!(cooldown.last_slot(3), 105);
+		assert_eq!(cooldown.touch(3, 115, 10, 3).unwrap(), 1);
+
+		// the truncated slot wraps around
+		let slot = u64::from(u32::MAX) - 2;
+		assert_eq!(cooldown.touch(5, slot, 10, 0).unwrap(), 1);
+		assert!(cooldown.touch(5, slot + 5, 10, 0).is_err());
+		assert_eq!(cooldown.touch(5, slot + 20, 10, 0).unwrap(), 1);
+	}
+
+	#[test]
+	fn team_rewards() {
+		let mut pool = TeamPoolState::new(0, 0);
+		let mut on = PlayerTeamState::new(Pubkey::new_unique(), 0, Team::On, 0);
+		let mut other_on = PlayerTeamState::new(Pubkey::new_unique(), 0, Team::On, 0);
+		let mut off = PlayerTeamState::new(Pubkey::new_unique(), 0, Team::Off, 0);
+		let flip = |value| {
+			FlipBit {
+				section_index: 0,
+				array_index: 0,
+				offset: 0,
+				value,
+				mine: 0,
+				expected: 0,
+				flags: 0,
+				max_lamports_per_bit: MAX_LAMPORTS_PER_BIT.into(),
+				player_bump: 0,
+				section_locks_bump: 0,
+				section_metadata_bump: 0,
+			}
+		};
+
+		// flips only count towards the color of the team
+		assert_eq!(on.record_flip(&mut pool, &flip(0)).unwrap(), 0);
+		assert_eq!(on.record_flip(&mut pool, &flip(1)).unwrap(), 1);
+		on.record_flip(&mut pool, &flip(1)).unwrap();
+		other_on.record_flip(&mut pool, &flip(1)).unwrap();
+		off.record_flip(&mut pool, &flip(0)).unwrap();
+		assert_eq!(pool.flips(Team::On), 3);
+		assert_eq!(pool.flips(Team::Off), 1);
+
+		pool.deposit(TeamPoolState::revenue_share(30_000)).unwrap();
+		assert_eq!(pool.rewards(), 3_000);
+
+		let mut section = SectionState::new(Pubkey::default(), 0, 0, 0);
+		section.flip_on(BITFLIP_SECTION_TOTAL_BITS / 2 + 1).unwrap();
+		pool.tally(&section).unwrap();
+		assert_eq!(pool.winner(), Some(Team::On));
+
+		assert_eq!(on.claim(&pool).unwrap(), 2_000);
+		assert_eq!(other_on.claim(&pool).unwrap(), 1_000);
+		assert_eq!(
+			on.claim(&pool).unwrap_err(),
+			BitflipError::TeamRewardClaimed.into()
+		);
+		assert_eq!(
+			off.claim(&pool).unwrap_err(),
+			BitflipError::TeamNotWinner.into()
+		);
+
+		// both teams share the pool on a tie
+		pool.bits = [0.into(); 2];
+		assert_eq!(pool.winner(), None);
+		assert_eq!(off.reward(&pool).unwrap(), 750);
 	}
 }
@@ -1,5 +1,29 @@
+use solana_program::hash::hashv;
 use steel::ProgramError;
 
+use crate::BitflipError;
+use crate::CanvasLeaves;
+use crate::PodSectionData;
+use crate::SectionData;
+use crate::SectionEdge;
+use crate::TokenMember;
+use crate::BITFLIP_SECTION_WIDTH;
+use crate::LOCK_LAMPORTS_PER_BIT_SECOND;
+use crate::TOKEN_PAYMENT_BURN_BASIS_POINTS;
+
+/// Log a message only when the `logs` feature is enabled.
+///
+/// Formatting and logging are charged compute units so the messages of hot
+/// instructions like `FlipBit` are compiled out of the default build.
+macro_rules! debug_msg {
+	($($arg:tt)*) => {
+		#[cfg(feature = "logs")]
+		::solana_program::msg!($($arg)*);
+	};
+}
+
+pub(crate) use debug_msg;
+
 #[inline(always)]
 pub fn get_token_amount(tokens: u64, decimals: u8) -> Result<u64, ProgramError> {
 	tokens
@@ -7,6 +31,208 @@ pub fn get_token_amount(tokens: u64, decimals: u8) -> Result<u64, ProgramError>
 		.ok_or(ProgramError::ArithmeticOverflow)
 }
 
+/// Get the index of the `u16` within the [`SectionData`] and the offset of the
+/// bit for the `(x, y)` coordinate of the `64x64` section grid.
+///
+/// Each `u16` represents a `4x4` block of bits and each group of `4x4` blocks
+/// is stored sequentially.
+#[inline(always)]
+pub const fn get_index_offset(x: u16, y: u16) -> (u8, u8) {
+	let index = ((x / 16 + (y / 16) * 4) * 16) + ((x / 4) % 4) + 4 * ((y / 4) % 4);
+	let offset = x % 4 + (y % 4) * 4;
+
+	(index as u8, offset as u8)
+}
+
+/// Get the `(x, y)` coordinate of the `64x64` section grid for the index of the
+/// `u16` within the [`SectionData`] and the offset of the bit. This is the
+/// inverse of [`get_index_offset`].
+#[inline(always)]
+pub const fn get_coordinates(index: u8, offset: u8) -> (u16, u16) {
+	let (index, offset) = (index as u16, offset as u16);
+	let block_x = (index / 16 % 4) * 4 + index % 4;
+	let block_y = (index / 16 / 4) * 4 + (index % 16) / 4;
+
+	(block_x * 4 + offset % 4, block_y * 4 + offset / 4)
+}
+
+/// The commitment to a mine layout which is stored when the section is
+/// unlocked. The `salt` prevents players from brute forcing the layout.
+pub fn get_mines_commitment(mines: &SectionData, salt: &[u8; 32]) -> [u8; 32] {
+	let bytes = mines
+		.iter()
+		.flat_map(|value| value.to_le_bytes())
+		.collect::<Vec<u8>>();
+
+	hashv(&[&bytes, salt]).to_bytes()
+}
+
+/// The rows of the `64x64` section grid where bit `x` of row `y` is the
+/// `(x, y)` coordinate.
+pub type SectionRows = [u64; BITFLIP_SECTION_WIDTH as usize];
+
+/// Convert the [`SectionData`] from the `4x4` block layout into
+/// [`SectionRows`]. Each `u16` holds four rows of four bits.
+pub fn get_section_rows(data: &SectionData) -> SectionRows {
+	let mut rows = [0u64; BITFLIP_SECTION_WIDTH as usize];
+
+	for (index, value) in data.iter().enumerate() {
+		let block_x = (index / 16 % 4) * 4 + index % 4;
+		let block_y = (index / 16 / 4) * 4 + (index % 16) / 4;
+
+		for row in 0..4 {
+			let nibble = u64::from((value >> (row * 4)) & 0xF);
+			rows[block_y * 4 + row] |= nibble << (block_x * 4);
+		}
+	}
+
+	rows
+}
+
+/// Convert the [`SectionRows`] back into the `4x4` block layout of the
+/// [`SectionData`]. This is the inverse of [`get_section_rows`].
+pub fn get_section_data(rows: &SectionRows) -> SectionData {
+	std::array::from_fn(|index| {
+		let block_x = (index / 16 % 4) * 4 + index % 4;
+		let block_y = (index / 16 / 4) * 4 + (index % 16) / 4;
+
+		(0..4).fold(0u16, |value, row| {
+			let nibble = ((rows[block_y * 4 + row] >> (block_x * 4)) & 0xF) as u16;
+			value | (nibble << (row * 4))
+		})
+	})
+}
+
+/// Advance the [`SectionRows`] by one generation of Conway's Game of Life.
+///
+/// The neighbours of every cell in a row are counted in parallel using bitwise
+/// adders so that a whole row is updated with a handful of operations.
+pub fn get_next_generation(rows: &SectionRows, edge: SectionEdge) -> SectionRows {
+	let last = rows.len() - 1;
+	let shift = |row: u64| {
+		match edge {
+			SectionEdge::Dead => (row << 1, row >> 1),
+			SectionEdge::Torus => (row.rotate_left(1), row.rotate_right(1)),
+		}
+	};
+
+	std::array::from_fn(|y| {
+		let alive = rows[y];
+		let above = match (y, edge) {
+			(0, SectionEdge::Dead) => 0,
+			(0, SectionEdge::Torus) => rows[last],
+			_ => rows[y - 1],
+		};
+		let below = match (y == last, edge) {
+			(true, SectionEdge::Dead) => 0,
+			(true, SectionEdge::Torus) => rows[0],
+			_ => rows[y + 1],
+		};
+		let (above_left, above_right) = shift(above);
+		let (left, right) = shift(alive);
+		let (below_left, below_right) = shift(below);
+		// bitwise counters for the number of live neighbours: `ones` and `twos`
+		// hold the count and `many` is set once it reaches four.
+		let (mut ones, mut twos, mut many) = (0u64, 0u64, 0u64);
+
+		for neighbours in [
+			above_left,
+			above,
+			above_right,
+			left,
+			right,
+			below_left,
+			below,
+			below_right,
+		] {
+			let carry = ones & neighbours;
+			ones ^= neighbours;
+			many |= twos & carry;
+			twos ^= carry;
+		}
+
+		// a cell lives with exactly three neighbours, or two if already alive.
+		!many & twos & (ones | alive)
+	})
+}
+
+/// The mask for the lowest `width` bits of a row.
+pub const fn get_region_mask(width: u8) -> u64 {
+	if width as u32 >= u64::BITS {
+		u64::MAX
+	} else {
+		(1 << width) - 1
+	}
+}
+
+/// The price of locking a `width` by `height` region for `duration` seconds.
+pub fn get_lock_price_in_lamports(width: u8, height: u8, duration: i64) -> u64 {
+	let bits = u64::from(width) * u64::from(height);
+
+	bits.saturating_mul(duration.max(0) as u64)
+		.saturating_mul(LOCK_LAMPORTS_PER_BIT_SECOND)
+}
+
+/// Check that the rectangular region fits within the `64x64` section grid.
+pub fn validate_region(x: u8, y: u8, width: u8, height: u8) -> Result<(), ProgramError> {
+	let fits = |start: u8, length: u8| {
+		length > 0 && u16::from(start) + u16::from(length) <= BITFLIP_SECTION_WIDTH
+	};
+
+	if !fits(x, width) || !fits(y, height) {
+		return Err(BitflipError::BountyRegionInvalid.into());
+	}
+
+	Ok(())
+}
+
+/// The tokens of the `member` paid for a flip which costs `lamports`. Each bit
+/// is valued at the `base_lamports` of the game and the price is rounded up to
+/// a whole bit, the smallest unit of the tokens. Paying with a larger member
+/// fails unless the bits are a whole number of its tokens so that the player is
+/// never charged for more bits than the flip costs. Pay with
+/// [`TokenMember::Bit`] for any other amount.
+pub fn get_token_payment(
+	lamports: u64,
+	base_lamports: u64,
+	member: TokenMember,
+) -> Result<u64, ProgramError> {
+	let bits = lamports.div_ceil(base_lamports.max(1));
+
+	if bits % member.bits() != 0 {
+		return Err(BitflipError::TokenPaymentInexact.into());
+	}
+
+	Ok(bits / member.bits())
+}
+
+/// The tokens of a token payment which are burned.
+pub fn get_token_payment_burn(tokens: u64) -> u64 {
+	tokens.saturating_mul(TOKEN_PAYMENT_BURN_BASIS_POINTS) / 10_000
+}
+
+/// The leaf of the canvas merkle tree for a section.
+pub fn get_canvas_leaf(section_index: u8, data: &PodSectionData) -> [u8; 32] {
+	hashv(&[&[section_index], bytemuck::bytes_of(data)]).to_bytes()
+}
+
+/// The merkle root of the canvas leaves. Each parent is the hash of its left
+/// and right children.
+pub fn get_canvas_root(leaves: &CanvasLeaves) -> [u8; 32] {
+	let mut nodes = leaves.to_vec();
+	let mut length = nodes.len();
+
+	while length > 1 {
+		for index in 0..length / 2 {
+			nodes[index] = hashv(&[&nodes[index * 2], &nodes[index * 2 + 1]]).to_bytes();
+		}
+
+		length /= 2;
+	}
+
+	nodes[0]
+}
+
 #[cfg(feature = "client")]
 pub fn round_up(amount: u64, significant_digits: u8) -> u64 {
 	let multiplier = 10u64.pow(significant_digits.into());
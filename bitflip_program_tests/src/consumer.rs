@@ -0,0 +1,83 @@
+//! A sample program which composes with the bitflip program through
+//! [`bitflip_program::interface`]. It owns a vault which steps sections of the
+//! game on behalf of its depositors.
+
+use bitflip_program::interface::SectionStepAccounts;
+use bitflip_program::SectionEdge;
+use bitflip_program::SectionStep;
+use steel::*;
+
+/// The id of the sample consumer program.
+pub const ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// The seed of the vault which pays for and signs the steps.
+pub const SEED_VAULT: &[u8] = b"vault";
+
+pub fn get_pda_vault() -> (Pubkey, u8) {
+	Pubkey::find_program_address(&[SEED_VAULT], &ID)
+}
+
+/// Step a section of the bitflip game with the vault as the player.
+///
+/// The instruction data is the `[section_index, edge, section_locks_bump]` of
+/// the step and the accounts are `[vault, bitflip_program, game, section,
+/// section_locks, system_program]`.
+pub fn process_instruction(
+	program_id: &Pubkey,
+	accounts: &[AccountInfo],
+	data: &[u8],
+) -> ProgramResult {
+	if program_id.ne(&ID) {
+		return Err(ProgramError::IncorrectProgramId);
+	}
+
+	let [section_index, edge, section_locks_bump] = data else {
+		return Err(ProgramError::InvalidInstructionData);
+	};
+	let edge = SectionEdge::try_from(*edge).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+	let [vault_info, bitflip_program_info, game_info, section_info, section_locks_info, system_program_info] =
+		accounts
+	else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	let vault_bump = vault_info.assert_canonical_bump(&[SEED_VAULT], &ID)?;
+	vault_info.assert_writable()?;
+
+	SectionStepAccounts {
+		program: bitflip_program_info,
+		player: vault_info,
+		game: game_info,
+		section: section_info,
+		section_locks: section_locks_info,
+		system_program: system_program_info,
+		remaining_accounts: &[],
+	}
+	.invoke_signed(
+		&SectionStep::new(*section_index, edge, *section_locks_bump),
+		&[&[SEED_VAULT, &[vault_bump]]],
+	)
+}
+
+/// Create the instruction which steps a section through the vault.
+pub fn vault_section_step(game_index: u8, section_index: u8, edge: SectionEdge) -> Instruction {
+	let (section_locks, section_locks_bump) =
+		bitflip_program::get_pda_section_locks(game_index, section_index);
+
+	Instruction {
+		program_id: ID,
+		accounts: vec![
+			AccountMeta::new(get_pda_vault().0, false),
+			AccountMeta::new_readonly(bitflip_program::ID, false),
+			AccountMeta::new_readonly(bitflip_program::get_pda_game(game_index).0, false),
+			AccountMeta::new(
+				bitflip_program::get_pda_section(game_index, section_index).0,
+				false,
+			),
+			AccountMeta::new_readonly(section_locks, false),
+			AccountMeta::new_readonly(system_program::ID, false),
+		],
+		data: vec![section_index, edge.into(), section_locks_bump],
+	}
+}
@@ -9,9 +9,11 @@ use bitflip_program::get_pda_game;
 use bitflip_program::get_pda_mint;
 use bitflip_program::get_pda_section;
 use bitflip_program::get_pda_treasury;
+use bitflip_program::get_player_token_account;
 use bitflip_program::get_section_token_account;
 use bitflip_program::get_token_amount;
 use bitflip_program::get_treasury_token_account;
+use bitflip_program::player_token_account_initialize;
 use bitflip_program::ConfigState;
 use bitflip_program::GameState;
 use bitflip_program::GameStatus;
@@ -32,6 +34,7 @@ use solana_sdk::pubkey::Pubkey;
 use solana_sdk::rent::Rent;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signature::Signer;
+use solana_sdk::transaction::VersionedTransaction;
 use spl_pod::bytemuck::pod_get_packed_len;
 use spl_pod::primitives::PodBool;
 use spl_token_2022::extension::group_member_pointer::GroupMemberPointer;
@@ -53,12 +56,15 @@ use test_utils_keypairs::get_admin_keypair;
 use test_utils_keypairs::get_authority_keypair;
 use test_utils_keypairs::get_treasury_keypair;
 use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
 use test_utils_solana::processor;
 use test_utils_solana::solana_sdk::account::Account;
 use test_utils_solana::ProgramTest;
 use test_utils_solana::TestRpcProvider;
 use wasm_client_solana::SolanaRpcClient;
 
+pub mod consumer;
+
 #[cfg(feature = "test_validator")]
 const DEVENV_ROOT: &str = env!("DEVENV_ROOT");
 
@@ -167,6 +173,55 @@ pub fn get_wallet(rpc: &SolanaRpcClient, payer: &Keypair) -> MemoryWallet {
 	MemoryWallet::new(rpc.clone(), &[payer.insecure_clone()])
 }
 
+/// Create the `$BIT` token account of the player which must exist before
+/// their first lamport flip.
+pub async fn initialize_player_token_account(
+	rpc: &SolanaRpcClient,
+	player_keypair: &Keypair,
+) -> anyhow::Result<()> {
+	let player = player_keypair.pubkey();
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = player_token_account_initialize(&player);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[player_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	Ok(())
+}
+
+/// Send the `instruction` paid by the `payer` and signed by the extra
+/// `signers`. Returns the compute units consumed by a simulation of the
+/// transaction.
+pub async fn send_instruction(
+	rpc: &SolanaRpcClient,
+	payer: &Keypair,
+	signers: &[&Keypair],
+	instruction: Instruction,
+) -> anyhow::Result<u64> {
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let mut transaction = VersionedTransaction::new_unsigned_v0(
+		&payer.pubkey(),
+		&[instruction],
+		&[],
+		recent_blockhash,
+	)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+	let compute_units = simulation.value.units_consumed.unwrap_or_default();
+	transaction.try_sign(&[payer], None)?;
+
+	for signer in signers {
+		transaction.try_sign(&[*signer], None)?;
+	}
+
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	Ok(compute_units)
+}
+
 pub fn create_config_accounts() -> HashMap<Pubkey, AccountSharedData> {
 	let mut map = HashMap::new();
 	let authority = get_authority_keypair().pubkey();
@@ -236,6 +291,27 @@ pub fn create_token_accounts(
 	Ok(map)
 }
 
+/// Create the token account of the `player` for the `member` holding `amount`
+/// tokens.
+pub fn create_player_token_account(
+	player: Pubkey,
+	member: TokenMember,
+	amount: u64,
+) -> anyhow::Result<(Pubkey, AccountSharedData)> {
+	let player_token_account = get_player_token_account(&player, member);
+	let token_amount = get_token_amount(amount, member.decimals())?;
+	let data = create_token_account_data(member, player, player, token_amount)?;
+	let account = AccountSharedData::create(
+		Rent::default().minimum_balance(data.len()),
+		data,
+		spl_token_2022::ID,
+		false,
+		u64::MAX,
+	);
+
+	Ok((player_token_account, account))
+}
+
 fn create_mint_data(
 	member: TokenMember,
 	treasury: Pubkey,
@@ -505,27 +581,47 @@ pub fn save_compute_units(name: &str, compute_units: u64, description: &str) ->
 		.output()
 		.context("Failed to run dprint fmt command")?;
 
+	generate_compute_constants()?;
+
 	Ok(())
 }
 
+/// Generate the compute unit constants of the client from
+/// `compute_units.json`.
 #[cfg(feature = "test_validator")]
-pub fn generate_compute_constants() -> std::io::Result<()> {
-	let data: ComputeUnits = serde_json::from_str(&fs::read_to_string("compute_units.json")?)?;
-
-	let mut output = String::from("// Auto-generated compute unit constants\n\n");
+pub fn generate_compute_constants() -> anyhow::Result<()> {
+	let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+	let path = manifest_dir.join("../bitflip_program/src/compute_units.rs");
+	let json = fs::read_to_string(manifest_dir.join("compute_units.json"))?;
+	let data: ComputeUnits = serde_json::from_str(&json)?;
+
+	let mut output = [
+		"//! The compute unit limits of the instructions recorded by the validator",
+		"//! tests.",
+		"//!",
+		"//! Generated from `bitflip_program_tests/compute_units.json` by",
+		"//! `generate_compute_constants`. Do not edit this file by hand.",
+	]
+	.join("\n");
+
+	let mut arms = String::new();
 
 	for (name, metrics) in data.instructions {
-		let const_name = name.to_uppercase();
+		let const_name = format!("{}_COMPUTE_UNITS", name.replace(':', "_").to_uppercase());
 		output.push_str(&format!(
-			"pub const {}_COMPUTE_UNITS: u64 = {};\n",
-			const_name, metrics.rounded_compute_units
+			"\n\n/// {}\npub const {const_name}: u32 = {};",
+			metrics.description, metrics.rounded_compute_units
 		));
+		arms.push_str(&format!("\n\t\t\"{name}\" => Some({const_name}),"));
 	}
 
-	fs::write(
-		Path::new(&std::env::var("OUT_DIR").unwrap()).join("compute_units.rs"),
-		output,
-	)?;
+	output.push_str("\n\n/// Get the recorded compute units of an instruction by its saved name.");
+	output.push_str("\npub fn get_recorded_compute_units(name: &str) -> Option<u32> {");
+	output.push_str(&format!(
+		"\n\tmatch name {{{arms}\n\t\t_ => None,\n\t}}\n}}\n"
+	));
+
+	fs::write(path, output)?;
 
 	Ok(())
 }
@@ -0,0 +1,79 @@
+use assert2::check;
+use bitflip_program::account_migrate;
+use bitflip_program::get_pda_game;
+use bitflip_program::AccountVersion;
+use bitflip_program::GameState;
+use bitflip_program::BASE_LAMPORTS_PER_STEP;
+use bitflip_program::ID;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::rent::Rent;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+/// The size of a version `0` game account before `step_lamports` was added.
+const GAME_STATE_V0_SPACE: usize = 149;
+
+/// A version `0` game account is resized, topped up for rent and migrated.
+#[test_log::test(tokio::test)]
+async fn account_migrate_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let game = get_pda_game(game_index).0;
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in create_config_accounts() {
+			p.add_account(key, account.into());
+		}
+
+		let game_state = GameState::builder()
+			.version(0)
+			.step_lamports(0)
+			.game_index(game_index)
+			.bump(get_pda_game(game_index).1)
+			.section_index(0)
+			.build();
+		let mut data = vec![0u8; 8];
+		data[0] = GameState::discriminator();
+		data.extend_from_slice(game_state.to_bytes());
+		data.truncate(GAME_STATE_V0_SPACE);
+
+		p.add_account(
+			game,
+			Account {
+				lamports: Rent::default().minimum_balance(GAME_STATE_V0_SPACE),
+				data,
+				owner: ID,
+				..Account::default()
+			},
+		);
+
+		Ok(())
+	})
+	.await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = account_migrate(&wallet, &game);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let game_account = rpc.get_account(&game).await?;
+	check!(game_account.data.len() == GameState::space());
+	check!(game_account.lamports >= Rent::default().minimum_balance(GameState::space()));
+
+	let game_state = GameState::try_from_bytes(&game_account.data)?;
+	check!(game_state.version == GameState::VERSION);
+	check!(game_state.step_lamports() == BASE_LAMPORTS_PER_STEP);
+	check!(!game_state.finalized());
+	check!(game_state.game_index == game_index);
+
+	Ok(())
+}
@@ -0,0 +1,68 @@
+use assert2::check;
+use assert2::let_assert;
+use bitflip_program::fetch_all_sections;
+use bitflip_program::fetch_config;
+use bitflip_program::fetch_game;
+use bitflip_program::fetch_section;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::FetchError;
+use bitflip_program::GameStatus;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_section_state;
+use bitflip_program_tests::ToRpcClient;
+use steel::*;
+use test_utils_keypairs::get_authority_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn fetch_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let owner = Pubkey::new_unique();
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in create_config_accounts() {
+			p.add_account(key, account.into());
+		}
+
+		let game = get_pda_game(game_index).0;
+		let create_game_state = create_game_state(game_index, 3, 0, GameStatus::Running);
+		p.add_account(game, create_game_state.game_state_account.into());
+
+		for (key, account) in create_section_state(owner, game_index, 3, false)? {
+			p.add_account(key, account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+	let rpc = provider.to_rpc();
+
+	let config = fetch_config(&rpc).await?;
+	check!(config.authority == get_authority_keypair().pubkey());
+
+	let game = fetch_game(&rpc, game_index).await?;
+	check!(game.game_index == game_index);
+	check!(game.section_index == 3);
+
+	let section = fetch_section(&rpc, game_index, 1).await?;
+	check!(section.section_index == 1);
+	check!(section.owner == owner);
+
+	let sections = fetch_all_sections(&rpc, game_index).await?;
+	let section_indices = sections
+		.iter()
+		.map(|section| section.section_index)
+		.collect::<Vec<_>>();
+	check!(section_indices == vec![0, 1, 2]);
+
+	let result = fetch_section(&rpc, game_index, 3).await;
+	let_assert!(Err(FetchError::AccountNotFound(address)) = result);
+	check!(address == get_pda_section(game_index, 3).0);
+
+	let result = fetch_game(&rpc, 1).await;
+	check!(let Err(FetchError::AccountNotFound(_)) = result);
+
+	Ok(())
+}
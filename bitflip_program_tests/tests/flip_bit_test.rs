@@ -3,32 +3,62 @@ use std::time::SystemTime;
 
 use assert2::check;
 use bitflip_program::flip_bit;
+use bitflip_program::flip_bit_checked;
+use bitflip_program::flip_bit_with_options;
+use bitflip_program::flip_bit_with_tokens;
+use bitflip_program::game_update_cooldown;
 use bitflip_program::get_pda_game;
 use bitflip_program::get_pda_mint;
+use bitflip_program::get_pda_player;
 use bitflip_program::get_pda_section;
+use bitflip_program::get_pda_section_cooldown;
+use bitflip_program::get_pda_section_mines;
 use bitflip_program::get_player_token_account;
 use bitflip_program::get_section_token_account;
+use bitflip_program::with_first_flip;
+use bitflip_program::BadgeType;
+use bitflip_program::BitflipError;
+use bitflip_program::FlipBitOptions;
+use bitflip_program::GameFlags;
+use bitflip_program::GameState;
 use bitflip_program::GameStatus;
+use bitflip_program::PlayerState;
+use bitflip_program::SectionCooldownState;
+use bitflip_program::SectionMinesState;
 use bitflip_program::SectionState;
 use bitflip_program::TokenMember;
 use bitflip_program::BITFLIP_SECTION_LENGTH;
+use bitflip_program::EARNED_TOKENS_PER_SECTION;
+use bitflip_program::FLIP_BIT_COMPUTE_UNITS;
 use bitflip_program::TOKEN_DECIMALS;
 use bitflip_program_tests::create_config_accounts;
 use bitflip_program_tests::create_game_state;
 use bitflip_program_tests::create_program_context_with_factory;
 use bitflip_program_tests::create_section_state;
 use bitflip_program_tests::create_token_accounts;
+use bitflip_program_tests::initialize_player_token_account;
+use bitflip_program_tests::IntoAccountSharedData;
 use bitflip_program_tests::ToRpcClient;
 use solana_sdk::account::ReadableAccount;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Keypair;
+use solana_sdk::transaction::TransactionError;
 use solana_sdk::transaction::VersionedTransaction;
 use spl_pod::primitives::PodU16;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Account;
 use steel::*;
 use test_utils_insta::create_insta_redaction;
+use test_utils_keypairs::get_authority_keypair;
 use test_utils_keypairs::get_wallet_keypair;
 use test_utils_solana::prelude::*;
 use wasm_client_solana::solana_account_decoder::parse_account_data::SplTokenAdditionalData;
 use wasm_client_solana::solana_account_decoder::parse_token::parse_token_v2;
 
+/// The target compute units of a lamport flip once the player's token account
+/// exists.
+const FLIP_BIT_COMPUTE_UNITS_BUDGET: u64 = 20_000;
+
 #[test_log::test(tokio::test)]
 async fn flip_bit_test() -> anyhow::Result<()> {
 	let game_index = 0;
@@ -55,7 +85,7 @@ async fn flip_bit_test_validator() -> anyhow::Result<()> {
 	.await?;
 	let rounded_compute_units = bitflip_program::round_compute_units_up(compute_units);
 
-	check!(rounded_compute_units <= 100_000);
+	check!(compute_units <= FLIP_BIT_COMPUTE_UNITS_BUDGET);
 	insta::assert_snapshot!(format!("{rounded_compute_units} CU"));
 	bitflip_program_tests::save_compute_units(
 		"flip_bit",
@@ -66,6 +96,552 @@ async fn flip_bit_test_validator() -> anyhow::Result<()> {
 	Ok(())
 }
 
+/// The flip limit used by clients is recorded by `flip_bit_test_validator`.
+/// This runs without the validator so a recording above the budget fails every
+/// test run.
+#[test]
+fn flip_bit_compute_units_budget_test() {
+	check!(u64::from(FLIP_BIT_COMPUTE_UNITS) <= FLIP_BIT_COMPUTE_UNITS_BUDGET);
+}
+
+#[test_log::test(tokio::test)]
+async fn flip_bit_expected_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+
+	// the bit is off so expecting it to be on should fail.
+	let ix = flip_bit_checked(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		Some(true),
+		false,
+	);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::BitUnexpected.into())
+			)
+	);
+
+	let ix = with_first_flip(flip_bit_checked(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		Some(false),
+		false,
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.is_checked(0, 0));
+	check!(section_state.flips() == 1);
+
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn flip_bit_skip_unchanged_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+	let section_lamports = rpc.get_account(&section).await?.lamports();
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+
+	// the bit is already off so nothing is paid or earned.
+	let ix = flip_bit_checked(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		0,
+		u64::MAX,
+		None,
+		true,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_account = rpc.get_account(&section).await?;
+	let section_state = SectionState::try_from_bytes(section_account.data())?;
+	check!(section_account.lamports() == section_lamports);
+	check!(section_state.flips() == 0);
+
+	// without skipping the unchanged bit is charged as two flips.
+	let ix = with_first_flip(flip_bit(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		0,
+		u64::MAX,
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_account = rpc.get_account(&section).await?;
+	let section_state = SectionState::try_from_bytes(section_account.data())?;
+	check!(section_account.lamports() > section_lamports);
+	check!(section_state.flips() == 2);
+
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn flip_bit_price_exceeded_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let player = get_wallet_keypair().pubkey();
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit(&player, game_index, section_index, 0, 0, 1, 0));
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::PriceExceeded.into())
+			)
+	);
+
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn flip_bit_player_token_account_missing_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let player = get_wallet_keypair().pubkey();
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+	));
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::PlayerTokenAccountMissing.into())
+			)
+	);
+
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn flip_bit_cooldown_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let authority_keypair = get_authority_keypair();
+	let player = wallet_keypair.pubkey();
+	let section_cooldown = get_pda_section_cooldown(game_index, section_index).0;
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = game_update_cooldown(&authority_keypair.pubkey(), game_index, 1_000, 0);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair, &authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let ix = with_first_flip(flip_bit_with_options(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		&FlipBitOptions::new(GameFlags::empty(), true),
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_cooldown_data = rpc.get_account_data(&section_cooldown).await?;
+	let section_cooldown_state = SectionCooldownState::try_from_bytes(&section_cooldown_data)?;
+	check!(section_cooldown_state.last_slot(0) > 0);
+	check!(section_cooldown_state.last_slot(1) == 0);
+
+	// another bit within the same word is still cooling down
+	let ix = flip_bit_with_options(
+		&player,
+		game_index,
+		section_index,
+		0,
+		1,
+		1,
+		u64::MAX,
+		&FlipBitOptions::new(GameFlags::empty(), true),
+	);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::BitCooldown.into())
+			)
+	);
+
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn flip_bit_with_tokens_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	shared_flip_bit_with_tokens_test(
+		|| create_banks_client_rpc(game_index, section_index),
+		game_index,
+		section_index,
+	)
+	.await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "test_validator")]
+#[test_log::test(tokio::test)]
+async fn flip_bit_with_tokens_test_validator() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let compute_units = shared_flip_bit_with_tokens_test(
+		|| create_validator_rpc(game_index, section_index),
+		game_index,
+		section_index,
+	)
+	.await?;
+	bitflip_program_tests::save_compute_units(
+		"flip_bit_with_tokens",
+		compute_units,
+		"Flip a single bit from `0` to `1` paid with bit tokens",
+	)?;
+
+	Ok(())
+}
+
+/// A single flip costs far less than a kibibit so paying with kibibits would
+/// overcharge the player.
+#[test_log::test(tokio::test)]
+async fn flip_bit_with_tokens_inexact_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let player = get_wallet_keypair().pubkey();
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit_with_tokens(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		TokenMember::Kibibit,
+	));
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::TokenPaymentInexact.into())
+			)
+	);
+
+	Ok(())
+}
+
+/// The first flip of the game must pass the game account as writable so that
+/// the badge doesn't depend on the client. Later flips leave it read-only.
+#[test_log::test(tokio::test)]
+async fn flip_bit_first_flip_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let game = get_pda_game(game_index).0;
+	let player_state = get_pda_player(game_index, &player).0;
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = flip_bit(&player, game_index, section_index, 0, 0, 1, u64::MAX);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::FirstFlipNotWritable.into())
+			)
+	);
+
+	let ix = with_first_flip(flip_bit(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let game_state_data = rpc.get_account_data(&game).await?;
+	check!(GameState::try_from_bytes(&game_state_data)?.flipped());
+	let player_state_data = rpc.get_account_data(&player_state).await?;
+	let player_state_account = PlayerState::try_from_bytes(&player_state_data)?;
+	check!(player_state_account.earned(BadgeType::FirstFlip));
+
+	let ix = flip_bit(&player, game_index, section_index, 0, 1, 1, u64::MAX);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	Ok(())
+}
+
+/// Paying with tokens moves no tokens out of the section so the flips can pass
+/// the tokens the section earns without breaking the price.
+#[test_log::test(tokio::test)]
+async fn flip_bit_with_tokens_past_token_cap_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let (provider, _) = create_capped_banks_client_rpc(game_index, section_index, 0).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+
+	// earn a bit token with a lamport flip
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let ix = flip_bit_with_tokens(
+		&player,
+		game_index,
+		section_index,
+		0,
+		1,
+		1,
+		u64::MAX,
+		TokenMember::Bit,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.is_checked(0, 1));
+	check!(u64::from(section_state.flips()) == EARNED_TOKENS_PER_SECTION + 2);
+
+	Ok(())
+}
+
+/// A mine hit moves no tokens out of the section so the flips can pass the
+/// tokens the section earns without breaking the price.
+#[test_log::test(tokio::test)]
+async fn flip_bit_mine_past_token_cap_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let (provider, temp_signer_keypair) =
+		create_capped_banks_client_rpc(game_index, section_index, GameFlags::MINESWEEPER.bits())
+			.await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+	let section_mines = get_pda_section_mines(game_index, section_index).0;
+	let section_lamports = rpc.get_balance(&section).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit_with_options(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		&FlipBitOptions::new(GameFlags::MINESWEEPER, false)
+			.minesweeper(temp_signer_keypair.pubkey(), true),
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction
+		.try_sign(&[&wallet_keypair], None)?
+		.try_sign(&[&temp_signer_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(u64::from(section_state.flips()) == EARNED_TOKENS_PER_SECTION + 1);
+
+	let section_mines_data = rpc.get_account_data(&section_mines).await?;
+	let section_mines_state = SectionMinesState::try_from_bytes(&section_mines_data)?;
+	let forfeited_lamports = rpc.get_balance(&section).await? - section_lamports;
+	check!(u16::from(section_mines_state.hits[0]) == 1);
+	check!(section_mines_state.pot() == forfeited_lamports);
+	check!(forfeited_lamports > 0);
+
+	Ok(())
+}
+
+/// Create a running game where the first section has already been flipped as
+/// many times as it has tokens to earn.
+async fn create_capped_banks_client_rpc(
+	game_index: u8,
+	section_index: u8,
+	flags: u8,
+) -> anyhow::Result<(impl ToRpcClient, Keypair)> {
+	let temp_signer_keypair = Keypair::new();
+	let temp_signer = temp_signer_keypair.pubkey();
+	let provider = create_program_context_with_factory(|p| {
+		let mut accounts = create_config_accounts();
+		accounts.extend(create_token_accounts(false)?);
+		accounts.extend(create_section_state(
+			Pubkey::new_unique(),
+			game_index,
+			section_index.saturating_add(1),
+			false,
+		)?);
+
+		let (section, section_bump) = get_pda_section(game_index, section_index);
+		let mut section_state = SectionState::new(
+			Pubkey::new_unique(),
+			game_index,
+			section_index,
+			section_bump,
+		);
+		section_state.flips = (EARNED_TOKENS_PER_SECTION as u32).into();
+		accounts.insert(section, section_state.to_account_shared_data());
+
+		let (section_mines, section_mines_bump) = get_pda_section_mines(game_index, section_index);
+		let section_mines_state =
+			SectionMinesState::new([0; 32], game_index, section_index, section_mines_bump);
+		accounts.insert(section_mines, section_mines_state.to_account_shared_data());
+
+		for (key, account) in accounts {
+			p.add_account(key, account.into());
+		}
+
+		let now = SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.unwrap()
+			.as_secs() as i64;
+		let (game, game_bump) = get_pda_game(game_index);
+		let game_state = GameState::builder()
+			.temp_signer(temp_signer)
+			.start_time(now - 3600)
+			.game_index(game_index)
+			.bump(game_bump)
+			.section_index(section_index)
+			.status(GameStatus::Running)
+			.flags(flags)
+			.build();
+		p.add_account(game, game_state.to_account());
+
+		Ok(())
+	})
+	.await?;
+
+	Ok((provider, temp_signer_keypair))
+}
+
 async fn create_banks_client_rpc(
 	game_index: u8,
 	section_index: u8,
@@ -155,15 +731,20 @@ async fn shared_flip_bit_test<
 	let section_bit_token_account =
 		get_section_token_account(game_index, section_index, TokenMember::Bit);
 	let section = get_pda_section(game_index, section_index).0;
+
+	// create the token account first so only the flip itself is measured.
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+
 	let recent_blockhash = rpc.get_latest_blockhash().await?;
-	let ix = flip_bit(
+	let ix = with_first_flip(flip_bit(
 		&player,
 		game_index,
 		section_index,
 		array_index,
 		offset,
 		value,
-	);
+		u64::MAX,
+	));
 	let mut transaction =
 		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
 
@@ -215,3 +796,72 @@ async fn shared_flip_bit_test<
 
 	Ok(compute_units)
 }
+
+async fn shared_flip_bit_with_tokens_test<
+	T: ToRpcClient,
+	Fut: Future<Output = anyhow::Result<T>>,
+	Create: FnOnce() -> Fut,
+>(
+	create_provider: Create,
+	game_index: u8,
+	section_index: u8,
+) -> anyhow::Result<u64> {
+	let provider = create_provider().await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+	let player_bit_token_account = get_player_token_account(&player, TokenMember::Bit);
+
+	// earn a bit token with a lamport flip
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let player_token_data = rpc.get_account_data(&player_bit_token_account).await?;
+	let player_token_account = StateWithExtensions::<Account>::unpack(&player_token_data)?;
+	check!(player_token_account.base.amount == 1);
+
+	let ix = flip_bit_with_tokens(
+		&player,
+		game_index,
+		section_index,
+		0,
+		1,
+		1,
+		u64::MAX,
+		TokenMember::Bit,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+	let compute_units = simulation.value.units_consumed.unwrap();
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let player_token_data = rpc.get_account_data(&player_bit_token_account).await?;
+	let player_token_account = StateWithExtensions::<Account>::unpack(&player_token_data)?;
+	check!(player_token_account.base.amount == 0);
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.is_checked(0, 1));
+	check!(section_state.flips() == 2);
+
+	Ok(compute_units)
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::game_finalize;
+use bitflip_program::get_pda_game;
+use bitflip_program::BitflipError;
+use bitflip_program::GameStatus;
+use bitflip_program::SESSION_DURATION;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_section_state;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn game_finalize_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let start_time = now() - SESSION_DURATION - 3600;
+	shared_game_finalize_test(
+		|| create_banks_client_rpc(game_index, start_time, GameStatus::Running),
+		game_index,
+	)
+	.await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "test_validator")]
+#[test_log::test(tokio::test)]
+async fn game_finalize_test_validator() -> anyhow::Result<()> {
+	let game_index = 0;
+	let start_time = now() - SESSION_DURATION - 3600;
+	let compute_units = shared_game_finalize_test(
+		|| create_validator_rpc(game_index, start_time, GameStatus::Running),
+		game_index,
+	)
+	.await?;
+	bitflip_program_tests::save_compute_units(
+		"game_finalize",
+		compute_units,
+		"Hash a single section into the canvas",
+	)?;
+
+	Ok(())
+}
+
+/// A game which never started has an end time in the past but must not be
+/// finalized.
+#[test_log::test(tokio::test)]
+async fn game_finalize_not_started_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let provider = create_banks_client_rpc(game_index, 0, GameStatus::Pending).await?;
+	let rpc = provider.to_rpc();
+	let wallet = get_wallet_keypair().pubkey();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = game_finalize(&wallet, game_index, &[0]);
+	let transaction = VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::GameNotEnded.into())
+			)
+	);
+
+	Ok(())
+}
+
+fn now() -> i64 {
+	SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64
+}
+
+fn create_accounts(
+	game_index: u8,
+	start_time: i64,
+	status: GameStatus,
+) -> anyhow::Result<HashMap<Pubkey, AccountSharedData>> {
+	let mut accounts = create_config_accounts();
+	let game = get_pda_game(game_index).0;
+	let create_game_state = create_game_state(game_index, 1, start_time, status);
+	accounts.insert(game, create_game_state.game_state_account);
+	accounts.extend(create_section_state(
+		Pubkey::new_unique(),
+		game_index,
+		1,
+		false,
+	)?);
+
+	Ok(accounts)
+}
+
+async fn create_banks_client_rpc(
+	game_index: u8,
+	start_time: i64,
+	status: GameStatus,
+) -> anyhow::Result<impl ToRpcClient> {
+	let accounts = create_accounts(game_index, start_time, status)?;
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in accounts.clone() {
+			p.add_account(key, account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
+
+#[cfg(feature = "test_validator")]
+async fn create_validator_rpc(
+	game_index: u8,
+	start_time: i64,
+	status: GameStatus,
+) -> anyhow::Result<impl ToRpcClient> {
+	let accounts = create_accounts(game_index, start_time, status)?;
+	let runner = bitflip_program_tests::create_runner_with_accounts(accounts).await;
+
+	Ok(runner)
+}
+
+async fn shared_game_finalize_test<
+	T: ToRpcClient,
+	Fut: Future<Output = anyhow::Result<T>>,
+	Create: FnOnce() -> Fut,
+>(
+	create_provider: Create,
+	game_index: u8,
+) -> anyhow::Result<u64> {
+	let provider = create_provider().await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = game_finalize(&wallet, game_index, &[0]);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+	let compute_units = simulation.value.units_consumed.unwrap();
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	Ok(compute_units)
+}
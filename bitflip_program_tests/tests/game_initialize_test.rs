@@ -3,7 +3,9 @@ use std::future::Future;
 use assert2::check;
 use bitflip_program::game_initialize;
 use bitflip_program::get_pda_game;
+use bitflip_program::GameFlags;
 use bitflip_program::GameState;
+use bitflip_program::BASE_LAMPORTS_PER_STEP;
 use bitflip_program_tests::create_config_accounts;
 use bitflip_program_tests::create_program_context_with_factory;
 use bitflip_program_tests::create_token_accounts;
@@ -81,7 +83,14 @@ async fn shared_game_initialize_test<
 	let funded_signer = funded_signer_keypair.pubkey();
 	let game = get_pda_game(game_index).0;
 	let recent_blockhash = rpc.get_latest_blockhash().await?;
-	let ix = game_initialize(&authority, &temp_signer, &funded_signer, game_index);
+	let ix = game_initialize(
+		&authority,
+		&temp_signer,
+		&funded_signer,
+		game_index,
+		GameFlags::empty(),
+		BASE_LAMPORTS_PER_STEP,
+	);
 	let mut transaction =
 		VersionedTransaction::new_unsigned_v0(&authority, &[ix], &[], recent_blockhash)?;
 
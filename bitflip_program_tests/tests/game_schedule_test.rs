@@ -0,0 +1,83 @@
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::game_schedule;
+use bitflip_program::get_pda_game;
+use bitflip_program::BitflipError;
+use bitflip_program::GameState;
+use bitflip_program::GameStatus;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_authority_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn game_schedule_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let provider = create_banks_client_rpc(game_index).await?;
+	let rpc = provider.to_rpc();
+	let authority_keypair = get_authority_keypair();
+	let authority = authority_keypair.pubkey();
+	let game = get_pda_game(game_index).0;
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64;
+
+	// the start time must be in the future
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = game_schedule(&authority, game_index, now - 3600);
+	let transaction =
+		VersionedTransaction::new_unsigned_v0(&authority, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::GameStartTimeInvalid.into())
+			)
+	);
+
+	let start_time = now + 3600;
+	let ix = game_schedule(&authority, game_index, start_time);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&authority, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let game_data = rpc.get_account_data(&game).await?;
+	let game_state = GameState::try_from_bytes(&game_data)?;
+	check!(game_state.status() == GameStatus::Running);
+	check!(game_state.start_time() == start_time);
+	check!(game_state.scheduled(now));
+	check!(!game_state.running(now));
+	check!(game_state.running(start_time));
+
+	Ok(())
+}
+
+async fn create_banks_client_rpc(game_index: u8) -> anyhow::Result<impl ToRpcClient> {
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in create_config_accounts() {
+			p.add_account(key, account.into());
+		}
+
+		let game = get_pda_game(game_index).0;
+		let create_game_state = create_game_state(game_index, 0, 0, GameStatus::Pending);
+		p.add_account(game, create_game_state.game_state_account.into());
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
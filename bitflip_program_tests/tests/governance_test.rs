@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_proposal;
+use bitflip_program::get_vote_token_account;
+use bitflip_program::proposal_create;
+use bitflip_program::proposal_execute;
+use bitflip_program::proposal_vote;
+use bitflip_program::vote_withdraw;
+use bitflip_program::BitflipError;
+use bitflip_program::GameParameter;
+use bitflip_program::GameStatus;
+use bitflip_program::ProposalState;
+use bitflip_program::ProposalStatus;
+use bitflip_program::TokenMember;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_player_token_account;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_token_accounts;
+use bitflip_program_tests::send_instruction;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Account;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn governance_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	shared_governance_test(|| create_banks_client_rpc(game_index), game_index).await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "test_validator")]
+#[test_log::test(tokio::test)]
+async fn governance_test_validator() -> anyhow::Result<()> {
+	let game_index = 0;
+	let compute_units =
+		shared_governance_test(|| create_validator_rpc(game_index), game_index).await?;
+	bitflip_program_tests::save_compute_units(
+		"proposal_create",
+		compute_units.proposal_create,
+		"Create a proposal to change a game parameter",
+	)?;
+	bitflip_program_tests::save_compute_units(
+		"proposal_vote",
+		compute_units.proposal_vote,
+		"Vote on a proposal with kibibit tokens",
+	)?;
+
+	Ok(())
+}
+
+/// The compute units of the governance instructions.
+struct GovernanceComputeUnits {
+	proposal_create: u64,
+	proposal_vote: u64,
+}
+
+async fn shared_governance_test<
+	T: ToRpcClient,
+	Fut: Future<Output = anyhow::Result<T>>,
+	Create: FnOnce() -> Fut,
+>(
+	create_provider: Create,
+	game_index: u8,
+) -> anyhow::Result<GovernanceComputeUnits> {
+	let provider = create_provider().await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let proposal = get_pda_proposal(game_index, &player, 0).0;
+
+	let ix = proposal_create(&player, game_index, 0, GameParameter::Duration, 3600);
+	let proposal_create_compute_units = send_instruction(&rpc, &wallet_keypair, &[], ix).await?;
+
+	let proposal_data = rpc.get_account_data(&proposal).await?;
+	let proposal_state = ProposalState::try_from_bytes(&proposal_data)?;
+	check!(proposal_state.status() == ProposalStatus::Active);
+	check!(proposal_state.parameter()? == GameParameter::Duration);
+	check!(proposal_state.value() == 3600);
+
+	let ix = proposal_vote(&player, &proposal, TokenMember::Kibibit, 2, true);
+	let proposal_vote_compute_units = send_instruction(&rpc, &wallet_keypair, &[], ix).await?;
+
+	let proposal_data = rpc.get_account_data(&proposal).await?;
+	let proposal_state = ProposalState::try_from_bytes(&proposal_data)?;
+	check!(proposal_state.votes_for() == 2048);
+	check!(proposal_state.votes_against() == 0);
+
+	let vote_token_account = get_vote_token_account(&proposal, &player, TokenMember::Kibibit);
+	let vote_token_data = rpc.get_account_data(&vote_token_account).await?;
+	let vote_token = StateWithExtensions::<Account>::unpack(&vote_token_data)?;
+	check!(vote_token.base.amount == 2);
+
+	// the proposal can't be executed while voting is open
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = proposal_execute(game_index, &proposal);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::ProposalActive.into())
+			)
+	);
+
+	// the escrowed tokens are locked while voting is open
+	let ix = vote_withdraw(&player, &proposal, TokenMember::Kibibit);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::ProposalActive.into())
+			)
+	);
+
+	Ok(GovernanceComputeUnits {
+		proposal_create: proposal_create_compute_units,
+		proposal_vote: proposal_vote_compute_units,
+	})
+}
+
+fn create_accounts(game_index: u8) -> anyhow::Result<HashMap<Pubkey, AccountSharedData>> {
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64;
+	let created_game_state = create_game_state(game_index, 0, now - 3600, GameStatus::Running);
+	let mut accounts = create_config_accounts();
+	accounts.extend(create_token_accounts(false)?);
+
+	let (player_token_account, player_token_account_data) =
+		create_player_token_account(get_wallet_keypair().pubkey(), TokenMember::Kibibit, 10)?;
+	accounts.insert(player_token_account, player_token_account_data);
+
+	let game = get_pda_game(game_index).0;
+	accounts.insert(game, created_game_state.game_state_account);
+
+	Ok(accounts)
+}
+
+async fn create_banks_client_rpc(game_index: u8) -> anyhow::Result<impl ToRpcClient> {
+	let accounts = create_accounts(game_index)?;
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in accounts.clone() {
+			p.add_account(key, account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
+
+#[cfg(feature = "test_validator")]
+async fn create_validator_rpc(game_index: u8) -> anyhow::Result<impl ToRpcClient> {
+	let accounts = create_accounts(game_index)?;
+	let runner = bitflip_program_tests::create_runner_with_accounts(accounts).await;
+
+	Ok(runner)
+}
@@ -0,0 +1,98 @@
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::GameStatus;
+use bitflip_program::SectionEdge;
+use bitflip_program::BASE_LAMPORTS_PER_STEP;
+use bitflip_program_tests::consumer;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_section_state;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::native_token::sol_to_lamports;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+use test_utils_solana::processor;
+
+#[test_log::test(tokio::test)]
+async fn interface_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+	let vault = consumer::get_pda_vault().0;
+	let section = get_pda_section(game_index, section_index).0;
+	let vault_lamports = rpc.get_balance(&vault).await?;
+	let section_lamports = rpc.get_balance(&section).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = consumer::vault_section_step(game_index, section_index, SectionEdge::Dead);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	// the vault signed the step through the cpi and paid for it
+	check!(rpc.get_balance(&vault).await? == vault_lamports - BASE_LAMPORTS_PER_STEP);
+	check!(rpc.get_balance(&section).await? == section_lamports + BASE_LAMPORTS_PER_STEP);
+
+	Ok(())
+}
+
+async fn create_banks_client_rpc(
+	game_index: u8,
+	section_index: u8,
+) -> anyhow::Result<impl ToRpcClient> {
+	let provider = create_program_context_with_factory(|p| {
+		p.add_program(
+			"bitflip_consumer",
+			consumer::ID,
+			processor!(consumer::process_instruction),
+		);
+
+		for (key, account) in create_config_accounts() {
+			p.add_account(key, account.into());
+		}
+
+		let now = SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.unwrap()
+			.as_secs() as i64;
+		let game = get_pda_game(game_index).0;
+		let create_game_state = create_game_state(game_index, 0, now - 3600, GameStatus::Running);
+		p.add_account(game, create_game_state.game_state_account.into());
+
+		let section_accounts = create_section_state(
+			Pubkey::new_unique(),
+			game_index,
+			section_index.saturating_add(1),
+			false,
+		)?;
+
+		for (section, section_account) in section_accounts {
+			p.add_account(section, section_account.into());
+		}
+
+		p.add_account(
+			consumer::get_pda_vault().0,
+			Account {
+				lamports: sol_to_lamports(1.0),
+				..Account::default()
+			},
+		);
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
@@ -0,0 +1,218 @@
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::fetch_game;
+use bitflip_program::fetch_section;
+use bitflip_program::flip_bit;
+use bitflip_program::flip_bit_with_options;
+use bitflip_program::game_update_cooldown;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::quote_flip;
+use bitflip_program::quote_flip_bits;
+use bitflip_program::with_first_flip;
+use bitflip_program::FlipBit;
+use bitflip_program::FlipBitOptions;
+use bitflip_program::GameStatus;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_section_state;
+use bitflip_program_tests::create_token_accounts;
+use bitflip_program_tests::initialize_player_token_account;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::from_account;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_authority_keypair;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+/// The quote must match the lamports collected by the section when the same
+/// flips are executed by the program.
+#[test_log::test(tokio::test)]
+async fn quote_flip_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+
+	let clock_account = rpc.get_account(&sysvar::clock::ID).await?;
+	let clock = from_account::<Clock, _>(&clock_account).unwrap();
+	let game_state = fetch_game(&rpc, game_index).await?;
+	let section_state = fetch_section(&rpc, game_index, section_index).await?;
+	let section_lamports = rpc.get_balance(&section).await?;
+
+	// the third flip doesn't change the bit and is charged twice.
+	let offsets = [0, 1, 0];
+	let flip_bits = offsets.map(|offset| {
+		FlipBit::builder()
+			.section_index(section_index)
+			.array_index(0)
+			.offset(offset)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build()
+	});
+	let quote = quote_flip_bits(
+		&game_state,
+		&section_state,
+		None,
+		clock.slot,
+		clock.unix_timestamp,
+		&flip_bits,
+	)?;
+	let changed_quote = quote_flip(&game_state, &section_state, clock.unix_timestamp, 2)?;
+	check!(quote.flips == vec![1, 1, 2]);
+	check!(quote.prices[..2] == changed_quote.prices[..]);
+
+	let instructions = offsets.map(|offset| {
+		with_first_flip(flip_bit(
+			&player,
+			game_index,
+			section_index,
+			0,
+			offset,
+			1,
+			quote.max_price(),
+		))
+	});
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &instructions, &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let updated_section_state = fetch_section(&rpc, game_index, section_index).await?;
+	check!(rpc.get_balance(&section).await? - section_lamports == quote.lamports);
+	check!(u64::from(updated_section_state.flips()) == quote.total_flips());
+
+	Ok(())
+}
+
+/// Flips of a word which is cooling down are quoted with the cooldown
+/// multiplier and match the lamports collected by the program.
+#[test_log::test(tokio::test)]
+async fn quote_flip_cooldown_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let cooldown_multiplier = 3;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let authority_keypair = get_authority_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = game_update_cooldown(
+		&authority_keypair.pubkey(),
+		game_index,
+		1_000,
+		cooldown_multiplier,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair, &authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let clock_account = rpc.get_account(&sysvar::clock::ID).await?;
+	let clock = from_account::<Clock, _>(&clock_account).unwrap();
+	let game_state = fetch_game(&rpc, game_index).await?;
+	let section_state = fetch_section(&rpc, game_index, section_index).await?;
+	let section_lamports = rpc.get_balance(&section).await?;
+
+	// every flip is within the first word so only the first flip isn't cooling
+	// down.
+	let offsets = [0, 1, 0];
+	let flip_bits = offsets.map(|offset| {
+		FlipBit::builder()
+			.section_index(section_index)
+			.array_index(0)
+			.offset(offset)
+			.value(1)
+			.max_lamports_per_bit(u64::MAX)
+			.build()
+	});
+	let quote = quote_flip_bits(
+		&game_state,
+		&section_state,
+		None,
+		clock.slot,
+		clock.unix_timestamp,
+		&flip_bits,
+	)?;
+	let uncooled_quote = quote_flip(&game_state, &section_state, clock.unix_timestamp, 2)?;
+	let multiplier = u64::from(cooldown_multiplier);
+	check!(quote.flips == vec![1, 1, 2]);
+	check!(quote.prices[0] == uncooled_quote.prices[0]);
+	check!(quote.prices[1] == uncooled_quote.prices[1] * multiplier);
+
+	let instructions = offsets.map(|offset| {
+		with_first_flip(flip_bit_with_options(
+			&player,
+			game_index,
+			section_index,
+			0,
+			offset,
+			1,
+			quote.max_price(),
+			&FlipBitOptions::from_game(&game_state),
+		))
+	});
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &instructions, &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	check!(rpc.get_balance(&section).await? - section_lamports == quote.lamports);
+
+	Ok(())
+}
+
+async fn create_banks_client_rpc(
+	game_index: u8,
+	section_index: u8,
+) -> anyhow::Result<impl ToRpcClient> {
+	let provider = create_program_context_with_factory(|p| {
+		let mut accounts = create_config_accounts();
+		accounts.extend(create_token_accounts(false)?);
+
+		for (key, account) in accounts {
+			p.add_account(key, account.into());
+		}
+
+		let now = SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.unwrap()
+			.as_secs() as i64;
+		let game = get_pda_game(game_index).0;
+		let create_game_state = create_game_state(game_index, 0, now - 3600, GameStatus::Running);
+		p.add_account(game, create_game_state.game_state_account.into());
+
+		let section_accounts = create_section_state(
+			Pubkey::new_unique(),
+			game_index,
+			section_index.saturating_add(1),
+			false,
+		)?;
+
+		for (section, section_account) in section_accounts {
+			p.add_account(section, section_account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
@@ -0,0 +1,138 @@
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::section_seed;
+use bitflip_program::section_seed_batch;
+use bitflip_program::BitflipError;
+use bitflip_program::GameStatus;
+use bitflip_program::SectionState;
+use bitflip_program::BITFLIP_SECTION_LENGTH;
+use bitflip_program::BITFLIP_SECTION_TOTAL_BITS;
+use bitflip_program::SECTION_SEED_WORDS;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_authority_keypair;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn section_seed_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 3;
+	let provider = create_banks_client_rpc(game_index, GameStatus::Pending).await?;
+	let rpc = provider.to_rpc();
+	let authority_keypair = get_authority_keypair();
+	let authority = authority_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+
+	let mut data = [0u16; BITFLIP_SECTION_LENGTH];
+	data[0] = 0b1111;
+	data[BITFLIP_SECTION_LENGTH - 1] = u16::MAX;
+	let instructions = section_seed_batch(&authority, game_index, section_index, &data);
+	check!(instructions.len() == BITFLIP_SECTION_LENGTH / SECTION_SEED_WORDS);
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&authority, &instructions, &[], recent_blockhash)?;
+	transaction.try_sign(&[&authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.owner == Pubkey::default());
+	check!(section_state.section_index == section_index);
+	check!(u16::from(section_state.data[0]) == 0b1111);
+	check!(u16::from(section_state.data[BITFLIP_SECTION_LENGTH - 1]) == u16::MAX);
+	check!(section_state.on() == 20);
+	check!(section_state.off() == BITFLIP_SECTION_TOTAL_BITS - 20);
+	check!(section_state.flips() == 0);
+
+	// seeding the section again replaces the earlier data
+	let mut data = [0u16; BITFLIP_SECTION_LENGTH];
+	data[1] = 0b11;
+	let instructions = section_seed_batch(&authority, game_index, section_index, &data);
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&authority, &instructions, &[], recent_blockhash)?;
+	transaction.try_sign(&[&authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(u16::from(section_state.data[0]) == 0);
+	check!(u16::from(section_state.data[1]) == 0b11);
+	check!(u16::from(section_state.data[BITFLIP_SECTION_LENGTH - 1]) == 0);
+	check!(section_state.on() == 2);
+
+	// only the config authority can seed the canvas
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+	let ix = section_seed(&wallet, game_index, section_index, 0, &[1]);
+	let transaction = VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::Unauthorized.into())
+			)
+	);
+
+	Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn section_seed_running_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let provider = create_banks_client_rpc(game_index, GameStatus::Running).await?;
+	let rpc = provider.to_rpc();
+	let authority_keypair = get_authority_keypair();
+	let authority = authority_keypair.pubkey();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_seed(&authority, game_index, 0, 0, &[1]);
+	let transaction =
+		VersionedTransaction::new_unsigned_v0(&authority, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::GameAlreadyStarted.into())
+			)
+	);
+
+	Ok(())
+}
+
+async fn create_banks_client_rpc(
+	game_index: u8,
+	status: GameStatus,
+) -> anyhow::Result<impl ToRpcClient> {
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in create_config_accounts() {
+			p.add_account(key, account.into());
+		}
+
+		let game = get_pda_game(game_index).0;
+		let start_time = if status == GameStatus::Running { 1 } else { 0 };
+		let create_game_state = create_game_state(game_index, 0, start_time, status);
+		p.add_account(game, create_game_state.game_state_account.into());
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
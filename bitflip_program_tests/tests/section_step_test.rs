@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::get_pda_section_locks;
+use bitflip_program::section_step;
+use bitflip_program::BitflipError;
+use bitflip_program::GameStatus;
+use bitflip_program::SectionEdge;
+use bitflip_program::SectionLock;
+use bitflip_program::SectionLocksState;
+use bitflip_program::SectionState;
+use bitflip_program::BASE_LAMPORTS_PER_STEP;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_section_state;
+use bitflip_program_tests::IntoAccountSharedData;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+const GAME_INDEX: u8 = 0;
+const SECTION_INDEX: u8 = 0;
+
+/// The locks of the player don't stop them from stepping the section.
+#[test_log::test(tokio::test)]
+async fn section_step_test() -> anyhow::Result<()> {
+	let holder = get_wallet_keypair().pubkey();
+	shared_section_step_test(|| create_banks_client_rpc(Some(holder))).await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "test_validator")]
+#[test_log::test(tokio::test)]
+async fn section_step_test_validator() -> anyhow::Result<()> {
+	let holder = get_wallet_keypair().pubkey();
+	let compute_units = shared_section_step_test(|| create_validator_rpc(Some(holder))).await?;
+	bitflip_program_tests::save_compute_units(
+		"section_step",
+		compute_units,
+		"Step a section by one generation",
+	)?;
+
+	Ok(())
+}
+
+/// Stepping would rewrite the locked region of another player.
+#[test_log::test(tokio::test)]
+async fn section_step_locked_test() -> anyhow::Result<()> {
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let provider = create_banks_client_rpc(Some(Pubkey::new_unique())).await?;
+	let rpc = provider.to_rpc();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_step(&player, GAME_INDEX, SECTION_INDEX, SectionEdge::Dead);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::BitLocked.into())
+			)
+	);
+
+	Ok(())
+}
+
+/// Create a running game where the first section holds a blinker and,
+/// optionally, an active lock of the `holder`.
+fn create_accounts(holder: Option<Pubkey>) -> anyhow::Result<HashMap<Pubkey, AccountSharedData>> {
+	let mut accounts = create_config_accounts();
+	accounts.extend(create_section_state(
+		Pubkey::new_unique(),
+		GAME_INDEX,
+		SECTION_INDEX + 1,
+		false,
+	)?);
+
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64;
+	let game = get_pda_game(GAME_INDEX).0;
+	let create_game_state =
+		create_game_state(GAME_INDEX, SECTION_INDEX, now - 3600, GameStatus::Running);
+	accounts.insert(game, create_game_state.game_state_account);
+
+	let (section, section_bump) = get_pda_section(GAME_INDEX, SECTION_INDEX);
+	let mut section_state = SectionState::new(
+		Pubkey::new_unique(),
+		GAME_INDEX,
+		SECTION_INDEX,
+		section_bump,
+	);
+	// a horizontal blinker at `(1..4, 4)`.
+	section_state.data[4] = 0b1110.into();
+	accounts.insert(section, section_state.to_account_shared_data());
+
+	if let Some(holder) = holder {
+		let (section_locks, section_locks_bump) = get_pda_section_locks(GAME_INDEX, SECTION_INDEX);
+		let mut section_locks_state =
+			SectionLocksState::new(GAME_INDEX, SECTION_INDEX, section_locks_bump);
+		let lock = SectionLock {
+			holder,
+			expiry: (now + 3600).into(),
+			x: 0,
+			y: 0,
+			width: 1,
+			height: 1,
+		};
+		section_locks_state.lock(lock, now)?;
+		accounts.insert(section_locks, section_locks_state.to_account_shared_data());
+	}
+
+	Ok(accounts)
+}
+
+async fn create_banks_client_rpc(holder: Option<Pubkey>) -> anyhow::Result<impl ToRpcClient> {
+	let accounts = create_accounts(holder)?;
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in accounts.clone() {
+			p.add_account(key, account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}
+
+#[cfg(feature = "test_validator")]
+async fn create_validator_rpc(holder: Option<Pubkey>) -> anyhow::Result<impl ToRpcClient> {
+	let accounts = create_accounts(holder)?;
+	let runner = bitflip_program_tests::create_runner_with_accounts(accounts).await;
+
+	Ok(runner)
+}
+
+async fn shared_section_step_test<
+	T: ToRpcClient,
+	Fut: Future<Output = anyhow::Result<T>>,
+	Create: FnOnce() -> Fut,
+>(
+	create_provider: Create,
+) -> anyhow::Result<u64> {
+	let provider = create_provider().await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let section = get_pda_section(GAME_INDEX, SECTION_INDEX).0;
+	let section_lamports = rpc.get_balance(&section).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_step(&player, GAME_INDEX, SECTION_INDEX, SectionEdge::Dead);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+	let compute_units = simulation.value.units_consumed.unwrap();
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.steps() == 4);
+	check!(rpc.get_balance(&section).await? == section_lamports + BASE_LAMPORTS_PER_STEP);
+
+	Ok(compute_units)
+}
@@ -0,0 +1,195 @@
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::get_pda_treasury;
+use bitflip_program::section_unlock_inherited;
+use bitflip_program::BitflipError;
+use bitflip_program::GameFlags;
+use bitflip_program::GameState;
+use bitflip_program::GameStatus;
+use bitflip_program::SectionState;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::IntoAccountSharedData;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Keypair;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use spl_pod::primitives::PodU16;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+const GAME_INDEX: u8 = 1;
+const SECTION_INDEX: u8 = 0;
+const LAMPORTS: u64 = 1_000_000;
+
+/// The previous owner re-claims their section during the reserved window at a
+/// discount and the section starts with the final data of the previous game.
+#[test_log::test(tokio::test)]
+async fn section_unlock_inherited_test() -> anyhow::Result<()> {
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+	let (provider, temp_signer_keypair) = create_banks_client_rpc(wallet, true).await?;
+	let rpc = provider.to_rpc();
+	let treasury = get_pda_treasury().0;
+	let section = get_pda_section(GAME_INDEX, SECTION_INDEX).0;
+	let treasury_lamports = rpc.get_balance(&treasury).await?;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_unlock_inherited(
+		&wallet,
+		&temp_signer_keypair.pubkey(),
+		GAME_INDEX,
+		SECTION_INDEX,
+		LAMPORTS,
+		None,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	transaction
+		.try_sign(&[&wallet_keypair], None)?
+		.try_sign(&[&temp_signer_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.owner == wallet);
+	check!(section_state.game_index == GAME_INDEX);
+	check!(u16::from(section_state.data[0]) == 0b1010);
+
+	let paid_lamports = rpc.get_balance(&treasury).await? - treasury_lamports;
+	check!(paid_lamports == GameState::inherit_lamports(LAMPORTS));
+	check!(paid_lamports < LAMPORTS);
+
+	Ok(())
+}
+
+/// Only the previous owner can unlock the section while it is reserved.
+#[test_log::test(tokio::test)]
+async fn section_unlock_inherited_reserved_test() -> anyhow::Result<()> {
+	let (provider, temp_signer_keypair) =
+		create_banks_client_rpc(Pubkey::new_unique(), true).await?;
+
+	let error = simulate_unlock(&provider, &temp_signer_keypair).await?;
+	check!(
+		error
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::SectionReserved.into())
+			)
+	);
+
+	Ok(())
+}
+
+/// The canvas of the previous game can't be inherited until it is final.
+#[test_log::test(tokio::test)]
+async fn section_unlock_inherited_not_finalized_test() -> anyhow::Result<()> {
+	let wallet = get_wallet_keypair().pubkey();
+	let (provider, temp_signer_keypair) = create_banks_client_rpc(wallet, false).await?;
+
+	let error = simulate_unlock(&provider, &temp_signer_keypair).await?;
+	check!(
+		error
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::GameNotFinalized.into())
+			)
+	);
+
+	Ok(())
+}
+
+async fn simulate_unlock(
+	provider: &impl ToRpcClient,
+	temp_signer_keypair: &Keypair,
+) -> anyhow::Result<TransactionError> {
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_unlock_inherited(
+		&wallet,
+		&temp_signer_keypair.pubkey(),
+		GAME_INDEX,
+		SECTION_INDEX,
+		LAMPORTS,
+		None,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	transaction
+		.try_sign(&[&wallet_keypair], None)?
+		.try_sign(&[temp_signer_keypair], None)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	Ok(simulation.value.err.unwrap())
+}
+
+/// Create a running inheriting game which is still within the reserved window
+/// and the previous game where the first section is owned by the
+/// `previous_owner`.
+async fn create_banks_client_rpc(
+	previous_owner: Pubkey,
+	finalized: bool,
+) -> anyhow::Result<(impl ToRpcClient, Keypair)> {
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64;
+	let temp_signer_keypair = Keypair::new();
+	let temp_signer = temp_signer_keypair.pubkey();
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in create_config_accounts() {
+			p.add_account(key, account.into());
+		}
+
+		let previous_game_index = GAME_INDEX - 1;
+		let (previous_game, previous_game_bump) = get_pda_game(previous_game_index);
+		let previous_game_state = GameState::builder()
+			.start_time(now - 7200)
+			.duration(3600)
+			.game_index(previous_game_index)
+			.bump(previous_game_bump)
+			.section_index(1)
+			.status(GameStatus::Ended)
+			.canvas_root(if finalized { [1; 32] } else { [0; 32] })
+			.build();
+		p.add_account(previous_game, previous_game_state.to_account());
+
+		let (previous_section, previous_section_bump) =
+			get_pda_section(previous_game_index, SECTION_INDEX);
+		let mut previous_section_state = SectionState::new(
+			previous_owner,
+			previous_game_index,
+			SECTION_INDEX,
+			previous_section_bump,
+		);
+		previous_section_state.data[0] = PodU16::from(0b1010);
+		p.add_account(previous_section, previous_section_state.to_account());
+
+		let (game, game_bump) = get_pda_game(GAME_INDEX);
+		let game_state = GameState::builder()
+			.temp_signer(temp_signer)
+			.start_time(now - 60)
+			.game_index(GAME_INDEX)
+			.bump(game_bump)
+			.section_index(SECTION_INDEX)
+			.status(GameStatus::Running)
+			.flags(GameFlags::INHERIT.bits())
+			.build();
+		p.add_account(game, game_state.to_account());
+
+		Ok(())
+	})
+	.await?;
+
+	Ok((provider, temp_signer_keypair))
+}
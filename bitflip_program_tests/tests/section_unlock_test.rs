@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_section;
+use bitflip_program::get_pda_stake_pool;
+use bitflip_program::get_pda_treasury;
+use bitflip_program::section_unlock;
+use bitflip_program::BitflipError;
+use bitflip_program::GameStatus;
+use bitflip_program::SectionState;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::IntoAccountSharedData;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Keypair;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use spl_pod::primitives::PodU16;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+/// A seeded section has no owner and is claimed by the first unlock.
+#[test_log::test(tokio::test)]
+async fn section_unlock_seeded_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 1;
+	shared_section_unlock_seeded_test(
+		|| create_banks_client_rpc(game_index, section_index, Pubkey::default()),
+		game_index,
+		section_index,
+	)
+	.await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "test_validator")]
+#[test_log::test(tokio::test)]
+async fn section_unlock_seeded_test_validator() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 1;
+	let compute_units = shared_section_unlock_seeded_test(
+		|| create_validator_rpc(game_index, section_index, Pubkey::default()),
+		game_index,
+		section_index,
+	)
+	.await?;
+	bitflip_program_tests::save_compute_units(
+		"section_unlock",
+		compute_units,
+		"Unlock a seeded section",
+	)?;
+
+	Ok(())
+}
+
+/// The section index stops incrementing at the last section so the last section
+/// already exists once it has been unlocked. Unlocking it again must not
+/// replace its owner.
+#[test_log::test(tokio::test)]
+async fn section_unlock_owned_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = u8::MAX;
+	let owner = Pubkey::new_unique();
+	let (provider, temp_signer_keypair) =
+		create_banks_client_rpc(game_index, section_index, owner).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_unlock(
+		&wallet,
+		&temp_signer_keypair.pubkey(),
+		game_index,
+		section_index,
+		1_000_000,
+		None,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	transaction
+		.try_sign(&[&wallet_keypair], None)?
+		.try_sign(&[&temp_signer_keypair], None)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::SectionAlreadyUnlocked.into())
+			)
+	);
+
+	Ok(())
+}
+
+/// The stake pool is an optional trailing account so clients built before
+/// staking can still unlock sections. The whole bid is paid to the treasury.
+#[test_log::test(tokio::test)]
+async fn section_unlock_without_stake_pool_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 1;
+	let (provider, temp_signer_keypair) =
+		create_banks_client_rpc(game_index, section_index, Pubkey::default()).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+	let treasury = get_pda_treasury().0;
+	let treasury_lamports = rpc.get_balance(&treasury).await?;
+
+	let mut ix = section_unlock(
+		&wallet,
+		&temp_signer_keypair.pubkey(),
+		game_index,
+		section_index,
+		1_000_000,
+		None,
+	);
+	let stake_pool = ix.accounts.pop().map(|meta| meta.pubkey);
+	check!(stake_pool == Some(get_pda_stake_pool().0));
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	transaction
+		.try_sign(&[&wallet_keypair], None)?
+		.try_sign(&[&temp_signer_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	check!(rpc.get_balance(&treasury).await? == treasury_lamports + 1_000_000);
+
+	Ok(())
+}
+
+/// Create a running game whose next section already exists with the `owner`.
+/// The section is seeded when the `owner` is the default pubkey.
+fn create_accounts(
+	game_index: u8,
+	section_index: u8,
+	owner: Pubkey,
+) -> (HashMap<Pubkey, AccountSharedData>, Keypair) {
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64;
+	let created_game_state =
+		create_game_state(game_index, section_index, now - 3600, GameStatus::Running);
+	let mut accounts = create_config_accounts();
+
+	let game = get_pda_game(game_index).0;
+	accounts.insert(game, created_game_state.game_state_account);
+
+	let (section, section_bump) = get_pda_section(game_index, section_index);
+	let mut section_state = SectionState::new(owner, game_index, section_index, section_bump);
+	section_state.data[0] = PodU16::from(0b1111);
+	accounts.insert(section, section_state.to_account_shared_data());
+
+	(accounts, created_game_state.temp_signer)
+}
+
+async fn create_banks_client_rpc(
+	game_index: u8,
+	section_index: u8,
+	owner: Pubkey,
+) -> anyhow::Result<(impl ToRpcClient, Keypair)> {
+	let (accounts, temp_signer_keypair) = create_accounts(game_index, section_index, owner);
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in accounts.clone() {
+			p.add_account(key, account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok((provider, temp_signer_keypair))
+}
+
+#[cfg(feature = "test_validator")]
+async fn create_validator_rpc(
+	game_index: u8,
+	section_index: u8,
+	owner: Pubkey,
+) -> anyhow::Result<(impl ToRpcClient, Keypair)> {
+	let (accounts, temp_signer_keypair) = create_accounts(game_index, section_index, owner);
+	let runner = bitflip_program_tests::create_runner_with_accounts(accounts).await;
+
+	Ok((runner, temp_signer_keypair))
+}
+
+async fn shared_section_unlock_seeded_test<
+	T: ToRpcClient,
+	Fut: Future<Output = anyhow::Result<(T, Keypair)>>,
+	Create: FnOnce() -> Fut,
+>(
+	create_provider: Create,
+	game_index: u8,
+	section_index: u8,
+) -> anyhow::Result<u64> {
+	let (provider, temp_signer_keypair) = create_provider().await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let wallet = wallet_keypair.pubkey();
+	let section = get_pda_section(game_index, section_index).0;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = section_unlock(
+		&wallet,
+		&temp_signer_keypair.pubkey(),
+		game_index,
+		section_index,
+		1_000_000,
+		None,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&wallet, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+	let compute_units = simulation.value.units_consumed.unwrap();
+	transaction
+		.try_sign(&[&wallet_keypair], None)?
+		.try_sign(&[&temp_signer_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let section_data = rpc.get_account_data(&section).await?;
+	let section_state = SectionState::try_from_bytes(&section_data)?;
+	check!(section_state.owner == wallet);
+	check!(u16::from(section_state.data[0]) == 0b1111);
+
+	Ok(compute_units)
+}
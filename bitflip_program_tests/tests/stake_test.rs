@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_stake;
+use bitflip_program::get_pda_stake_pool;
+use bitflip_program::get_pda_treasury;
+use bitflip_program::get_stake_pool_token_account;
+use bitflip_program::section_unlock;
+use bitflip_program::stake_claim;
+use bitflip_program::stake_pool_update;
+use bitflip_program::stake_tokens;
+use bitflip_program::unstake_tokens;
+use bitflip_program::BitflipError;
+use bitflip_program::GameStatus;
+use bitflip_program::StakePoolState;
+use bitflip_program::StakeState;
+use bitflip_program::TokenMember;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_game_state;
+use bitflip_program_tests::create_player_token_account;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_token_accounts;
+use bitflip_program_tests::send_instruction;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::Keypair;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Account;
+use steel::*;
+use test_utils_keypairs::get_authority_keypair;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn stake_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	shared_stake_test(|| create_banks_client_rpc(game_index), game_index).await?;
+
+	Ok(())
+}
+
+#[cfg(feature = "test_validator")]
+#[test_log::test(tokio::test)]
+async fn stake_test_validator() -> anyhow::Result<()> {
+	let game_index = 0;
+	let compute_units = shared_stake_test(|| create_validator_rpc(game_index), game_index).await?;
+	bitflip_program_tests::save_compute_units(
+		"stake_tokens",
+		compute_units.stake_tokens,
+		"Stake kibibit tokens",
+	)?;
+	bitflip_program_tests::save_compute_units(
+		"stake_claim",
+		compute_units.stake_claim,
+		"Claim the staking rewards",
+	)?;
+	bitflip_program_tests::save_compute_units(
+		"unstake_tokens",
+		compute_units.unstake_tokens,
+		"Unstake kibibit tokens",
+	)?;
+
+	Ok(())
+}
+
+/// The compute units of the staking instructions.
+struct StakeComputeUnits {
+	stake_tokens: u64,
+	stake_claim: u64,
+	unstake_tokens: u64,
+}
+
+fn create_accounts(
+	game_index: u8,
+) -> anyhow::Result<(HashMap<Pubkey, AccountSharedData>, Keypair)> {
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.unwrap()
+		.as_secs() as i64;
+	let created_game_state = create_game_state(game_index, 0, now - 3600, GameStatus::Running);
+	let mut accounts = create_config_accounts();
+	accounts.extend(create_token_accounts(false)?);
+
+	let (player_token_account, player_token_account_data) =
+		create_player_token_account(get_wallet_keypair().pubkey(), TokenMember::Kibibit, 10)?;
+	accounts.insert(player_token_account, player_token_account_data);
+
+	let game = get_pda_game(game_index).0;
+	accounts.insert(game, created_game_state.game_state_account);
+
+	Ok((accounts, created_game_state.temp_signer))
+}
+
+async fn create_banks_client_rpc(game_index: u8) -> anyhow::Result<(impl ToRpcClient, Keypair)> {
+	let (accounts, temp_signer_keypair) = create_accounts(game_index)?;
+	let provider = create_program_context_with_factory(|p| {
+		for (key, account) in accounts.clone() {
+			p.add_account(key, account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok((provider, temp_signer_keypair))
+}
+
+#[cfg(feature = "test_validator")]
+async fn create_validator_rpc(game_index: u8) -> anyhow::Result<(impl ToRpcClient, Keypair)> {
+	let (accounts, temp_signer_keypair) = create_accounts(game_index)?;
+	let runner = bitflip_program_tests::create_runner_with_accounts(accounts).await;
+
+	Ok((runner, temp_signer_keypair))
+}
+
+async fn shared_stake_test<
+	T: ToRpcClient,
+	Fut: Future<Output = anyhow::Result<(T, Keypair)>>,
+	Create: FnOnce() -> Fut,
+>(
+	create_provider: Create,
+	game_index: u8,
+) -> anyhow::Result<StakeComputeUnits> {
+	let (provider, temp_signer_keypair) = create_provider().await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let authority_keypair = get_authority_keypair();
+	let player = wallet_keypair.pubkey();
+	let stake_pool = get_pda_stake_pool().0;
+	let stake = get_pda_stake(&player).0;
+	let stake_pool_token_account = get_stake_pool_token_account(TokenMember::Kibibit);
+	let treasury = get_pda_treasury().0;
+
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = stake_pool_update(&authority_keypair.pubkey(), 5_000, 0);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair, &authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let ix = stake_tokens(&player, TokenMember::Kibibit, 2);
+	let stake_tokens_compute_units = send_instruction(&rpc, &wallet_keypair, &[], ix).await?;
+
+	let stake_data = rpc.get_account_data(&stake).await?;
+	let stake_state = StakeState::try_from_bytes(&stake_data)?;
+	check!(stake_state.amount(TokenMember::Kibibit) == 2);
+	check!(stake_state.weight() == 2048);
+
+	let stake_pool_token_data = rpc.get_account_data(&stake_pool_token_account).await?;
+	let stake_pool_token = StateWithExtensions::<Account>::unpack(&stake_pool_token_data)?;
+	check!(stake_pool_token.base.amount == 2);
+
+	// half of the unlock bid is shared with the stakers
+	let treasury_lamports = rpc.get_balance(&treasury).await?;
+	let stake_pool_lamports = rpc.get_balance(&stake_pool).await?;
+	let ix = section_unlock(
+		&player,
+		&temp_signer_keypair.pubkey(),
+		game_index,
+		0,
+		1_000_000,
+		None,
+	);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair, &temp_signer_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	check!(rpc.get_balance(&treasury).await? == treasury_lamports + 500_000);
+	check!(rpc.get_balance(&stake_pool).await? == stake_pool_lamports + 500_000);
+
+	let stake_pool_data = rpc.get_account_data(&stake_pool).await?;
+	let stake_pool_state = StakePoolState::try_from_bytes(&stake_pool_data)?;
+	check!(stake_state.pending_rewards(stake_pool_state) == 500_000);
+
+	let ix = stake_claim(&player);
+	let stake_claim_compute_units = send_instruction(&rpc, &wallet_keypair, &[], ix).await?;
+
+	check!(rpc.get_balance(&stake_pool).await? == stake_pool_lamports);
+
+	let ix = unstake_tokens(&player, TokenMember::Kibibit, 2);
+	let unstake_tokens_compute_units = send_instruction(&rpc, &wallet_keypair, &[], ix).await?;
+
+	let stake_pool_token_data = rpc.get_account_data(&stake_pool_token_account).await?;
+	let stake_pool_token = StateWithExtensions::<Account>::unpack(&stake_pool_token_data)?;
+	check!(stake_pool_token.base.amount == 0);
+
+	// a new stake is locked by the cooldown
+	let ix = stake_pool_update(&authority_keypair.pubkey(), 5_000, 3_600);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair, &authority_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let ix = stake_tokens(&player, TokenMember::Kibibit, 1);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let ix = unstake_tokens(&player, TokenMember::Kibibit, 1);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::StakeLocked.into())
+			)
+	);
+
+	Ok(StakeComputeUnits {
+		stake_tokens: stake_tokens_compute_units,
+		stake_claim: stake_claim_compute_units,
+		unstake_tokens: unstake_tokens_compute_units,
+	})
+}
@@ -0,0 +1,175 @@
+use std::time::SystemTime;
+
+use assert2::check;
+use bitflip_program::flip_bit;
+use bitflip_program::flip_bit_with_options;
+use bitflip_program::get_pda_game;
+use bitflip_program::get_pda_player_team;
+use bitflip_program::get_pda_team_pool;
+use bitflip_program::team_claim;
+use bitflip_program::team_join;
+use bitflip_program::with_first_flip;
+use bitflip_program::BitflipError;
+use bitflip_program::FlipBitOptions;
+use bitflip_program::GameFlags;
+use bitflip_program::GameState;
+use bitflip_program::GameStatus;
+use bitflip_program::PlayerTeamState;
+use bitflip_program::Team;
+use bitflip_program::TeamPoolState;
+use bitflip_program_tests::create_config_accounts;
+use bitflip_program_tests::create_program_context_with_factory;
+use bitflip_program_tests::create_section_state;
+use bitflip_program_tests::create_token_accounts;
+use bitflip_program_tests::initialize_player_token_account;
+use bitflip_program_tests::IntoAccountSharedData;
+use bitflip_program_tests::ToRpcClient;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transaction::VersionedTransaction;
+use steel::*;
+use test_utils_keypairs::get_wallet_keypair;
+use test_utils_solana::prelude::*;
+
+#[test_log::test(tokio::test)]
+async fn team_test() -> anyhow::Result<()> {
+	let game_index = 0;
+	let section_index = 0;
+	let provider = create_banks_client_rpc(game_index, section_index).await?;
+	let rpc = provider.to_rpc();
+	let wallet_keypair = get_wallet_keypair();
+	let player = wallet_keypair.pubkey();
+	let player_team = get_pda_player_team(game_index, &player).0;
+	let team_pool = get_pda_team_pool(game_index).0;
+
+	// players must join a team before flipping
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let ix = with_first_flip(flip_bit_with_options(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		&FlipBitOptions::new(GameFlags::TEAMS, false),
+	));
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+	log::info!("simulation: {simulation:#?}");
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::TeamNotJoined.into())
+			)
+	);
+
+	initialize_player_token_account(&rpc, &wallet_keypair).await?;
+	let ix = team_join(&player, game_index, Team::On);
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let team_pool_lamports = rpc.get_balance(&team_pool).await?;
+	let ix = with_first_flip(flip_bit_with_options(
+		&player,
+		game_index,
+		section_index,
+		0,
+		0,
+		1,
+		u64::MAX,
+		&FlipBitOptions::new(GameFlags::TEAMS, false),
+	));
+	let mut transaction =
+		VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	transaction.try_sign(&[&wallet_keypair], None)?;
+	let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let player_team_data = rpc.get_account_data(&player_team).await?;
+	let player_team_state = PlayerTeamState::try_from_bytes(&player_team_data)?;
+	check!(player_team_state.team()? == Team::On);
+	check!(player_team_state.flips() == 1);
+
+	let team_pool_data = rpc.get_account_data(&team_pool).await?;
+	let team_pool_state = TeamPoolState::try_from_bytes(&team_pool_data)?;
+	check!(team_pool_state.flips(Team::On) == 1);
+	check!(team_pool_state.flips(Team::Off) == 0);
+	check!(team_pool_state.rewards() > 0);
+	check!(rpc.get_balance(&team_pool).await? == team_pool_lamports + team_pool_state.rewards());
+
+	// the team accounts are required in a team game
+	let ix = flip_bit(&player, game_index, section_index, 0, 2, 1, u64::MAX);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+	);
+
+	// the reward can only be claimed once the game is finalized
+	let ix = team_claim(&player, game_index);
+	let transaction = VersionedTransaction::new_unsigned_v0(&player, &[ix], &[], recent_blockhash)?;
+	let simulation = rpc.simulate_transaction(&transaction).await?;
+
+	check!(
+		simulation.value.err.unwrap()
+			== TransactionError::InstructionError(
+				0,
+				InstructionError::Custom(BitflipError::GameNotFinalized.into())
+			)
+	);
+
+	Ok(())
+}
+
+async fn create_banks_client_rpc(
+	game_index: u8,
+	section_index: u8,
+) -> anyhow::Result<impl ToRpcClient> {
+	let provider = create_program_context_with_factory(|p| {
+		let mut accounts = create_config_accounts();
+		accounts.extend(create_token_accounts(false)?);
+
+		for (key, account) in accounts {
+			p.add_account(key, account.into());
+		}
+
+		let now = SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.unwrap()
+			.as_secs() as i64;
+		let (game, game_bump) = get_pda_game(game_index);
+		let game_state = GameState::builder()
+			.start_time(now - 3600)
+			.game_index(game_index)
+			.bump(game_bump)
+			.section_index(section_index)
+			.status(GameStatus::Running)
+			.flags(GameFlags::TEAMS.bits())
+			.build();
+		p.add_account(game, game_state.to_account_shared_data().into());
+
+		let section_accounts = create_section_state(
+			Pubkey::new_unique(),
+			game_index,
+			section_index.saturating_add(1),
+			false,
+		)?;
+
+		for (section, section_account) in section_accounts {
+			p.add_account(section, section_account.into());
+		}
+
+		Ok(())
+	})
+	.await?;
+
+	Ok(provider)
+}